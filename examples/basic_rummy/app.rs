@@ -1,15 +1,17 @@
+use crate::input::{action_name, Input, InputMode};
 use crossterm::event::KeyCode;
 use rummy::{
-    cards::{deck::DeckConfig, meld::Meldable},
+    cards::{deck::DeckConfig, meld::Meld},
     game::{
-        action::{
-            DiscardAction, DrawDeckAction, DrawDiscardPileAction, FormMeldAction, GameAction,
-            LayOffAction,
-        },
+        action::{DrawDeckAction, DrawDiscardPileAction, GameAction},
+        danger::{discard_dangers, DiscardDanger},
         error::GameError,
+        hints::hints,
         r#trait::Game,
         state::GamePhase,
-        variants::basic::{config::BasicConfig, game::BasicRummyGame},
+        tracker::CardTracker,
+        variants::basic::{config::BasicConfig, game::BasicRummyGame, score::BasicScore},
+        view::PlayerView,
     },
 };
 
@@ -27,50 +29,59 @@ pub enum AppState {
     Error(String),
 }
 
-#[derive(Debug, Clone, PartialEq)]
-pub enum InputMode {
-    Normal,
-    DrawChoice,
-    PlayChoice,
-    LayOffCardIndex,
-    LayOffTargetPlayer,
-    LayOffTargetMeld,
-    MeldCardSelection,
-    DiscardCardIndex,
-}
-
 pub struct App {
     pub game: Option<BasicRummyGame>,
     pub state: AppState,
-    pub input_mode: InputMode,
-    pub input_buffer: String,
-    pub selected_cards: Vec<usize>,
-    pub layoff_data: LayOffData,
+    pub input: Input,
     pub error_message: Option<String>,
     pub should_quit: bool,
 }
 
-#[derive(Default)]
-pub struct LayOffData {
-    pub card_index: Option<usize>,
-    pub target_player_index: Option<usize>,
-    pub target_meld_index: Option<usize>,
-}
-
 impl App {
     pub fn new() -> Self {
         Self {
             game: None,
             state: AppState::MainMenu,
-            input_mode: InputMode::Normal,
-            input_buffer: String::new(),
-            selected_cards: Vec::new(),
-            layoff_data: LayOffData::default(),
+            input: Input::new(),
             error_message: None,
             should_quit: false,
         }
     }
 
+    /// Get the view of the game for the player whose turn it is.
+    pub fn view(&self) -> Option<PlayerView<BasicScore>> {
+        let state = self.game.as_ref()?.get_state();
+        Some(state.view_for(state.get_current_player().ok().map(|p| p.id())))
+    }
+
+    /// Get how risky discarding each card in the current player's hand is, judging only by what's on the table.
+    pub fn discard_dangers(&self) -> Vec<DiscardDanger> {
+        match (self.game.as_ref(), self.current_tracker()) {
+            (Some(game), Some(tracker)) => discard_dangers(game, &tracker),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Show the best hint for the current player in the status bar.
+    fn show_hint(&mut self) {
+        let hint = match (self.game.as_ref(), self.current_tracker()) {
+            (Some(game), Some(tracker)) => hints(game, &tracker).into_iter().next(),
+            _ => None,
+        };
+        if let Some(hint) = hint {
+            self.error_message = Some(format!("Hint: {hint}"));
+        }
+    }
+
+    /// Get a tracker for the current player, synced with what they can see.
+    fn current_tracker(&self) -> Option<CardTracker> {
+        let state = self.game.as_ref()?.get_state();
+        let player_id = state.get_current_player().ok()?.id();
+        let mut tracker = CardTracker::new(player_id, state.deck().config());
+        tracker.sync(&state.view_for(Some(player_id)));
+        Some(tracker)
+    }
+
     fn setup_game(&mut self) -> Result<(), GameError> {
         let player_ids = vec![0, 1];
         let deck_config = DeckConfig {
@@ -83,6 +94,7 @@ impl App {
             deal_amount: None,
             draw_deck_amount: None,
             draw_discard_pile_amount: None,
+            quit_hand_policy: None,
         };
         let mut game = BasicRummyGame::new(player_ids, game_config, deck_config).unwrap();
         game.next_round()?;
@@ -95,8 +107,7 @@ impl App {
         match key {
             KeyCode::Char('q') => self.should_quit = true,
             KeyCode::Esc => {
-                self.input_buffer.clear();
-                self.input_mode = InputMode::Normal;
+                self.input.reset();
                 if let Some(ref game) = self.game {
                     match game.get_state().phase() {
                         GamePhase::Draw => self.state = AppState::DrawPhase,
@@ -110,9 +121,9 @@ impl App {
                 AppState::MainMenu => self.handle_main_menu_input(key),
                 AppState::DrawPhase => self.handle_draw_input(key),
                 AppState::PlayPhase => self.handle_play_input(key),
-                AppState::LayOffInput => self.handle_layoff_input(key),
-                AppState::FormMeldInput => self.handle_meld_input(key),
-                AppState::DiscardInput => self.handle_discard_input(key),
+                AppState::LayOffInput | AppState::FormMeldInput | AppState::DiscardInput => {
+                    self.handle_prompt_input(key)
+                }
                 AppState::RoundEnd => self.handle_round_end_input(key),
                 AppState::GameEnd => self.handle_game_end_input(key),
                 _ => {}
@@ -133,28 +144,20 @@ impl App {
     fn handle_draw_input(&mut self, key: KeyCode) {
         match key {
             KeyCode::Char('1') => {
-                if let Some(ref mut game) = self.game {
-                    let action = GameAction::DrawDeck(DrawDeckAction {});
-                    if let Err(e) = game.execute_action(action) {
-                        self.error_message = Some(format!("Draw failed: {e:?}"));
-                    }
-                    self.update_game_state();
-                }
+                self.execute(GameAction::DrawDeck(DrawDeckAction {}));
             }
             KeyCode::Char('2') => {
-                if let Some(ref mut game) = self.game {
+                if let Some(ref game) = self.game {
                     if !game.get_state().deck().discard_pile().is_empty() {
-                        let action =
-                            GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(1) });
-                        if let Err(e) = game.execute_action(action) {
-                            self.error_message = Some(format!("Draw failed: {e:?}"));
-                        }
-                        self.update_game_state();
+                        self.execute(GameAction::DrawDiscardPile(DrawDiscardPileAction {
+                            count: Some(1),
+                        }));
                     } else {
                         self.error_message = Some("Discard pile is empty!".to_string());
                     }
                 }
             }
+            KeyCode::Char('h') => self.show_hint(),
             _ => {}
         }
     }
@@ -163,131 +166,32 @@ impl App {
         match key {
             KeyCode::Char('1') => {
                 self.state = AppState::LayOffInput;
-                self.input_mode = InputMode::LayOffCardIndex;
-                self.layoff_data = LayOffData::default();
+                self.input.start(InputMode::LayOffCardIndex);
             }
             KeyCode::Char('2') => {
                 self.state = AppState::FormMeldInput;
-                self.input_mode = InputMode::MeldCardSelection;
-                self.selected_cards.clear();
+                self.input.start(InputMode::MeldCardSelection);
             }
             KeyCode::Char('3') => {
                 self.state = AppState::DiscardInput;
-                self.input_mode = InputMode::DiscardCardIndex;
+                self.input.start(InputMode::DiscardCardIndex);
             }
             KeyCode::Char('4') => {
                 self.execute_sort_hand();
             }
+            KeyCode::Char('h') => self.show_hint(),
             _ => {}
         }
     }
 
-    fn handle_layoff_input(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.input_buffer.push(c);
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Enter => {
-                if let Ok(value) = self.input_buffer.parse::<usize>() {
-                    match self.input_mode {
-                        InputMode::LayOffCardIndex => {
-                            if let Some(ref game) = self.game {
-                                let hand_size =
-                                    game.get_state().get_current_player().unwrap().cards().len();
-                                if value < hand_size {
-                                    self.layoff_data.card_index = Some(value);
-                                    self.input_mode = InputMode::LayOffTargetPlayer;
-                                    self.input_buffer.clear();
-                                } else {
-                                    self.error_message =
-                                        Some("Card index out of bounds".to_string());
-                                }
-                            }
-                        }
-                        InputMode::LayOffTargetPlayer => {
-                            if let Some(ref game) = self.game {
-                                let player_count = game.get_state().players().len();
-                                if value < player_count {
-                                    self.layoff_data.target_player_index = Some(value);
-                                    self.input_mode = InputMode::LayOffTargetMeld;
-                                    self.input_buffer.clear();
-                                } else {
-                                    self.error_message =
-                                        Some("Player index out of bounds".to_string());
-                                }
-                            }
-                        }
-                        InputMode::LayOffTargetMeld => {
-                            if let Some(ref game) = self.game {
-                                let target_player = self.layoff_data.target_player_index.unwrap();
-                                let meld_count =
-                                    game.get_state().players()[target_player].melds().len();
-                                if value < meld_count {
-                                    self.layoff_data.target_meld_index = Some(value);
-                                    self.execute_layoff();
-                                } else {
-                                    self.error_message =
-                                        Some("Meld index out of bounds".to_string());
-                                }
-                            }
-                        }
-                        _ => {}
-                    }
-                } else {
-                    self.error_message = Some("Invalid number".to_string());
-                }
-            }
-            _ => {}
-        }
-    }
-
-    fn handle_meld_input(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.input_buffer.push(c);
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Enter => {
-                if let Ok(card_index) = self.input_buffer.parse::<usize>() {
-                    if let Some(ref game) = self.game {
-                        let hand_size =
-                            game.get_state().get_current_player().unwrap().cards().len();
-                        if card_index < hand_size && !self.selected_cards.contains(&card_index) {
-                            self.selected_cards.push(card_index);
-                            self.input_buffer.clear();
-                        } else {
-                            self.error_message =
-                                Some("Invalid or duplicate card index".to_string());
-                        }
-                    }
-                }
-            }
-            KeyCode::Char('d') | KeyCode::Char('D') => {
-                self.execute_form_meld();
-            }
-            _ => {}
-        }
-    }
-
-    fn handle_discard_input(&mut self, key: KeyCode) {
-        match key {
-            KeyCode::Char(c) if c.is_ascii_digit() => {
-                self.input_buffer.push(c);
-            }
-            KeyCode::Backspace => {
-                self.input_buffer.pop();
-            }
-            KeyCode::Enter => {
-                if let Ok(card_index) = self.input_buffer.parse::<usize>() {
-                    self.execute_discard(card_index);
-                }
-            }
-            _ => {}
+    fn handle_prompt_input(&mut self, key: KeyCode) {
+        let Some(view) = self.view() else {
+            return;
+        };
+        match self.input.handle_key(key, &view) {
+            Ok(Some(action)) => self.execute(action),
+            Ok(None) => {}
+            Err(msg) => self.error_message = Some(msg),
         }
     }
 
@@ -323,64 +227,25 @@ impl App {
         }
     }
 
-    fn execute_layoff(&mut self) {
-        if let Some(ref mut game) = self.game {
-            let action = LayOffAction {
-                card_index: self.layoff_data.card_index.unwrap(),
-                target_player_index: self.layoff_data.target_player_index.unwrap(),
-                target_meld_index: self.layoff_data.target_meld_index.unwrap(),
-            };
-
-            match game.execute_action(GameAction::LayOff(action)) {
-                Ok(_) => {
-                    self.error_message = Some("Layoff successful!".to_string());
-                }
-                Err(e) => {
-                    self.error_message = Some(format!("Layoff failed: {e:?}"));
-                }
-            }
-            self.update_game_state();
-        }
-    }
-
-    fn execute_form_meld(&mut self) {
+    fn execute(&mut self, action: GameAction) {
         if let Some(ref mut game) = self.game {
-            let action = FormMeldAction {
-                card_indices: self.selected_cards.clone(),
-            };
-
-            match game.execute_action(GameAction::FormMeld(action)) {
-                Ok(_) => {
-                    self.error_message = Some("Meld formed successfully!".to_string());
-                }
-                Err(e) => {
-                    self.error_message = Some(format!("Meld failed: {e:?}"));
+            let name = action_name(&action);
+            // explain why a meld is rejected, rather than just showing the error
+            let report = match (&action, game.get_state().get_current_player()) {
+                (GameAction::FormMeld(meld), Ok(player)) => {
+                    Meld::report(player.cards(), &meld.card_indices).ok()
                 }
-            }
-            self.selected_cards.clear();
-            self.update_game_state();
-        }
-    }
-
-    fn execute_discard(&mut self, card_index: usize) {
-        if let Some(ref mut game) = self.game {
-            let hand_size = game.get_state().get_current_player().unwrap().cards().len();
-            if card_index >= hand_size {
-                self.error_message = Some("Card index out of bounds".to_string());
-                return;
-            }
-
-            let action = DiscardAction {
-                card_index,
-                declare_going_out: None,
+                _ => None,
             };
-
-            match game.execute_action(GameAction::Discard(action)) {
+            match game.execute_action(action) {
                 Ok(_) => {
-                    self.error_message = Some("Discard successful!".to_string());
+                    self.error_message = Some(format!("{name} successful!"));
                 }
                 Err(e) => {
-                    self.error_message = Some(format!("Discard failed: {e:?}"));
+                    self.error_message = match report.filter(|report| !report.is_valid()) {
+                        Some(report) => Some(format!("{name} failed: {report}")),
+                        None => Some(format!("{name} failed: {e:?}")),
+                    };
                 }
             }
             self.update_game_state();
@@ -395,8 +260,7 @@ impl App {
                 GamePhase::RoundEnd => self.state = AppState::RoundEnd,
                 GamePhase::GameEnd => self.state = AppState::GameEnd,
             }
-            self.input_mode = InputMode::Normal;
-            self.input_buffer.clear();
+            self.input.reset();
         }
     }
 }
@@ -0,0 +1,160 @@
+//! The input for the lay off, meld and discard prompts, which turns key presses into actions.
+//!
+//! This is shared with the networked client (`examples/rummy_client`), so it only relies on a `PlayerView`.
+
+use crossterm::event::KeyCode;
+use rummy::game::{
+    action::{DiscardAction, FormMeldAction, GameAction, LayOffAction},
+    variants::basic::score::BasicScore,
+    view::PlayerView,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputMode {
+    Normal,
+    #[allow(dead_code)]
+    DrawChoice,
+    #[allow(dead_code)]
+    PlayChoice,
+    LayOffCardIndex,
+    LayOffTargetPlayer,
+    LayOffTargetMeld,
+    MeldCardSelection,
+    DiscardCardIndex,
+}
+
+#[derive(Default)]
+pub struct LayOffData {
+    pub card_index: Option<usize>,
+    pub target_player_index: Option<usize>,
+    pub target_meld_index: Option<usize>,
+}
+
+pub struct Input {
+    pub mode: InputMode,
+    pub buffer: String,
+    pub selected_cards: Vec<usize>,
+    pub layoff_data: LayOffData,
+}
+
+impl Input {
+    pub fn new() -> Self {
+        Self {
+            mode: InputMode::Normal,
+            buffer: String::new(),
+            selected_cards: Vec::new(),
+            layoff_data: LayOffData::default(),
+        }
+    }
+
+    /// Start a prompt, clearing anything entered for the previous one.
+    pub fn start(&mut self, mode: InputMode) {
+        *self = Self::new();
+        self.mode = mode;
+    }
+
+    /// Stop prompting.
+    pub fn reset(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Handle a key press for the current prompt, using `view` to check what's entered.
+    ///
+    /// Returns the action once the prompt is complete, or an error message if the input is invalid.
+    pub fn handle_key(
+        &mut self,
+        key: KeyCode,
+        view: &PlayerView<BasicScore>,
+    ) -> Result<Option<GameAction>, String> {
+        match key {
+            KeyCode::Char(c) if c.is_ascii_digit() => {
+                self.buffer.push(c);
+                Ok(None)
+            }
+            KeyCode::Backspace => {
+                self.buffer.pop();
+                Ok(None)
+            }
+            KeyCode::Char('d') | KeyCode::Char('D')
+                if self.mode == InputMode::MeldCardSelection =>
+            {
+                let card_indices = std::mem::take(&mut self.selected_cards);
+                Ok(Some(GameAction::FormMeld(FormMeldAction { card_indices })))
+            }
+            KeyCode::Enter => {
+                let Ok(value) = self.buffer.parse::<usize>() else {
+                    return Err("Invalid number".to_string());
+                };
+                self.handle_value(value, view)
+            }
+            _ => Ok(None),
+        }
+    }
+
+    fn handle_value(
+        &mut self,
+        value: usize,
+        view: &PlayerView<BasicScore>,
+    ) -> Result<Option<GameAction>, String> {
+        match self.mode {
+            InputMode::LayOffCardIndex => {
+                if value >= view.hand.len() {
+                    return Err("Card index out of bounds".to_string());
+                }
+                self.layoff_data.card_index = Some(value);
+                self.mode = InputMode::LayOffTargetPlayer;
+            }
+            InputMode::LayOffTargetPlayer => {
+                if value >= view.players.len() {
+                    return Err("Player index out of bounds".to_string());
+                }
+                self.layoff_data.target_player_index = Some(value);
+                self.mode = InputMode::LayOffTargetMeld;
+            }
+            InputMode::LayOffTargetMeld => {
+                // UNWRAP: the target player is always entered before the meld
+                let target_player = self.layoff_data.target_player_index.unwrap();
+                if value >= view.players[target_player].melds.len() {
+                    return Err("Meld index out of bounds".to_string());
+                }
+                self.layoff_data.target_meld_index = Some(value);
+                self.buffer.clear();
+                return Ok(Some(GameAction::LayOff(LayOffAction {
+                    // UNWRAP: the card is always entered before the target player
+                    card_index: self.layoff_data.card_index.unwrap(),
+                    target_player_index: target_player,
+                    target_meld_index: value,
+                })));
+            }
+            InputMode::MeldCardSelection => {
+                if value >= view.hand.len() || self.selected_cards.contains(&value) {
+                    return Err("Invalid or duplicate card index".to_string());
+                }
+                self.selected_cards.push(value);
+            }
+            InputMode::DiscardCardIndex => {
+                if value >= view.hand.len() {
+                    return Err("Card index out of bounds".to_string());
+                }
+                self.buffer.clear();
+                return Ok(Some(GameAction::Discard(DiscardAction {
+                    card_index: value,
+                    declare_going_out: None,
+                })));
+            }
+            InputMode::Normal | InputMode::DrawChoice | InputMode::PlayChoice => return Ok(None),
+        }
+        self.buffer.clear();
+        Ok(None)
+    }
+}
+
+/// The name of an action, for status messages.
+pub fn action_name(action: &GameAction) -> &'static str {
+    match action {
+        GameAction::DrawDeck(_) | GameAction::DrawDiscardPile(_) => "Draw",
+        GameAction::LayOff(_) => "Layoff",
+        GameAction::FormMeld(_) | GameAction::FormMelds(_) => "Meld",
+        GameAction::Discard(_) => "Discard",
+    }
+}
@@ -1,26 +1,68 @@
-use crate::app::App;
+use crate::input::Input;
 use ratatui::{
     layout::Rect,
-    style::{Modifier, Style},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
-use rummy::cards::meld::Meldable;
+use rummy::game::danger::{DangerReason, DiscardDanger};
 
-pub fn render_discard_input(f: &mut Frame, area: Rect, app: &App) {
-    let content = vec![
+/// Dangers with at least this score are warned about.
+const RISKY_SCORE: f64 = 0.5;
+
+pub fn render_discard_input(f: &mut Frame, area: Rect, input: &Input, dangers: &[DiscardDanger]) {
+    let mut content = vec![
         Line::from(Span::styled(
             "Discard Action",
             Style::default().add_modifier(Modifier::BOLD),
         )),
         Line::from(""),
         Line::from("Enter card index to discard:"),
-        Line::from(format!("> {}", app.input_buffer)),
+        Line::from(format!("> {}", input.buffer)),
     ];
 
+    let risky: Vec<_> = dangers.iter().filter(|d| d.score >= RISKY_SCORE).collect();
+    if !risky.is_empty() {
+        let warning = Style::default().fg(Color::Yellow);
+        content.push(Line::from(""));
+        content.push(Line::from(Span::styled("⚠ Risky discards:", warning)));
+        for danger in risky {
+            content.push(Line::from(Span::styled(
+                format!(
+                    "  {}: {} - {}",
+                    danger.card_index,
+                    danger.card,
+                    describe(&danger.reasons)
+                ),
+                warning,
+            )));
+        }
+    }
+
     let paragraph = Paragraph::new(content)
         .block(Block::default().borders(Borders::ALL).title("Discard"))
         .wrap(Wrap { trim: true });
     f.render_widget(paragraph, area);
 }
+
+/// Describe why a discard is risky, leaving out the live partners that nearly every card has.
+fn describe(reasons: &[DangerReason]) -> String {
+    let described: Vec<_> = reasons
+        .iter()
+        .filter_map(|reason| match reason {
+            DangerReason::Wildcard => Some("it's a wildcard".to_string()),
+            DangerReason::ExtendsMeld { player_id, .. } => {
+                Some(format!("it fits player {player_id}'s meld"))
+            }
+            DangerReason::NextPlayerHolds { card } => {
+                Some(format!("the next player picked up {card}"))
+            }
+            DangerReason::LivePartners { .. } => None,
+        })
+        .collect();
+    match described.is_empty() {
+        true => "many cards that go with it are still out".to_string(),
+        false => described.join(", "),
+    }
+}
@@ -5,7 +5,6 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use rummy::cards::meld::Meldable;
 
 pub fn render_error(f: &mut Frame, area: Rect, error_msg: &str) {
     let content = vec![
@@ -1,4 +1,4 @@
-use crate::app::App;
+use crate::input::Input;
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -6,9 +6,8 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph, Wrap},
     Frame,
 };
-use rummy::cards::meld::Meldable;
 
-pub fn render_meld_input(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_meld_input(f: &mut Frame, area: Rect, input: &Input) {
     let content = vec![
         Line::from(Span::styled(
             "Form Meld Action",
@@ -16,9 +15,9 @@ pub fn render_meld_input(f: &mut Frame, area: Rect, app: &App) {
         )),
         Line::from(""),
         Line::from("Enter card indices to add to meld (press D when done):"),
-        Line::from(format!("> {}", app.input_buffer)),
+        Line::from(format!("> {}", input.buffer)),
         Line::from(""),
-        Line::from(format!("Selected cards: {:?}", app.selected_cards)),
+        Line::from(format!("Selected cards: {:?}", input.selected_cards)),
     ];
 
     let paragraph = Paragraph::new(content)
@@ -5,7 +5,6 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use rummy::cards::meld::Meldable;
 
 pub fn render_main_menu(f: &mut Frame, area: Rect) {
     let welcome_text = vec![
@@ -1,4 +1,3 @@
-use crate::app::App;
 use ratatui::{
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
@@ -6,37 +5,34 @@ use ratatui::{
     widgets::{Block, Borders, Paragraph},
     Frame,
 };
-use rummy::{cards::meld::Meldable, game::r#trait::Game};
+use rummy::game::{variants::basic::score::BasicScore, view::PlayerView};
 
-pub fn render_round_end(f: &mut Frame, area: Rect, app: &App) {
-    if let Some(ref game) = app.game {
-        let gamestate = game.get_state();
-        let latest_score = gamestate.round_scores().get(&gamestate.current_round());
+pub fn render_round_end(f: &mut Frame, area: Rect, view: &PlayerView<BasicScore>) {
+    let latest_score = view.round_scores.get(&view.current_round);
 
-        let content = vec![
-            Line::from(Span::styled(
-                "Round Ended!",
-                Style::default()
-                    .add_modifier(Modifier::BOLD)
-                    .fg(Color::Green),
-            )),
-            Line::from(""),
-            Line::from(format!("Round {} complete", gamestate.current_round())),
-            Line::from(format!("Scores: {latest_score:?}")),
-            Line::from(""),
-            Line::from("Press Enter to continue to next round..."),
-        ];
+    let content = vec![
+        Line::from(Span::styled(
+            "Round Ended!",
+            Style::default()
+                .add_modifier(Modifier::BOLD)
+                .fg(Color::Green),
+        )),
+        Line::from(""),
+        Line::from(format!("Round {} complete", view.current_round)),
+        Line::from(format!("Scores: {latest_score:?}")),
+        Line::from(""),
+        Line::from("Press Enter to continue to next round..."),
+    ];
 
-        let paragraph = Paragraph::new(content).alignment(Alignment::Center).block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title("Round Complete"),
-        );
-        f.render_widget(paragraph, area);
-    }
+    let paragraph = Paragraph::new(content).alignment(Alignment::Center).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .title("Round Complete"),
+    );
+    f.render_widget(paragraph, area);
 }
 
-pub fn render_game_end(f: &mut Frame, area: Rect, app: &App) {
+pub fn render_game_end(f: &mut Frame, area: Rect) {
     let content = vec![
         Line::from(Span::styled(
             "Game Over!",
@@ -44,8 +40,6 @@ pub fn render_game_end(f: &mut Frame, area: Rect, app: &App) {
         )),
         Line::from(""),
         Line::from("Thanks for playing!"),
-        Line::from(""),
-        Line::from("Press Enter to return to main menu..."),
     ];
 
     let paragraph = Paragraph::new(content)
@@ -19,7 +19,7 @@ pub fn ui(f: &mut Frame, app: &App) {
             Constraint::Length(3), // Status/Input
             Constraint::Length(3), // Instructions
         ])
-        .split(f.size());
+        .split(f.area());
 
     let title = Paragraph::new("🃏 Rummy Game 🃏")
         .style(
@@ -31,17 +31,21 @@ pub fn ui(f: &mut Frame, app: &App) {
         .block(Block::default().borders(Borders::ALL));
     f.render_widget(title, chunks[0]);
 
-    match app.state {
-        AppState::MainMenu => render_main_menu(f, chunks[1]),
-        AppState::DrawPhase | AppState::PlayPhase | AppState::GamePlay => {
-            render_game_state(f, chunks[1], app)
+    let view = app.view();
+    match (&app.state, &view) {
+        (AppState::MainMenu, _) => render_main_menu(f, chunks[1]),
+        (AppState::DrawPhase | AppState::PlayPhase | AppState::GamePlay, Some(view)) => {
+            render_game_state(f, chunks[1], view)
         }
-        AppState::LayOffInput => render_layoff_input(f, chunks[1], app),
-        AppState::FormMeldInput => render_meld_input(f, chunks[1], app),
-        AppState::DiscardInput => render_discard_input(f, chunks[1], app),
-        AppState::RoundEnd => render_round_end(f, chunks[1], app),
-        AppState::GameEnd => render_game_end(f, chunks[1], app),
-        AppState::Error(ref msg) => render_error(f, chunks[1], msg),
+        (AppState::LayOffInput, Some(view)) => render_layoff_input(f, chunks[1], &app.input, view),
+        (AppState::FormMeldInput, _) => render_meld_input(f, chunks[1], &app.input),
+        (AppState::DiscardInput, _) => {
+            render_discard_input(f, chunks[1], &app.input, &app.discard_dangers())
+        }
+        (AppState::RoundEnd, Some(view)) => render_round_end(f, chunks[1], view),
+        (AppState::GameEnd, _) => render_game_end(f, chunks[1]),
+        (AppState::Error(msg), _) => render_error(f, chunks[1], msg),
+        _ => {}
     }
 
     render_status_bar(f, chunks[2], app);
@@ -49,14 +53,14 @@ pub fn ui(f: &mut Frame, app: &App) {
 }
 
 fn render_status_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
-    use crate::app::InputMode;
+    use crate::input::InputMode;
 
     let status = if let Some(ref msg) = app.error_message {
         msg.clone()
     } else {
-        match app.input_mode {
+        match app.input.mode {
             InputMode::Normal => "Ready".to_string(),
-            _ => format!("Input: {}", app.input_buffer),
+            _ => format!("Input: {}", app.input.buffer),
         }
     };
 
@@ -69,9 +73,9 @@ fn render_status_bar(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
 fn render_instructions(f: &mut Frame, area: ratatui::layout::Rect, app: &App) {
     let instructions = match app.state {
         AppState::MainMenu => "Press Enter to start new game • Q to quit",
-        AppState::DrawPhase => "Press 1 for deck, 2 for discard pile • ESC to cancel • Q to quit",
+        AppState::DrawPhase => "Press 1 for deck, 2 for discard pile, H for a hint • ESC to cancel • Q to quit",
         AppState::PlayPhase => {
-            "Press 1 for layoff, 2 for meld, 3 for discard, 4 to sort your hand • Q to quit"
+            "Press 1 for layoff, 2 for meld, 3 for discard, 4 to sort your hand, H for a hint • Q to quit"
         }
         AppState::LayOffInput => {
             "Enter card index, then target player, meld, position • ESC to cancel"
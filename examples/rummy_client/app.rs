@@ -0,0 +1,260 @@
+use crate::input::{action_name, Input, InputMode};
+use crossterm::event::KeyCode;
+use rummy::{
+    game::{
+        action::{DrawDeckAction, DrawDiscardPileAction, GameAction, GameInteractions},
+        event::GameEvent,
+        state::GamePhase,
+        variants::basic::score::BasicScore,
+        view::PlayerView,
+    },
+    server::protocol::{ClientMessage, GameId, ServerMessage},
+};
+use std::collections::VecDeque;
+use tokio::sync::mpsc;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum AppState {
+    Lobby,
+    Waiting,
+    DrawPhase,
+    PlayPhase,
+    LayOffInput,
+    FormMeldInput,
+    DiscardInput,
+    RoundEnd,
+    GameEnd,
+    Error(String),
+}
+
+/// The seat the client took.
+pub struct Seat {
+    pub game_id: GameId,
+    pub player_id: usize,
+    pub token: String,
+}
+
+pub struct App {
+    pub seat: Seat,
+    /// The latest view of the game, once it has started.
+    pub view: Option<PlayerView<BasicScore>>,
+    /// The IDs of the players seated in the game, from the last time the lobby was listed.
+    pub lobby_player_ids: Vec<usize>,
+    pub state: AppState,
+    pub input: Input,
+    pub status_message: Option<String>,
+    pub should_quit: bool,
+    outgoing: mpsc::UnboundedSender<ClientMessage>,
+    /// The names of the requests waiting for a reply, in the order they were sent.
+    pending_requests: VecDeque<&'static str>,
+}
+
+impl App {
+    pub fn new(seat: Seat, outgoing: mpsc::UnboundedSender<ClientMessage>) -> Self {
+        let mut app = Self {
+            lobby_player_ids: vec![seat.player_id],
+            seat,
+            view: None,
+            state: AppState::Lobby,
+            input: Input::new(),
+            status_message: None,
+            should_quit: false,
+            outgoing,
+            pending_requests: VecDeque::new(),
+        };
+        app.send("List games", ClientMessage::ListGames);
+        app
+    }
+
+    pub fn handle_message(&mut self, message: ServerMessage) {
+        match message {
+            ServerMessage::View { view } => {
+                self.view = Some(view);
+                self.update_game_state();
+            }
+            ServerMessage::Event { event } => match event {
+                GameEvent::Paused => {
+                    self.status_message = Some("Paused while a player reconnects".to_string());
+                }
+                GameEvent::Resumed => self.status_message = Some("Resumed!".to_string()),
+                GameEvent::PlayerJoined { player_id } => {
+                    self.status_message = Some(format!("Player {player_id} joined"));
+                }
+                GameEvent::PlayerQuit { player_id } => {
+                    self.status_message = Some(format!("Player {player_id} left"));
+                }
+                _ => {}
+            },
+            ServerMessage::Games { games } => {
+                self.pending_requests.pop_front();
+                if let Some(game) = games.into_iter().find(|g| g.game_id == self.seat.game_id) {
+                    self.lobby_player_ids = game.player_ids;
+                }
+            }
+            ServerMessage::Ok => {
+                let name = self.pending_requests.pop_front().unwrap_or("Request");
+                self.status_message = Some(format!("{name} successful!"));
+            }
+            ServerMessage::Error { message } => {
+                let name = self.pending_requests.pop_front().unwrap_or("Request");
+                self.status_message = Some(format!("{name} failed: {message}"));
+            }
+            _ => {
+                self.pending_requests.pop_front();
+            }
+        }
+    }
+
+    /// Handle the connection to the server closing.
+    pub fn disconnected(&mut self) {
+        if self.state != AppState::GameEnd {
+            self.state = AppState::Error("Disconnected from the server".to_string());
+        }
+    }
+
+    pub fn handle_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('q') => self.should_quit = true,
+            KeyCode::Esc => {
+                self.input.reset();
+                self.update_game_state();
+            }
+            _ => match self.state {
+                AppState::Lobby => self.handle_lobby_input(key),
+                AppState::DrawPhase => self.handle_draw_input(key),
+                AppState::PlayPhase => self.handle_play_input(key),
+                AppState::LayOffInput | AppState::FormMeldInput | AppState::DiscardInput => {
+                    self.handle_prompt_input(key)
+                }
+                AppState::RoundEnd => self.handle_round_end_input(key),
+                AppState::GameEnd => self.handle_game_end_input(key),
+                _ => {}
+            },
+        }
+    }
+
+    fn handle_lobby_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Enter => self.send("Start", ClientMessage::StartGame),
+            KeyCode::Char('r') => self.send("List games", ClientMessage::ListGames),
+            _ => {}
+        }
+    }
+
+    fn handle_draw_input(&mut self, key: KeyCode) {
+        let Some(ref view) = self.view else {
+            return;
+        };
+        match key {
+            KeyCode::Char('1') => self.act(GameAction::DrawDeck(DrawDeckAction {})),
+            KeyCode::Char('2') => {
+                if !view.discard_pile.is_empty() {
+                    self.act(GameAction::DrawDiscardPile(DrawDiscardPileAction {
+                        count: Some(1),
+                    }));
+                } else {
+                    self.status_message = Some("Discard pile is empty!".to_string());
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_play_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Char('1') => {
+                self.state = AppState::LayOffInput;
+                self.input.start(InputMode::LayOffCardIndex);
+            }
+            KeyCode::Char('2') => {
+                self.state = AppState::FormMeldInput;
+                self.input.start(InputMode::MeldCardSelection);
+            }
+            KeyCode::Char('3') => {
+                self.state = AppState::DiscardInput;
+                self.input.start(InputMode::DiscardCardIndex);
+            }
+            KeyCode::Char('4') => {
+                self.sort_hand();
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_prompt_input(&mut self, key: KeyCode) {
+        let Some(ref view) = self.view else {
+            return;
+        };
+        match self.input.handle_key(key, view) {
+            Ok(Some(action)) => {
+                self.act(action);
+                self.input.reset();
+                self.state = AppState::PlayPhase;
+            }
+            Ok(None) => {}
+            Err(msg) => self.status_message = Some(msg),
+        }
+    }
+
+    fn handle_round_end_input(&mut self, key: KeyCode) {
+        if key == KeyCode::Enter {
+            self.send("Next round", ClientMessage::NextRound);
+        }
+    }
+
+    fn handle_game_end_input(&mut self, key: KeyCode) {
+        if key == KeyCode::Enter {
+            self.should_quit = true;
+        }
+    }
+
+    fn sort_hand(&mut self) {
+        let Some(ref view) = self.view else {
+            return;
+        };
+        let mut new_arrangement = view.hand.clone();
+        new_arrangement.sort();
+        let interaction = GameInteractions::HandRearrangement {
+            player_id: self.seat.player_id,
+            new_arrangement,
+        };
+        self.send("Sort", ClientMessage::Interact { interaction });
+    }
+
+    fn act(&mut self, action: GameAction) {
+        let name = action_name(&action);
+        let interaction = GameInteractions::Action(action);
+        self.send(name, ClientMessage::Interact { interaction });
+    }
+
+    fn send(&mut self, name: &'static str, message: ClientMessage) {
+        // the connection closing is handled when receiving from it
+        if self.outgoing.send(message).is_ok() {
+            self.pending_requests.push_back(name);
+        }
+    }
+
+    /// Move to the state matching the latest view, keeping any prompt open while it's still our play phase.
+    fn update_game_state(&mut self) {
+        let Some(ref view) = self.view else {
+            self.state = AppState::Lobby;
+            return;
+        };
+        let our_turn = view.current_player_id == Some(self.seat.player_id);
+        let state = match view.phase {
+            GamePhase::Draw if our_turn => AppState::DrawPhase,
+            GamePhase::Play if our_turn => AppState::PlayPhase,
+            GamePhase::Draw | GamePhase::Play => AppState::Waiting,
+            GamePhase::RoundEnd => AppState::RoundEnd,
+            GamePhase::GameEnd => AppState::GameEnd,
+        };
+        let prompting = matches!(
+            self.state,
+            AppState::LayOffInput | AppState::FormMeldInput | AppState::DiscardInput
+        );
+        if !(prompting && state == AppState::PlayPhase) {
+            self.state = state;
+            self.input.reset();
+        }
+    }
+}
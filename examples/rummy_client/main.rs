@@ -0,0 +1,226 @@
+//! A terminal client for `rummy-server`, which plays a single seat of a game over the network.
+//!
+//! Start a server with `cargo run --features server --bin rummy-server`, then run a client for each player:
+//! - `cargo run --features server --example rummy_client -- [address]` creates a game and joins it.
+//! - `cargo run --features server --example rummy_client -- [address] join <game_id>` joins a game.
+//! - `cargo run --features server --example rummy_client -- [address] reconnect <game_id> <token>`
+//!   takes back a seat after disconnecting.
+//!
+//! The address defaults to `127.0.0.1:7878`.
+
+use crossterm::{
+    event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyEventKind},
+    execute,
+    terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use rummy::server::protocol::{ClientMessage, GameId, ServerMessage};
+use std::{error::Error, io, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{tcp::OwnedReadHalf, TcpStream},
+    sync::mpsc,
+};
+
+mod app;
+#[path = "../basic_rummy/input.rs"]
+mod input;
+mod ui;
+
+use app::{App, Seat};
+use ui::ui;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+
+/// How to take a seat, from the command line.
+enum Command {
+    Create,
+    Join { game_id: GameId },
+    Reconnect { game_id: GameId, token: String },
+}
+
+fn parse_args() -> Result<(String, Command), Box<dyn Error>> {
+    let mut args: Vec<String> = std::env::args().skip(1).collect();
+    let address = match args.first() {
+        Some(arg) if arg != "join" && arg != "reconnect" => args.remove(0),
+        _ => DEFAULT_ADDRESS.to_string(),
+    };
+    let command = match args.as_slice() {
+        [] => Command::Create,
+        [join, game_id] if join == "join" => Command::Join {
+            game_id: game_id.parse()?,
+        },
+        [reconnect, game_id, token] if reconnect == "reconnect" => Command::Reconnect {
+            game_id: game_id.parse()?,
+            token: token.clone(),
+        },
+        _ => {
+            return Err(
+                "usage: rummy_client [address] [join <game_id> | reconnect <game_id> <token>]"
+                    .into(),
+            )
+        }
+    };
+    Ok((address, command))
+}
+
+/// Connect to the server and take a seat, then spawn tasks to send and receive messages.
+async fn connect(
+    address: &str,
+    command: Command,
+) -> Result<
+    (
+        Seat,
+        mpsc::UnboundedSender<ClientMessage>,
+        mpsc::UnboundedReceiver<ServerMessage>,
+    ),
+    Box<dyn Error>,
+> {
+    let (reader, mut writer) = TcpStream::connect(address).await?.into_split();
+    let mut lines = BufReader::new(reader).lines();
+
+    let take_seat = match command {
+        Command::Create => {
+            let ServerMessage::GameCreated { game_id } =
+                request(&mut writer, &mut lines, ClientMessage::CreateGame).await?
+            else {
+                return Err("the game wasn't created".into());
+            };
+            ClientMessage::JoinGame { game_id }
+        }
+        Command::Join { game_id } => ClientMessage::JoinGame { game_id },
+        Command::Reconnect { game_id, token } => ClientMessage::Reconnect { game_id, token },
+    };
+    let ServerMessage::Joined {
+        game_id,
+        player_id,
+        token,
+    } = request(&mut writer, &mut lines, take_seat).await?
+    else {
+        return Err("the seat wasn't taken".into());
+    };
+
+    let (outgoing, mut to_send) = mpsc::unbounded_channel::<ClientMessage>();
+    tokio::spawn(async move {
+        while let Some(message) = to_send.recv().await {
+            if send(&mut writer, &message).await.is_err() {
+                break;
+            }
+        }
+    });
+    let (received, incoming) = mpsc::unbounded_channel();
+    tokio::spawn(async move {
+        while let Ok(Some(line)) = lines.next_line().await {
+            let Ok(message) = serde_json::from_str(&line) else {
+                continue;
+            };
+            if received.send(message).is_err() {
+                break;
+            }
+        }
+    });
+
+    let seat = Seat {
+        game_id,
+        player_id,
+        token,
+    };
+    Ok((seat, outgoing, incoming))
+}
+
+async fn send(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    message: &ClientMessage,
+) -> io::Result<()> {
+    // UNWRAP: client messages always serialize
+    let mut line = serde_json::to_string(message).unwrap();
+    line.push('\n');
+    writer.write_all(line.as_bytes()).await
+}
+
+/// Send a message and wait for its reply, turning an `Error` reply into an `Err`.
+async fn request(
+    writer: &mut (impl AsyncWriteExt + Unpin),
+    lines: &mut Lines<BufReader<OwnedReadHalf>>,
+    message: ClientMessage,
+) -> Result<ServerMessage, Box<dyn Error>> {
+    send(writer, &message).await?;
+    let line = lines
+        .next_line()
+        .await?
+        .ok_or("the server closed the connection")?;
+    match serde_json::from_str(&line)? {
+        ServerMessage::Error { message } => Err(message.into()),
+        reply => Ok(reply),
+    }
+}
+
+fn run_app<B: ratatui::backend::Backend>(
+    terminal: &mut Terminal<B>,
+    app: &mut App,
+    incoming: &mut mpsc::UnboundedReceiver<ServerMessage>,
+) -> io::Result<()> {
+    loop {
+        loop {
+            match incoming.try_recv() {
+                Ok(message) => app.handle_message(message),
+                Err(mpsc::error::TryRecvError::Empty) => break,
+                Err(mpsc::error::TryRecvError::Disconnected) => {
+                    app.disconnected();
+                    break;
+                }
+            }
+        }
+
+        terminal.draw(|f| ui(f, app))?;
+
+        if event::poll(Duration::from_millis(50))? {
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    app.handle_input(key.code);
+                    if app.should_quit {
+                        break;
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn main() -> Result<(), Box<dyn Error>> {
+    let (address, command) = parse_args()?;
+    let runtime = tokio::runtime::Runtime::new()?;
+    let (seat, outgoing, mut incoming) = runtime.block_on(connect(&address, command))?;
+    let mut app = App::new(seat, outgoing);
+
+    // Setup terminal
+    enable_raw_mode()?;
+    let mut stdout = io::stdout();
+    execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
+    let backend = CrosstermBackend::new(stdout);
+    let mut terminal = Terminal::new(backend)?;
+
+    // Run the app
+    let res = run_app(&mut terminal, &mut app, &mut incoming);
+
+    // Restore terminal
+    disable_raw_mode()?;
+    execute!(
+        terminal.backend_mut(),
+        LeaveAlternateScreen,
+        DisableMouseCapture
+    )?;
+    terminal.show_cursor()?;
+
+    if let Err(err) = res {
+        println!("{err:?}");
+    }
+    println!(
+        "To take your seat back, run: rummy_client {address} reconnect {} {}",
+        app.seat.game_id, app.seat.token
+    );
+
+    Ok(())
+}
@@ -0,0 +1,132 @@
+// the main menu is only used by the hot-seat example
+#[allow(dead_code, unused_imports)]
+#[path = "../basic_rummy/ui/components/mod.rs"]
+mod components;
+
+use ratatui::{
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::{
+    app::{App, AppState},
+    input::InputMode,
+};
+use components::*;
+
+pub fn ui(f: &mut Frame, app: &App) {
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Title
+            Constraint::Min(10),   // Main content
+            Constraint::Length(3), // Status/Input
+            Constraint::Length(3), // Instructions
+        ])
+        .split(f.area());
+
+    let title = Paragraph::new(format!(
+        "🃏 Rummy Game {} • Player {} 🃏",
+        app.seat.game_id, app.seat.player_id
+    ))
+    .style(
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD),
+    )
+    .alignment(Alignment::Center)
+    .block(Block::default().borders(Borders::ALL));
+    f.render_widget(title, chunks[0]);
+
+    match (&app.state, &app.view) {
+        (AppState::Lobby, _) => render_lobby(f, chunks[1], app),
+        (AppState::Waiting | AppState::DrawPhase | AppState::PlayPhase, Some(view)) => {
+            render_game_state(f, chunks[1], view)
+        }
+        (AppState::LayOffInput, Some(view)) => render_layoff_input(f, chunks[1], &app.input, view),
+        (AppState::FormMeldInput, _) => render_meld_input(f, chunks[1], &app.input),
+        (AppState::DiscardInput, _) => render_discard_input(f, chunks[1], &app.input, &[]),
+        (AppState::RoundEnd, Some(view)) => render_round_end(f, chunks[1], view),
+        (AppState::GameEnd, _) => render_game_end(f, chunks[1]),
+        (AppState::Error(msg), _) => render_error(f, chunks[1], msg),
+        _ => {}
+    }
+
+    render_status_bar(f, chunks[2], app);
+    render_instructions(f, chunks[3], app);
+}
+
+fn render_lobby(f: &mut Frame, area: Rect, app: &App) {
+    let player_ids = app
+        .lobby_player_ids
+        .iter()
+        .map(|id| id.to_string())
+        .collect::<Vec<_>>()
+        .join(", ");
+
+    let content = vec![
+        Line::from(Span::styled(
+            format!("Waiting in game {}", app.seat.game_id),
+            Style::default()
+                .fg(Color::Green)
+                .add_modifier(Modifier::BOLD),
+        )),
+        Line::from(""),
+        Line::from(format!("Seated players: {player_ids}")),
+        Line::from(""),
+        Line::from(format!("Your seat token: {}", app.seat.token)),
+        Line::from("Keep it to reconnect to your seat."),
+    ];
+
+    let paragraph = Paragraph::new(content)
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Lobby"))
+        .wrap(Wrap { trim: true });
+    f.render_widget(paragraph, area);
+}
+
+fn render_status_bar(f: &mut Frame, area: Rect, app: &App) {
+    let status = if let Some(ref msg) = app.status_message {
+        msg.clone()
+    } else {
+        match app.input.mode {
+            InputMode::Normal => "Ready".to_string(),
+            _ => format!("Input: {}", app.input.buffer),
+        }
+    };
+
+    let status_paragraph = Paragraph::new(status)
+        .style(Style::default().fg(Color::Yellow))
+        .block(Block::default().borders(Borders::ALL).title("Status"));
+    f.render_widget(status_paragraph, area);
+}
+
+fn render_instructions(f: &mut Frame, area: Rect, app: &App) {
+    let instructions = match app.state {
+        AppState::Lobby => {
+            "Press Enter to start the game once everyone has joined • R to refresh • Q to quit"
+        }
+        AppState::Waiting => "Waiting for the other players • Q to quit",
+        AppState::DrawPhase => "Press 1 for deck, 2 for discard pile • ESC to cancel • Q to quit",
+        AppState::PlayPhase => {
+            "Press 1 for layoff, 2 for meld, 3 for discard, 4 to sort your hand • Q to quit"
+        }
+        AppState::LayOffInput => {
+            "Enter card index, then target player, meld, position • ESC to cancel"
+        }
+        AppState::FormMeldInput => "Enter card indices, press D when done • ESC to cancel",
+        AppState::DiscardInput => "Enter card index to discard • ESC to cancel",
+        AppState::RoundEnd => "Press Enter to continue to next round • Q to quit",
+        AppState::GameEnd => "Press Enter or Q to quit",
+        _ => "Q to quit",
+    };
+
+    let instructions_paragraph = Paragraph::new(instructions)
+        .style(Style::default().fg(Color::Gray))
+        .alignment(Alignment::Center)
+        .block(Block::default().borders(Borders::ALL).title("Instructions"));
+    f.render_widget(instructions_paragraph, area);
+}
@@ -0,0 +1,32 @@
+//! Hosts basic Rummy games over TCP and WebSocket, using the JSON protocol in `rummy::server::protocol`.
+//!
+//! Usage: `rummy-server [address] [websocket address]`
+//! (the defaults are `127.0.0.1:7878` and `127.0.0.1:7879`).
+
+use rummy::server::Server;
+use tokio::net::TcpListener;
+
+const DEFAULT_ADDRESS: &str = "127.0.0.1:7878";
+const DEFAULT_WEBSOCKET_ADDRESS: &str = "127.0.0.1:7879";
+
+#[tokio::main]
+async fn main() -> std::io::Result<()> {
+    let mut args = std::env::args().skip(1);
+    let address = args.next().unwrap_or_else(|| DEFAULT_ADDRESS.to_string());
+    let websocket_address = args
+        .next()
+        .unwrap_or_else(|| DEFAULT_WEBSOCKET_ADDRESS.to_string());
+    let listener = TcpListener::bind(&address).await?;
+    let websocket_listener = TcpListener::bind(&websocket_address).await?;
+    println!("Listening on {}", listener.local_addr()?);
+    println!(
+        "Listening for WebSocket clients on {}",
+        websocket_listener.local_addr()?
+    );
+    let server = Server::new();
+    tokio::try_join!(
+        server.clone().run(listener),
+        server.run_websocket(websocket_listener)
+    )?;
+    Ok(())
+}
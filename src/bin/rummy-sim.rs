@@ -0,0 +1,98 @@
+//! Plays many seeded games of basic Rummy between bots, and prints statistics about them.
+//!
+//! Usage: `rummy-sim [options]`, where the options are:
+//! - `--bots <names>`: a comma-separated list of bots (`greedy` or `random`), one per player (default `greedy,greedy`)
+//! - `--games <n>`, `--rounds <n>`, `--seed <n>`, `--threads <n>`, `--max-turns <n>`: see `SimulationConfig`
+//! - `--deal <n>`, `--draw-deck <n>`, `--draw-discard-pile <player-chooses|whole-pile|n>`: see `BasicConfig`
+//! - `--packs <n>`, `--high-rank <rank>`, `--wildcard <rank>`: see `DeckConfig`
+
+use rummy::{
+    cards::{deck::DeckConfig, suit_rank::Rank},
+    game::variants::basic::{
+        config::{BasicConfig, DrawDiscardPileOverride},
+        game::BasicRummyGame,
+    },
+    simulation::{
+        bots::{GreedyBot, RandomBot},
+        BotFactory, Simulation, SimulationConfig,
+    },
+};
+use std::{error::Error, process::ExitCode};
+
+fn main() -> ExitCode {
+    match run() {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(err) => {
+            eprintln!("rummy-sim: {err}");
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn run() -> Result<(), Box<dyn Error>> {
+    let mut config = SimulationConfig::default();
+    let mut game_config = BasicConfig {
+        deal_amount: None,
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let mut deck_config = DeckConfig::new();
+    let mut bot_names = vec!["greedy".to_string(), "greedy".to_string()];
+
+    let mut args = std::env::args().skip(1);
+    while let Some(option) = args.next() {
+        let value = args
+            .next()
+            .ok_or_else(|| format!("missing a value for {option}"))?;
+        match option.as_str() {
+            "--bots" => bot_names = value.split(',').map(str::to_string).collect(),
+            "--games" => config.games = value.parse()?,
+            "--rounds" => config.rounds_per_game = value.parse()?,
+            "--seed" => config.seed = value.parse()?,
+            "--threads" => config.threads = Some(value.parse()?),
+            "--max-turns" => config.max_turns_per_round = value.parse()?,
+            "--deal" => game_config.deal_amount = Some(value.parse()?),
+            "--draw-deck" => game_config.draw_deck_amount = Some(value.parse()?),
+            "--draw-discard-pile" => {
+                game_config.draw_discard_pile_amount = Some(match value.as_str() {
+                    "player-chooses" => DrawDiscardPileOverride::PlayerChooses,
+                    "whole-pile" => DrawDiscardPileOverride::WholePile,
+                    amount => DrawDiscardPileOverride::Constant(amount.parse()?),
+                })
+            }
+            "--packs" => deck_config.pack_count = value.parse()?,
+            "--high-rank" => deck_config.high_rank = Some(value.parse::<Rank>()?),
+            "--wildcard" => deck_config.wildcard_rank = Some(value.parse::<Rank>()?),
+            _ => return Err(format!("unknown option {option}").into()),
+        }
+    }
+
+    let bots = bot_names
+        .iter()
+        .map(|name| -> Result<BotFactory<BasicRummyGame>, String> {
+            match name.as_str() {
+                "greedy" => Ok(Box::new(|_| Box::new(GreedyBot))),
+                "random" => Ok(Box::new(|seed| Box::new(RandomBot::new(seed)))),
+                _ => Err(format!("unknown bot {name}")),
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let player_ids = (0..bots.len()).collect::<Vec<_>>();
+
+    // check the setup once, so a bad configuration is an error rather than a panic
+    BasicRummyGame::new(player_ids.clone(), game_config.clone(), deck_config.clone())?;
+    let new_game = |seed| {
+        let deck_config = DeckConfig {
+            shuffle_seed: Some(seed),
+            ..deck_config.clone()
+        };
+        // UNWRAP: the setup was already checked, and the seed doesn't affect it
+        BasicRummyGame::new(player_ids.clone(), game_config.clone(), deck_config).unwrap()
+    };
+
+    let report = Simulation::new(config, new_game, bots).run()?;
+    println!("Bots: {}", bot_names.join(", "));
+    print!("{report}");
+    Ok(())
+}
@@ -0,0 +1,480 @@
+//! Contains the `Encoder`/`Decoder` primitives, and the encoding of cards and interactions.
+//!
+//! - Integers are unsigned LEB128 varints (signed integers are zigzagged first).
+//! - Cards are a single byte: `(rank << 3) | suit`.
+//! - Interactions are a tag byte followed by their fields as varints.
+
+use crate::{
+    cards::{
+        card::{Card, CardData},
+        deck::DeckConfig,
+        suit_rank::{Rank, Suit},
+    },
+    game::action::*,
+};
+use std::sync::Arc;
+use strum::IntoEnumIterator;
+
+use super::{BinaryError, FORMAT_VERSION};
+
+/// Used to encode `None` for optional ranks.
+const NO_RANK: u8 = 0xFF;
+
+/// Set on an interaction's tag if it failed to execute.
+const FAILED_FLAG: u8 = 0x80;
+
+/// Set on an interaction's tag if it was undone.
+const UNDONE_FLAG: u8 = 0x40;
+
+/// Writes values into a compact binary buffer.
+#[derive(Debug, Default)]
+pub struct Encoder {
+    buf: Vec<u8>,
+}
+
+impl Encoder {
+    /// Create an empty encoder.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Consume the encoder, returning the encoded bytes.
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.buf
+    }
+
+    /// Write a single byte.
+    pub fn u8(&mut self, value: u8) {
+        self.buf.push(value);
+    }
+
+    /// Write raw bytes.
+    pub fn bytes(&mut self, bytes: &[u8]) {
+        self.buf.extend_from_slice(bytes);
+    }
+
+    /// Write a bool as a single byte.
+    pub fn bool(&mut self, value: bool) {
+        self.u8(value as u8);
+    }
+
+    /// Write an unsigned varint.
+    pub fn varint(&mut self, mut value: u64) {
+        loop {
+            let byte = (value & 0x7F) as u8;
+            value >>= 7;
+            if value == 0 {
+                self.u8(byte);
+                return;
+            }
+            self.u8(byte | 0x80);
+        }
+    }
+
+    /// Write a `usize` as an unsigned varint.
+    pub fn usize(&mut self, value: usize) {
+        self.varint(value as u64);
+    }
+
+    /// Write a signed varint (zigzag encoded).
+    pub fn signed_varint(&mut self, value: i64) {
+        self.varint(((value << 1) ^ (value >> 63)) as u64);
+    }
+
+    /// Write an optional `usize`, as `0` for `None` and `value + 1` for `Some`.
+    pub fn optional_usize(&mut self, value: Option<usize>) {
+        self.varint(value.map_or(0, |v| v as u64 + 1));
+    }
+
+    /// Write a rank as a single byte.
+    pub fn rank(&mut self, rank: Rank) {
+        self.u8(rank as u8);
+    }
+
+    /// Write an optional rank as a single byte.
+    pub fn optional_rank(&mut self, rank: Option<Rank>) {
+        self.u8(rank.map_or(NO_RANK, |r| r as u8));
+    }
+
+    /// Write a suit as a single byte.
+    pub fn suit(&mut self, suit: Suit) {
+        self.u8(suit as u8);
+    }
+
+    /// Write a card as a single byte.
+    pub fn card(&mut self, card: CardData) {
+        self.u8(((card.rank as u8) << 3) | card.suit as u8);
+    }
+
+    /// Write a length-prefixed list of cards.
+    pub fn cards(&mut self, cards: impl ExactSizeIterator<Item = CardData>) {
+        self.usize(cards.len());
+        for card in cards {
+            self.card(card);
+        }
+    }
+
+    /// Write a length-prefixed list of indices.
+    pub fn indices(&mut self, indices: &[usize]) {
+        self.usize(indices.len());
+        for &index in indices {
+            self.usize(index);
+        }
+    }
+
+    /// Write an optional varint, prefixed by whether it's present.
+    pub fn optional_varint(&mut self, value: Option<u64>) {
+        match value {
+            None => self.u8(0),
+            Some(value) => {
+                self.u8(1);
+                self.varint(value);
+            }
+        }
+    }
+
+    /// Write a `DeckConfig`.
+    pub fn deck_config(&mut self, config: &DeckConfig) {
+        self.optional_varint(config.shuffle_seed);
+        self.usize(config.pack_count);
+        self.optional_rank(config.high_rank);
+        self.optional_rank(config.wildcard_rank);
+    }
+
+    /// Write an interaction, whether it executed successfully, and whether it was undone.
+    pub fn interaction(&mut self, interaction: &GameInteractions, successful: bool, undone: bool) {
+        let mut flag = if successful { 0 } else { FAILED_FLAG };
+        if undone {
+            flag |= UNDONE_FLAG;
+        }
+        match interaction {
+            GameInteractions::Action(action) => match action {
+                GameAction::DrawDeck(_) => self.u8(flag),
+                GameAction::DrawDiscardPile(action) => {
+                    self.u8(1 | flag);
+                    self.optional_usize(action.count.map(usize::from));
+                }
+                GameAction::LayOff(action) => {
+                    self.u8(2 | flag);
+                    self.usize(action.card_index);
+                    self.usize(action.target_player_index);
+                    self.usize(action.target_meld_index);
+                }
+                GameAction::FormMeld(action) => {
+                    self.u8(3 | flag);
+                    self.indices(&action.card_indices);
+                }
+                GameAction::FormMelds(action) => {
+                    self.u8(4 | flag);
+                    self.usize(action.melds.len());
+                    for meld in &action.melds {
+                        self.indices(meld);
+                    }
+                }
+                GameAction::Discard(action) => {
+                    self.u8(5 | flag);
+                    self.usize(action.card_index);
+                    self.u8(match action.declare_going_out {
+                        None => 0,
+                        Some(false) => 1,
+                        Some(true) => 2,
+                    });
+                }
+            },
+            GameInteractions::PlayerJoin { player_id } => {
+                self.u8(6 | flag);
+                self.usize(*player_id);
+            }
+            GameInteractions::PlayerQuit { player_id } => {
+                self.u8(7 | flag);
+                self.usize(*player_id);
+            }
+            GameInteractions::HandRearrangement {
+                player_id,
+                new_arrangement,
+            } => {
+                self.u8(8 | flag);
+                self.usize(*player_id);
+                self.cards(new_arrangement.iter().copied());
+            }
+            GameInteractions::PlayerReplaced {
+                player_id,
+                new_player_id,
+            } => {
+                self.u8(9 | flag);
+                self.usize(*player_id);
+                self.usize(*new_player_id);
+            }
+            GameInteractions::Pause => self.u8(10 | flag),
+            GameInteractions::Resume => self.u8(11 | flag),
+        }
+    }
+}
+
+/// Reads values from a compact binary buffer.
+#[derive(Debug)]
+pub struct Decoder<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    version: u8,
+}
+
+impl<'a> Decoder<'a> {
+    /// Create a decoder over `bytes`, which are read with the current format version's layout.
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self {
+            bytes,
+            pos: 0,
+            version: FORMAT_VERSION,
+        }
+    }
+
+    /// Get the format version of the data being read, as given by its header.
+    ///
+    /// Games should check this before reading data added in later versions.
+    pub fn version(&self) -> u8 {
+        self.version
+    }
+
+    /// Set the format version of the data being read.
+    pub(super) fn set_version(&mut self, version: u8) {
+        self.version = version;
+    }
+
+    /// Whether all bytes have been read.
+    pub fn is_empty(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    /// Read a single byte.
+    pub fn u8(&mut self) -> Result<u8, BinaryError> {
+        let byte = *self.bytes.get(self.pos).ok_or(BinaryError::UnexpectedEnd)?;
+        self.pos += 1;
+        Ok(byte)
+    }
+
+    /// Read `len` raw bytes.
+    pub fn bytes(&mut self, len: usize) -> Result<&'a [u8], BinaryError> {
+        let end = self
+            .pos
+            .checked_add(len)
+            .ok_or(BinaryError::UnexpectedEnd)?;
+        let bytes = self
+            .bytes
+            .get(self.pos..end)
+            .ok_or(BinaryError::UnexpectedEnd)?;
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    /// Read a bool from a single byte.
+    pub fn bool(&mut self) -> Result<bool, BinaryError> {
+        match self.u8()? {
+            0 => Ok(false),
+            1 => Ok(true),
+            _ => Err(BinaryError::InvalidValue("bool")),
+        }
+    }
+
+    /// Read an unsigned varint.
+    pub fn varint(&mut self) -> Result<u64, BinaryError> {
+        let mut value = 0u64;
+        for shift in (0..64).step_by(7) {
+            let byte = self.u8()?;
+            value |= ((byte & 0x7F) as u64) << shift;
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        Err(BinaryError::InvalidValue("varint"))
+    }
+
+    /// Read an unsigned varint as a `usize`.
+    pub fn usize(&mut self) -> Result<usize, BinaryError> {
+        usize::try_from(self.varint()?).map_err(|_| BinaryError::InvalidValue("usize"))
+    }
+
+    /// Read a signed (zigzag encoded) varint.
+    pub fn signed_varint(&mut self) -> Result<i64, BinaryError> {
+        let value = self.varint()?;
+        Ok((value >> 1) as i64 ^ -((value & 1) as i64))
+    }
+
+    /// Read an optional `usize`.
+    pub fn optional_usize(&mut self) -> Result<Option<usize>, BinaryError> {
+        match self.usize()? {
+            0 => Ok(None),
+            value => Ok(Some(value - 1)),
+        }
+    }
+
+    /// Read a length for a collection, guarding against lengths larger than the remaining bytes.
+    pub fn len(&mut self) -> Result<usize, BinaryError> {
+        let len = self.usize()?;
+        if len > self.bytes.len() - self.pos {
+            return Err(BinaryError::InvalidValue("length"));
+        }
+        Ok(len)
+    }
+
+    /// Read a rank from a single byte.
+    pub fn rank(&mut self) -> Result<Rank, BinaryError> {
+        let byte = self.u8()?;
+        Rank::iter()
+            .nth(byte as usize)
+            .ok_or(BinaryError::InvalidValue("rank"))
+    }
+
+    /// Read an optional rank from a single byte.
+    pub fn optional_rank(&mut self) -> Result<Option<Rank>, BinaryError> {
+        let byte = self.u8()?;
+        if byte == NO_RANK {
+            return Ok(None);
+        }
+        Rank::iter()
+            .nth(byte as usize)
+            .map(Some)
+            .ok_or(BinaryError::InvalidValue("rank"))
+    }
+
+    /// Read a suit from a single byte.
+    pub fn suit(&mut self) -> Result<Suit, BinaryError> {
+        let byte = self.u8()?;
+        Suit::iter()
+            .nth(byte as usize)
+            .ok_or(BinaryError::InvalidValue("suit"))
+    }
+
+    /// Read a card from a single byte.
+    pub fn card(&mut self) -> Result<CardData, BinaryError> {
+        let byte = self.u8()?;
+        let rank = Rank::iter()
+            .nth((byte >> 3) as usize)
+            .ok_or(BinaryError::InvalidValue("card"))?;
+        let suit = Suit::iter()
+            .nth((byte & 0b111) as usize)
+            .ok_or(BinaryError::InvalidValue("card"))?;
+        Ok(CardData { rank, suit })
+    }
+
+    /// Read a length-prefixed list of cards.
+    pub fn cards(&mut self) -> Result<Vec<CardData>, BinaryError> {
+        let len = self.len()?;
+        (0..len).map(|_| self.card()).collect()
+    }
+
+    /// Read a length-prefixed list of cards, attaching `deck_config` to each.
+    pub fn cards_with_config(
+        &mut self,
+        deck_config: &Arc<DeckConfig>,
+    ) -> Result<Vec<Card>, BinaryError> {
+        Ok(self
+            .cards()?
+            .into_iter()
+            .map(|card| Card::from_card_data(card, deck_config.clone()))
+            .collect())
+    }
+
+    /// Read a length-prefixed list of indices.
+    pub fn indices(&mut self) -> Result<Vec<usize>, BinaryError> {
+        let len = self.len()?;
+        (0..len).map(|_| self.usize()).collect()
+    }
+
+    /// Read an optional varint, where `what` describes the value for errors.
+    pub fn optional_varint(&mut self, what: &'static str) -> Result<Option<u64>, BinaryError> {
+        match self.u8()? {
+            0 => Ok(None),
+            1 => Ok(Some(self.varint()?)),
+            _ => Err(BinaryError::InvalidValue(what)),
+        }
+    }
+
+    /// Read a `DeckConfig`.
+    pub fn deck_config(&mut self) -> Result<DeckConfig, BinaryError> {
+        Ok(DeckConfig {
+            shuffle_seed: self.optional_varint("shuffle seed")?,
+            pack_count: self.usize()?,
+            high_rank: self.optional_rank()?,
+            wildcard_rank: self.optional_rank()?,
+        })
+    }
+
+    /// Read an interaction, whether it executed successfully, and whether it was undone.
+    pub fn interaction(&mut self) -> Result<(GameInteractions, bool, bool), BinaryError> {
+        let tag = self.u8()?;
+        let successful = tag & FAILED_FLAG == 0;
+        let undone = tag & UNDONE_FLAG != 0;
+        let action = match tag & !(FAILED_FLAG | UNDONE_FLAG) {
+            0 => GameAction::DrawDeck(DrawDeckAction {}),
+            1 => {
+                let count = self
+                    .optional_usize()?
+                    .map(u8::try_from)
+                    .transpose()
+                    .map_err(|_| BinaryError::InvalidValue("draw count"))?;
+                GameAction::DrawDiscardPile(DrawDiscardPileAction { count })
+            }
+            2 => GameAction::LayOff(LayOffAction {
+                card_index: self.usize()?,
+                target_player_index: self.usize()?,
+                target_meld_index: self.usize()?,
+            }),
+            3 => GameAction::FormMeld(FormMeldAction {
+                card_indices: self.indices()?,
+            }),
+            4 => {
+                let len = self.len()?;
+                let melds = (0..len).map(|_| self.indices()).collect::<Result<_, _>>()?;
+                GameAction::FormMelds(FormMeldsAction { melds })
+            }
+            5 => {
+                let card_index = self.usize()?;
+                let declare_going_out = match self.u8()? {
+                    0 => None,
+                    1 => Some(false),
+                    2 => Some(true),
+                    _ => return Err(BinaryError::InvalidValue("going out declaration")),
+                };
+                GameAction::Discard(DiscardAction {
+                    card_index,
+                    declare_going_out,
+                })
+            }
+            6 => {
+                let player_id = self.usize()?;
+                return Ok((
+                    GameInteractions::PlayerJoin { player_id },
+                    successful,
+                    undone,
+                ));
+            }
+            7 => {
+                let player_id = self.usize()?;
+                return Ok((
+                    GameInteractions::PlayerQuit { player_id },
+                    successful,
+                    undone,
+                ));
+            }
+            8 => {
+                let interaction = GameInteractions::HandRearrangement {
+                    player_id: self.usize()?,
+                    new_arrangement: self.cards()?,
+                };
+                return Ok((interaction, successful, undone));
+            }
+            9 => {
+                let interaction = GameInteractions::PlayerReplaced {
+                    player_id: self.usize()?,
+                    new_player_id: self.usize()?,
+                };
+                return Ok((interaction, successful, undone));
+            }
+            10 => return Ok((GameInteractions::Pause, successful, undone)),
+            11 => return Ok((GameInteractions::Resume, successful, undone)),
+            _ => return Err(BinaryError::InvalidValue("interaction tag")),
+        };
+        Ok((GameInteractions::Action(action), successful, undone))
+    }
+}
@@ -0,0 +1,231 @@
+//! Contains the binary encoding of a `BasicRummyGame`.
+
+use super::{BinaryError, BinaryGame, Decoder, Encoder};
+use crate::{
+    cards::{
+        deck::Deck,
+        meld::{Meld, Meldable, Run, Set},
+    },
+    game::{
+        score::RoundScore,
+        state::{GamePhase, GameState, QuitHandPolicy},
+        variants::basic::{
+            config::{BasicConfig, DrawDiscardPileOverride},
+            game::BasicRummyGame,
+            rules::BasicRules,
+            score::BasicScore,
+            state::BasicState,
+        },
+    },
+    player::Player,
+};
+use std::{collections::HashMap, sync::Arc};
+
+impl BinaryGame for BasicRummyGame {
+    const VARIANT: &'static str = "basic";
+
+    fn encode(&self, encoder: &mut Encoder) {
+        let config = self.rules.config();
+        encoder.optional_usize(config.deal_amount);
+        encoder.optional_usize(config.draw_deck_amount);
+        match config.draw_discard_pile_amount {
+            None => encoder.u8(0),
+            Some(DrawDiscardPileOverride::PlayerChooses) => encoder.u8(1),
+            Some(DrawDiscardPileOverride::WholePile) => encoder.u8(2),
+            Some(DrawDiscardPileOverride::Constant(amount)) => {
+                encoder.u8(3);
+                encoder.usize(amount);
+            }
+        }
+        encoder.u8(match config.quit_hand_policy {
+            None => 0,
+            Some(QuitHandPolicy::Keep) => 1,
+            Some(QuitHandPolicy::ReturnToStock) => 2,
+            Some(QuitHandPolicy::Discard) => 3,
+        });
+        encode_state(&self.state, encoder);
+    }
+
+    fn decode(decoder: &mut Decoder) -> Result<Self, BinaryError> {
+        let deal_amount = decoder.optional_usize()?;
+        let draw_deck_amount = decoder.optional_usize()?;
+        let draw_discard_pile_amount = match decoder.u8()? {
+            0 => None,
+            1 => Some(DrawDiscardPileOverride::PlayerChooses),
+            2 => Some(DrawDiscardPileOverride::WholePile),
+            3 => Some(DrawDiscardPileOverride::Constant(decoder.usize()?)),
+            _ => return Err(BinaryError::InvalidValue("discard pile draw override")),
+        };
+        let quit_hand_policy = match decoder.u8()? {
+            0 => None,
+            1 => Some(QuitHandPolicy::Keep),
+            2 => Some(QuitHandPolicy::ReturnToStock),
+            3 => Some(QuitHandPolicy::Discard),
+            _ => return Err(BinaryError::InvalidValue("quit hand policy")),
+        };
+        let config = BasicConfig {
+            deal_amount,
+            draw_deck_amount,
+            draw_discard_pile_amount,
+            quit_hand_policy,
+        };
+        Ok(BasicRummyGame {
+            state: decode_state(decoder)?,
+            rules: BasicRules::new(config),
+        })
+    }
+}
+
+/// Write the game state.
+fn encode_state(state: &GameState<BasicScore, BasicRules>, encoder: &mut Encoder) {
+    encoder.deck_config(state.deck.config());
+    encoder.u8(match state.phase {
+        GamePhase::Draw => 0,
+        GamePhase::Play => 1,
+        GamePhase::RoundEnd => 2,
+        GamePhase::GameEnd => 3,
+    });
+    encoder.usize(state.current_player);
+    encoder.usize(state.current_round);
+    encoder.bool(state.paused);
+
+    encoder.usize(state.players.len());
+    for player in &state.players {
+        encoder.usize(player.id);
+        encoder.bool(player.active);
+        encoder.usize(player.joined_in_round);
+        encoder.cards(player.cards.iter().map(|c| c.data()));
+        encoder.usize(player.melds.len());
+        for meld in &player.melds {
+            match meld {
+                Meld::Set(set) => {
+                    encoder.u8(0);
+                    encoder.rank(set.set_rank);
+                }
+                Meld::Run(run) => {
+                    encoder.u8(1);
+                    encoder.suit(run.set_suit);
+                }
+            }
+            encoder.cards(meld.cards().iter().map(|c| c.data()));
+        }
+    }
+
+    encoder.cards(state.deck.stock.iter().map(|c| c.data()));
+    encoder.cards(state.deck.discard_pile.iter().map(|c| c.data()));
+    encoder.optional_varint(state.deck.seed);
+    encoder.varint(state.deck.reshuffles);
+    encoder.optional_varint(state.deck.committed_seed);
+
+    // sorted so that the same state always has the same encoding
+    let mut round_scores: Vec<_> = state.round_scores.iter().collect();
+    round_scores.sort_by_key(|(round, _)| **round);
+    encoder.usize(round_scores.len());
+    for (round, round_score) in round_scores {
+        encoder.usize(*round);
+        encoder.usize(round_score.winner_id);
+        let mut player_scores: Vec<_> = round_score.player_scores.iter().collect();
+        player_scores.sort_by_key(|(id, _)| **id);
+        encoder.usize(player_scores.len());
+        for (id, score) in player_scores {
+            encoder.usize(*id);
+            encoder.varint(score.score() as u64);
+        }
+    }
+}
+
+/// Read the game state.
+fn decode_state(decoder: &mut Decoder) -> Result<GameState<BasicScore, BasicRules>, BinaryError> {
+    let deck_config = Arc::new(decoder.deck_config()?);
+    let phase = match decoder.u8()? {
+        0 => GamePhase::Draw,
+        1 => GamePhase::Play,
+        2 => GamePhase::RoundEnd,
+        3 => GamePhase::GameEnd,
+        _ => return Err(BinaryError::InvalidValue("game phase")),
+    };
+    let current_player = decoder.usize()?;
+    let current_round = decoder.usize()?;
+    let paused = decoder.bool()?;
+
+    let player_count = decoder.len()?;
+    let mut players = Vec::with_capacity(player_count);
+    for _ in 0..player_count {
+        let id = decoder.usize()?;
+        let active = decoder.bool()?;
+        let joined_in_round = decoder.usize()?;
+        let cards = decoder.cards_with_config(&deck_config)?;
+        let meld_count = decoder.len()?;
+        let mut melds = Vec::with_capacity(meld_count);
+        for _ in 0..meld_count {
+            let meld = match decoder.u8()? {
+                0 => {
+                    let set_rank = decoder.rank()?;
+                    let cards = decoder.cards_with_config(&deck_config)?;
+                    Meld::Set(Set { cards, set_rank })
+                }
+                1 => {
+                    let set_suit = decoder.suit()?;
+                    let cards = decoder.cards_with_config(&deck_config)?;
+                    Meld::Run(Run { cards, set_suit })
+                }
+                _ => return Err(BinaryError::InvalidValue("meld kind")),
+            };
+            melds.push(meld);
+        }
+        players.push(Player {
+            id,
+            cards,
+            melds,
+            active,
+            joined_in_round,
+        });
+    }
+
+    let stock = decoder.cards_with_config(&deck_config)?;
+    let discard_pile = decoder.cards_with_config(&deck_config)?;
+    let seed = decoder.optional_varint("deck seed")?;
+    let reshuffles = decoder.varint()?;
+    let committed_seed = decoder.optional_varint("committed seed")?;
+    let deck = Deck {
+        config: deck_config,
+        stock,
+        discard_pile,
+        seed,
+        reshuffles,
+        committed_seed,
+    };
+
+    let round_count = decoder.len()?;
+    let mut round_scores = HashMap::with_capacity(round_count);
+    for _ in 0..round_count {
+        let round = decoder.usize()?;
+        let winner_id = decoder.usize()?;
+        let score_count = decoder.len()?;
+        let mut player_scores = HashMap::with_capacity(score_count);
+        for _ in 0..score_count {
+            let id = decoder.usize()?;
+            let score =
+                u32::try_from(decoder.varint()?).map_err(|_| BinaryError::InvalidValue("score"))?;
+            player_scores.insert(id, BasicScore::new(score));
+        }
+        round_scores.insert(
+            round,
+            RoundScore {
+                player_scores,
+                winner_id,
+            },
+        );
+    }
+
+    Ok(GameState {
+        phase,
+        players,
+        deck,
+        current_player,
+        current_round,
+        paused,
+        round_scores,
+        variant_state: BasicState {},
+    })
+}
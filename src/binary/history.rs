@@ -0,0 +1,154 @@
+//! Contains the binary encoding of a `History`.
+
+use super::{read_header, write_header, BinaryError, BinaryGame, Decoder, Encoder, EncodingKind};
+use crate::wrappers::history::{apply_interaction, History, HistoryEntry};
+use chrono::DateTime;
+use std::collections::HashMap;
+
+/// How a `History` is encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HistoryEncoding {
+    /// Store the game's state at the start of every round, as well as its current state.
+    ///
+    /// This is larger, but decodes quickly and doesn't rely on the game being reproducible.
+    Full,
+    /// Store only the initial game (including its deck's shuffle seed) and the interactions of every round.
+    ///
+    /// This is much smaller, but the rest of the history must be rebuilt by replaying it when decoding.
+    /// Every round must be shuffled from the deck's shuffle seed (games always pick one when started,
+    /// but rounds with a committed fair shuffle aren't); otherwise, encoding returns an `Err`.
+    SeedAndLog,
+}
+
+impl<G: BinaryGame> History<G> {
+    /// Encode the history into bytes.
+    ///
+    /// Interaction timestamps are stored with millisecond precision.
+    ///
+    /// Returns an `Err` if using `HistoryEncoding::SeedAndLog` for a game whose rounds aren't shuffled from its deck's shuffle seed.
+    pub fn to_binary(&self, encoding: HistoryEncoding) -> Result<Vec<u8>, BinaryError> {
+        let mut encoder = Encoder::new();
+        let mut rounds: Vec<_> = self.get_histories().keys().copied().collect();
+        rounds.sort();
+
+        match encoding {
+            HistoryEncoding::Full => {
+                write_header::<G>(&mut encoder, EncodingKind::FullHistory);
+                self.get_game().encode(&mut encoder);
+                encoder.usize(rounds.len());
+                for round in rounds {
+                    encoder.usize(round);
+                    self.get_initial_round_states()[&round].encode(&mut encoder);
+                    encode_entries(&self.get_histories()[&round], &mut encoder);
+                }
+            }
+            HistoryEncoding::SeedAndLog => {
+                let initial_game = &self.get_initial_round_states()[&0];
+                if !self.is_reproducible_from_seed() {
+                    return Err(BinaryError::UnseededDeck);
+                }
+                write_header::<G>(&mut encoder, EncodingKind::SeedAndLogHistory);
+                initial_game.encode(&mut encoder);
+                encoder.usize(rounds.len());
+                for round in rounds {
+                    encoder.usize(round);
+                    encode_entries(&self.get_histories()[&round], &mut encoder);
+                }
+            }
+        }
+
+        Ok(encoder.into_bytes())
+    }
+
+    /// Decode a history from bytes, replaying it if it was encoded with `HistoryEncoding::SeedAndLog`.
+    ///
+    /// Returns an `Err` if the data is invalid, or (for `SeedAndLog`) doesn't replay with the recorded outcomes.
+    pub fn from_binary(bytes: &[u8]) -> Result<Self, BinaryError> {
+        let mut decoder = Decoder::new(bytes);
+        let history = match read_header::<G>(&mut decoder)? {
+            EncodingKind::FullHistory => {
+                let current_game = G::decode(&mut decoder)?;
+                let round_count = decoder.len()?;
+                let mut initial_round_states = HashMap::with_capacity(round_count);
+                let mut round_histories = HashMap::with_capacity(round_count);
+                for _ in 0..round_count {
+                    let round = decoder.usize()?;
+                    initial_round_states.insert(round, G::decode(&mut decoder)?);
+                    round_histories.insert(round, decode_entries(&mut decoder)?);
+                }
+                if !initial_round_states.contains_key(&0) {
+                    return Err(BinaryError::InvalidValue("history without round 0"));
+                }
+                History::from_parts(current_game, initial_round_states, round_histories)
+            }
+            EncodingKind::SeedAndLogHistory => {
+                let mut game = G::decode(&mut decoder)?;
+                let round_count = decoder.len()?;
+                let mut initial_round_states = HashMap::with_capacity(round_count);
+                let mut round_histories = HashMap::with_capacity(round_count);
+                for i in 0..round_count {
+                    let round = decoder.usize()?;
+                    if i > 0 {
+                        game.next_round()?;
+                    }
+                    if game.get_state().current_round() != round {
+                        return Err(BinaryError::InvalidValue("round number"));
+                    }
+                    initial_round_states.insert(round, game.clone());
+
+                    let entries = decode_entries(&mut decoder)?;
+                    // undone entries have no effect, so are skipped
+                    for (index, entry) in entries.iter().enumerate().filter(|(_, e)| !e.undone) {
+                        if apply_interaction(&mut game, entry.entry.clone(), entry.actor)
+                            != entry.successful
+                        {
+                            return Err(BinaryError::ReplayMismatch { round, index });
+                        }
+                    }
+                    round_histories.insert(round, entries);
+                }
+                if !initial_round_states.contains_key(&0) {
+                    return Err(BinaryError::InvalidValue("history without round 0"));
+                }
+                History::from_parts(game, initial_round_states, round_histories)
+            }
+            EncodingKind::Game => return Err(BinaryError::InvalidValue("encoding kind")),
+        };
+        if !decoder.is_empty() {
+            return Err(BinaryError::TrailingBytes);
+        }
+        Ok(history)
+    }
+}
+
+/// Write a round's history entries.
+fn encode_entries(entries: &[HistoryEntry], encoder: &mut Encoder) {
+    encoder.usize(entries.len());
+    for entry in entries {
+        encoder.interaction(&entry.entry, entry.successful, entry.undone);
+        encoder.signed_varint(entry.time.timestamp_millis());
+        encoder.optional_varint(entry.actor.map(|actor| actor as u64));
+    }
+}
+
+/// Read a round's history entries.
+fn decode_entries(decoder: &mut Decoder) -> Result<Vec<HistoryEntry>, BinaryError> {
+    let len = decoder.len()?;
+    (0..len)
+        .map(|_| {
+            let (entry, successful, undone) = decoder.interaction()?;
+            let time = DateTime::from_timestamp_millis(decoder.signed_varint()?)
+                .ok_or(BinaryError::InvalidValue("timestamp"))?;
+            let actor = decoder
+                .optional_varint("actor")?
+                .map(|actor| actor as usize);
+            Ok(HistoryEntry {
+                entry,
+                time,
+                successful,
+                actor,
+                undone,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,149 @@
+//! Contains a compact binary encoding for games and their histories, enabled by the `binary` feature.
+//!
+//! This is much smaller than a self-describing format like JSON, which matters when storing large numbers of games:
+//! - Integers are varints, and cards are a single byte each.
+//! - Interactions are a tag byte followed by their fields as varints.
+//! - A `History` can optionally be stored as just its initial game and interactions
+//!   (see [`HistoryEncoding::SeedAndLog`]), rebuilding the rest by replaying it when decoded.
+//!
+//! Every encoding starts with a short header: the `RMY` magic bytes, the format version,
+//! the kind of encoding and the variant's name.
+//!
+//! ## Version history
+//! - `1`: The initial format.
+//!
+//! ## Adding a version
+//! When the encoded layout changes:
+//! 1. Bump `FORMAT_VERSION`.
+//! 2. Keep decoding the older layouts, checking [`Decoder::version`] before reading the new data
+//!    (or return an `Err` for the older versions if they can't be supported).
+//! 3. Add regression fixtures for the new version under `tests/fixtures/binary`.
+
+mod codec;
+mod game;
+mod history;
+
+pub use codec::{Decoder, Encoder};
+pub use history::HistoryEncoding;
+
+use crate::game::{
+    error::{GameError, GameSetupError},
+    r#trait::Game,
+};
+use thiserror::Error;
+
+/// The magic bytes at the start of every encoding.
+const MAGIC: &[u8; 3] = b"RMY";
+
+/// The current version of the binary format.
+pub const FORMAT_VERSION: u8 = 1;
+
+/// The kinds of data that can be encoded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[repr(u8)]
+enum EncodingKind {
+    Game = 0,
+    FullHistory = 1,
+    SeedAndLogHistory = 2,
+}
+
+/// A `Game` which can be encoded in the compact binary format.
+pub trait BinaryGame: Game + Clone {
+    /// The name of the variant, written in the header so that encodings can't be decoded as the wrong variant.
+    const VARIANT: &'static str;
+
+    /// Write the game's data (without any header).
+    fn encode(&self, encoder: &mut Encoder);
+
+    /// Read the game's data (without any header).
+    fn decode(decoder: &mut Decoder) -> Result<Self, BinaryError>;
+
+    /// Encode the game into bytes.
+    fn to_binary(&self) -> Vec<u8> {
+        let mut encoder = Encoder::new();
+        write_header::<Self>(&mut encoder, EncodingKind::Game);
+        self.encode(&mut encoder);
+        encoder.into_bytes()
+    }
+
+    /// Decode a game from bytes.
+    fn from_binary(bytes: &[u8]) -> Result<Self, BinaryError> {
+        let mut decoder = Decoder::new(bytes);
+        match read_header::<Self>(&mut decoder)? {
+            EncodingKind::Game => (),
+            _ => return Err(BinaryError::InvalidValue("encoding kind")),
+        }
+        let game = Self::decode(&mut decoder)?;
+        if !decoder.is_empty() {
+            return Err(BinaryError::TrailingBytes);
+        }
+        Ok(game)
+    }
+}
+
+/// Errors from encoding or decoding the binary format.
+#[derive(Debug, Clone, Error)]
+pub enum BinaryError {
+    #[error("The data doesn't start with the expected magic bytes")]
+    InvalidMagic,
+    #[error("Unsupported format version {0} (the latest supported version is {FORMAT_VERSION})")]
+    UnsupportedVersion(u8),
+    #[error("The data is for variant {found:?}, but expected {expected:?}")]
+    WrongVariant { expected: String, found: String },
+    #[error("The data ended unexpectedly")]
+    UnexpectedEnd,
+    #[error("The data has unexpected bytes after its end")]
+    TrailingBytes,
+    #[error("The data contains an invalid {0}")]
+    InvalidValue(&'static str),
+    #[error("The deck isn't shuffled from its shuffle seed, so the game can't be rebuilt from its action log")]
+    UnseededDeck,
+    #[error("Interaction {index} of round {round} didn't replay with the recorded outcome")]
+    ReplayMismatch { round: usize, index: usize },
+    #[error("Failed to set up the encoded game: {0}")]
+    Setup(#[from] GameSetupError),
+    #[error("Failed to replay the encoded game: {0}")]
+    Game(#[from] GameError),
+}
+
+/// Write the header for an encoding of `kind`.
+fn write_header<G: BinaryGame>(encoder: &mut Encoder, kind: EncodingKind) {
+    encoder.bytes(MAGIC);
+    encoder.u8(FORMAT_VERSION);
+    encoder.u8(kind as u8);
+    encoder.usize(G::VARIANT.len());
+    encoder.bytes(G::VARIANT.as_bytes());
+}
+
+/// Read and validate the header, returning the kind of encoding.
+///
+/// The decoder is set to the header's format version, so that the rest of the data is read with the right layout.
+fn read_header<G: BinaryGame>(decoder: &mut Decoder) -> Result<EncodingKind, BinaryError> {
+    if decoder
+        .bytes(MAGIC.len())
+        .map_err(|_| BinaryError::InvalidMagic)?
+        != MAGIC
+    {
+        return Err(BinaryError::InvalidMagic);
+    }
+    let version = decoder.u8()?;
+    if !(1..=FORMAT_VERSION).contains(&version) {
+        return Err(BinaryError::UnsupportedVersion(version));
+    }
+    decoder.set_version(version);
+    let kind = match decoder.u8()? {
+        0 => EncodingKind::Game,
+        1 => EncodingKind::FullHistory,
+        2 => EncodingKind::SeedAndLogHistory,
+        _ => return Err(BinaryError::InvalidValue("encoding kind")),
+    };
+    let len = decoder.len()?;
+    let variant = String::from_utf8_lossy(decoder.bytes(len)?);
+    if variant != G::VARIANT {
+        return Err(BinaryError::WrongVariant {
+            expected: G::VARIANT.to_string(),
+            found: variant.into_owned(),
+        });
+    }
+    Ok(kind)
+}
@@ -5,6 +5,8 @@
 //!
 //! Any external API in this crate that involves an owned `Card` will instead use
 //! `CardData`, and manage the conversion internally.
+//!
+//! `CardData` can also be parsed from text; see [`notation`](super::notation) for the accepted forms.
 
 use super::{
     deck::DeckConfig,
@@ -14,8 +16,10 @@ use std::{
     cmp::Ordering,
     fmt::{Debug, Display},
     hash::Hash,
+    str::FromStr,
     sync::Arc,
 };
+use thiserror::Error;
 
 /// The data of a card.
 ///
@@ -181,3 +185,52 @@ impl Display for CardData {
         write!(f, "{}{}", self.rank.as_str(), self.suit.as_str())
     }
 }
+
+/// Parses a card from text, such as `10♥`, `TH`, `10h` or `Jk`.
+///
+/// A joker can be written as `Jk`, `Joker`, `🃏` or `🃏🃏` (the `Display` output).
+/// Otherwise, the last character is the suit and everything before it is the rank.
+impl FromStr for CardData {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        if matches!(s, "🃏" | "🃏🃏") || matches!(s.to_ascii_uppercase().as_str(), "JK" | "JOKER")
+        {
+            return Ok(CardData {
+                rank: Rank::Joker,
+                suit: Suit::Joker,
+            });
+        }
+
+        let (suit_start, _) = s
+            .char_indices()
+            .last()
+            .ok_or_else(|| ParseCardError::InvalidCard(s.to_string()))?;
+        if suit_start == 0 {
+            return Err(ParseCardError::InvalidCard(s.to_string()));
+        }
+        let (rank, suit) = s.split_at(suit_start);
+        let card = CardData {
+            rank: rank.parse()?,
+            suit: suit.parse()?,
+        };
+
+        // jokers only ever come as a pair of joker rank + joker suit
+        if (card.rank == Rank::Joker) != (card.suit == Suit::Joker) {
+            return Err(ParseCardError::InvalidCard(s.to_string()));
+        }
+        Ok(card)
+    }
+}
+
+/// Errors from parsing cards (and collections of them) from text.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseCardError {
+    #[error("Unknown rank: {0:?}")]
+    InvalidRank(String),
+    #[error("Unknown suit: {0:?}")]
+    InvalidSuit(String),
+    #[error("Couldn't parse a card from {0:?}")]
+    InvalidCard(String),
+}
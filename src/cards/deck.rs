@@ -17,6 +17,10 @@ use strum::IntoEnumIterator;
 /// Optional seed for shuffling, where `0` results in no shuffle.
 /// The default is a completely randomized shuffle.
 ///
+/// The seed acts as a master seed: the first round is shuffled with it directly, while
+/// later rounds and reshuffles of the discard pile use seeds derived from it (see `round_seed`).
+/// Games started without a seed pick a random one, so they can always be reproduced.
+///
 /// ### `pack_count`
 /// The number of card packs to include in the deck.
 ///
@@ -52,6 +56,37 @@ impl DeckConfig {
             wildcard_rank: None,
         }
     }
+
+    /// Get the shuffle seed for `round`, derived from `shuffle_seed`.
+    ///
+    /// Rounds 0 and 1 use `shuffle_seed` as-is, and every later round gets its own seed,
+    /// so that each round is dealt differently while staying reproducible.
+    /// A `shuffle_seed` of `0` (no shuffle) stays `0` for every round.
+    pub fn round_seed(&self, round: usize) -> Option<u64> {
+        self.shuffle_seed.map(|seed| match (seed, round) {
+            (0, _) | (_, 0..=1) => seed,
+            _ => derive_seed(seed, round as u64),
+        })
+    }
+}
+
+/// Mixed into a shuffle's seed before deriving its reshuffle seeds,
+/// so that they never follow the same stream as the round seeds (see `reshuffle_seed`).
+const RESHUFFLE_TAG: u64 = 0x5245_5348_5546_464C;
+
+/// Get the seed for the `n`th reshuffle of the discard pile, after shuffling with `seed`.
+///
+/// This is derived from its own stream, so that reshuffles aren't correlated with the deal of another round.
+pub(crate) fn reshuffle_seed(seed: u64, n: u64) -> u64 {
+    derive_seed(derive_seed(seed, RESHUFFLE_TAG), n)
+}
+
+/// Derive a new non-zero seed from `seed` and a counter `n`, using the SplitMix64 finalizer.
+fn derive_seed(seed: u64, n: u64) -> u64 {
+    let mut z = seed.wrapping_add(n.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    (z ^ (z >> 31)).max(1)
 }
 
 /// The deck.
@@ -60,11 +95,20 @@ impl DeckConfig {
 /// - **config**, dictating shuffling, pack counts, wildcards etc.
 /// - **stock**, face-down cards that can be drawn at the start of each turn
 /// - **discard pile**, discarded cards, which can also be drawn
+///
+/// A seeded deck also tracks the seed of its last shuffle and how many times the discard
+/// pile was reshuffled since, so that reshuffles are reproducible too.
+///
+/// With the `fair-shuffle` feature, the next round's seed can also be committed to beforehand
+/// (see the `fairness` module).
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Deck {
     pub(crate) config: Arc<DeckConfig>,
     pub(crate) stock: Vec<Card>,
     pub(crate) discard_pile: Vec<Card>,
+    pub(crate) seed: Option<u64>,
+    pub(crate) reshuffles: u64,
+    pub(crate) committed_seed: Option<u64>,
 }
 
 impl Deck {
@@ -73,7 +117,7 @@ impl Deck {
     /// **Note**:
     /// - If `pack_count` < 1, it will be set to 1.
     /// - If `shuffle_seed` is `Some`, it will always be shuffled according to the seed.
-    /// - If `shuffle_seed` is `None`, it will not be shuffled.
+    /// - If `shuffle_seed` is `None`, it will be shuffled randomly.
     /// - If `wildcard_rank` is `Joker`, 2 jokers will be added per pack.
     pub fn new(mut config: DeckConfig) -> Self {
         config.pack_count = config.pack_count.max(1);
@@ -84,20 +128,34 @@ impl Deck {
             config: config.clone(),
             stock: Vec::new(),
             discard_pile: Vec::new(),
+            seed: config.shuffle_seed,
+            reshuffles: 0,
+            committed_seed: None,
         };
 
         Deck::generate_cards(&mut deck.stock, &config);
-        Deck::shuffle_cards(&mut deck.stock, &config);
+        Deck::shuffle_cards(&mut deck.stock, deck.seed);
 
         deck
     }
 
-    /// (Re)creates the deck and shuffling it.
+    /// (Re)creates the deck and shuffling it with the seed of its last shuffle.
     pub fn reset(&mut self) {
         self.stock.clear();
         self.discard_pile.clear();
+        self.reshuffles = 0;
         Deck::generate_cards(&mut self.stock, &self.config);
-        Deck::shuffle_cards(&mut self.stock, &self.config);
+        Deck::shuffle_cards(&mut self.stock, self.seed);
+    }
+
+    /// (Re)creates the deck and shuffles it with the seed for `round` (see `DeckConfig::round_seed`),
+    /// or the committed seed if a shuffle was committed to.
+    pub fn reset_for_round(&mut self, round: usize) {
+        self.seed = self
+            .committed_seed
+            .take()
+            .or_else(|| self.config.round_seed(round));
+        self.reset();
     }
 
     /// Draw `amount` cards from the deck stock.
@@ -164,15 +222,27 @@ impl Deck {
         self.discard_pile.append(cards);
     }
 
+    /// Drains `cards` into the bottom of the stock.
+    pub fn return_to_stock(&mut self, cards: &mut Vec<Card>) {
+        self.stock.splice(0..0, cards.drain(..));
+    }
+
     /// Add a single card onto the discard pile.
     pub fn add_to_discard_pile(&mut self, card: Card) {
         self.discard_pile.push(card);
     }
 
     /// Reset the stock by moving the discard pile into it and shuffling.
+    ///
+    /// If the deck is seeded, each reshuffle uses a new seed derived from the deck's seed (see `reshuffle_seed`).
     pub fn shuffle_discarded(&mut self) {
         self.stock.append(&mut self.discard_pile);
-        self.stock.shuffle(&mut rand::thread_rng());
+        self.reshuffles += 1;
+        let seed = self.seed.map(|seed| match seed {
+            0 => 0,
+            _ => reshuffle_seed(seed, self.reshuffles),
+        });
+        Deck::shuffle_cards(&mut self.stock, seed);
     }
 
     /// Reset the stock by moving the discard pile into it and turning it over.
@@ -186,6 +256,11 @@ impl Deck {
         &self.config
     }
 
+    /// Get the seed the deck was last shuffled with, if any.
+    pub fn seed(&self) -> Option<u64> {
+        self.seed
+    }
+
     /// Get a reference to the deck stock.
     pub fn stock(&self) -> &Vec<Card> {
         &self.stock
@@ -197,7 +272,7 @@ impl Deck {
     }
 
     /// Generating cards into a `stock` based on `config`.
-    fn generate_cards(stock: &mut Vec<Card>, config: &Arc<DeckConfig>) {
+    pub(crate) fn generate_cards(stock: &mut Vec<Card>, config: &Arc<DeckConfig>) {
         for _ in 0..config.pack_count {
             for rank in Rank::iter() {
                 if rank == Rank::Joker {
@@ -228,9 +303,9 @@ impl Deck {
         }
     }
 
-    /// Shuffles cards in a `stock` based on `config`.
-    fn shuffle_cards(stock: &mut Vec<Card>, config: &Arc<DeckConfig>) {
-        match config.shuffle_seed {
+    /// Shuffles cards in a `stock` based on `seed`, where `0` results in no shuffle.
+    fn shuffle_cards(stock: &mut [Card], seed: Option<u64>) {
+        match seed {
             Some(seed) => {
                 if seed != 0 {
                     stock.shuffle(&mut StdRng::seed_from_u64(seed));
@@ -2,7 +2,7 @@
 //! - `Set`, cards with the same rank
 //! - `Run`, consecutive cards of the same suit
 
-use std::collections::HashSet;
+use std::{collections::HashSet, fmt::Display};
 
 use super::{
     card::Card,
@@ -48,6 +48,14 @@ pub enum Meld {
 }
 
 impl Meld {
+    /// Get the kind of the meld.
+    pub fn kind(&self) -> MeldKind {
+        match self {
+            Meld::Set(_) => MeldKind::Set,
+            Meld::Run(_) => MeldKind::Run,
+        }
+    }
+
     /// Return if the meld is a set.
     pub fn is_set(&self) -> bool {
         if let Meld::Set(_) = self {
@@ -68,7 +76,7 @@ impl Meld {
     /// in which case `hand_cards` is not mutated.
     pub fn multiple(
         hand_cards: &mut Vec<Card>,
-        indices_of_melds: &Vec<Vec<usize>>,
+        indices_of_melds: &[Vec<usize>],
     ) -> Result<Vec<Self>, MeldError> {
         // Validate all card indices are unique
         let mut all_indices: Vec<_> = indices_of_melds.iter().flatten().collect();
@@ -107,6 +115,30 @@ impl Meld {
     }
 }
 
+/// The kind of a meld.
+#[derive(Debug, Display, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[strum(serialize_all = "lowercase")]
+pub enum MeldKind {
+    Set,
+    Run,
+}
+
+/// Displays the meld's cards in square brackets, like `[3♣ 4♣ 5♣]`.
+///
+/// This can be parsed back with [`parse_meld`](super::notation::parse_meld).
+impl Display for Meld {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let cards = self
+            .cards()
+            .iter()
+            .map(|card| card.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+        write!(f, "[{cards}]")
+    }
+}
+
 impl Meldable for Meld {
     fn new(hand_cards: &mut Vec<Card>, indices: &[usize]) -> Result<Self, MeldError>
     where
@@ -400,7 +432,7 @@ impl Meldable for Run {
         // ...or at the top (the only 2 possible places)
         else if self.cards[self.cards.len() - 1].same_suit_consecutive_rank(layoff_card) {
             self.cards.push(hand_cards.remove(index));
-            return Ok(());
+            Ok(())
         } else {
             Err(MeldError::InvalidLayoff)
         }
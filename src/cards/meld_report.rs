@@ -0,0 +1,442 @@
+//! Contains the `MeldReport`, which explains why cards do or don't form a valid meld.
+//!
+//! `Meld::valid` only says *that* cards don't form a meld (eg, `MeldError::InvalidRun`); a report says why,
+//! naming the offending cards, and suggests single changes which would make them valid:
+//!
+//! ```rust
+//! let report = Meld::report(player.cards(), &[0, 3, 4])?;
+//! if !report.is_valid() {
+//!     // eg, "not a set: 5♥ at index 1 isn't a 3; not a run: 3♣ at index 0 isn't a ♥ ..."
+//!     println!("{report}");
+//! }
+//! ```
+//!
+//! The checks mirror `Set::valid` and `Run::valid`, so a report always agrees with them.
+
+use super::{
+    card::{Card, CardData},
+    deck::Deck,
+    meld::{Meld, MeldError, MeldKind},
+    suit_rank::{Rank, Suit},
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+    hash::Hash,
+};
+
+/// The fewest cards in a meld.
+const MINIMUM_CARDS: usize = 3;
+
+/// The most fixes listed in a report's `Display` output.
+const DISPLAYED_FIXES: usize = 3;
+
+/// Why cards do or don't form a valid meld.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeldReport {
+    /// The cards, in the order given.
+    pub cards: Vec<CardData>,
+    /// Why the cards don't form a set; empty if they do.
+    pub set_problems: Vec<MeldProblem>,
+    /// Why the cards don't form a run; empty if they do.
+    pub run_problems: Vec<MeldProblem>,
+    /// Single changes which would make the cards a valid meld; empty if they already are one.
+    pub fixes: Vec<MeldFix>,
+}
+
+/// A reason why cards don't form a meld.
+///
+/// Positions are indices into the cards being melded (not the hand).
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeldProblem {
+    /// There aren't enough cards.
+    TooFewCards { provided: usize, minimum: usize },
+    /// Every card is a wildcard, so there's nothing to decide what the meld is.
+    OnlyWildcards,
+    /// A card in a set doesn't have the same rank as the rest.
+    MixedRank {
+        position: usize,
+        card: CardData,
+        rank: Rank,
+    },
+    /// A card in a run doesn't have the same suit as the normal card before it.
+    MixedSuit {
+        position: usize,
+        card: CardData,
+        suit: Suit,
+    },
+    /// A card in a run has the same rank as the normal card before it.
+    DuplicateRank { position: usize, card: CardData },
+    /// A card in a run has a lower rank than the normal card before it; runs must be in ascending order.
+    OutOfOrder { position: usize, card: CardData },
+    /// There are `missing` ranks between two consecutive normal cards in a run.
+    Gap {
+        from: CardData,
+        to: CardData,
+        missing: usize,
+    },
+    /// Bridging a run's breaks needs `needed` wildcards (one per break), but there are only `available`.
+    NotEnoughWildcards { needed: usize, available: usize },
+}
+
+/// A single change which would make cards a valid meld.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MeldFix {
+    /// The change to make.
+    pub change: MeldChange,
+    /// The kind of meld the cards would form.
+    pub kind: MeldKind,
+}
+
+/// A change to the cards being melded.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum MeldChange {
+    /// Remove the card at `position`.
+    Remove { position: usize, card: CardData },
+    /// Replace the card at `position` with `with`.
+    Replace {
+        position: usize,
+        card: CardData,
+        with: CardData,
+    },
+    /// Add `card` so that it's at `position`.
+    Add { position: usize, card: CardData },
+}
+
+impl Meld {
+    /// Report on whether the cards in `hand_cards` indexed by `indices` form a valid meld, and if not, why not.
+    ///
+    /// Returns an `Err` if an index is out of bounds or repeated.
+    pub fn report(hand_cards: &[Card], indices: &[usize]) -> Result<MeldReport, MeldError> {
+        for (i, index) in indices.iter().enumerate() {
+            if indices[..i].contains(index) {
+                return Err(MeldError::DuplicateCardIndex);
+            }
+        }
+        let cards = indices
+            .iter()
+            .map(|&i| {
+                hand_cards
+                    .get(i)
+                    .cloned()
+                    .ok_or(MeldError::InvalidCardIndex)
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let set_problems = set_problems(&cards);
+        let run_problems = run_problems(&cards);
+        let fixes = match set_problems.is_empty() || run_problems.is_empty() {
+            true => Vec::new(),
+            false => fixes(&cards),
+        };
+        Ok(MeldReport {
+            cards: cards.iter().map(Card::data).collect(),
+            set_problems,
+            run_problems,
+            fixes,
+        })
+    }
+}
+
+impl MeldReport {
+    /// Whether the cards form a valid meld.
+    pub fn is_valid(&self) -> bool {
+        self.kind().is_some()
+    }
+
+    /// Get the kind of meld the cards form (preferring a set, as `Meld::new` does), if they form one.
+    pub fn kind(&self) -> Option<MeldKind> {
+        if self.set_problems.is_empty() {
+            Some(MeldKind::Set)
+        } else if self.run_problems.is_empty() {
+            Some(MeldKind::Run)
+        } else {
+            None
+        }
+    }
+}
+
+/// Find why `cards` don't form a set.
+pub(crate) fn set_problems(cards: &[Card]) -> Vec<MeldProblem> {
+    let mut problems = too_few_cards(cards);
+    let normal_cards: Vec<_> = cards
+        .iter()
+        .enumerate()
+        .filter(|(_, card)| !card.is_wildcard())
+        .collect();
+    let Some(rank) = most_common(normal_cards.iter().map(|(_, card)| card.rank)) else {
+        if !cards.is_empty() {
+            problems.push(MeldProblem::OnlyWildcards);
+        }
+        return problems;
+    };
+    for (position, card) in normal_cards {
+        if card.rank != rank {
+            problems.push(MeldProblem::MixedRank {
+                position,
+                card: card.data(),
+                rank,
+            });
+        }
+    }
+    problems
+}
+
+/// Find why `cards` don't form a run.
+///
+/// As in `Run::valid`, each break between one normal card and the next (whether a different suit, a repeated
+/// or lower rank, or missing ranks) needs a wildcard to bridge it, wherever the wildcards are.
+pub(crate) fn run_problems(cards: &[Card]) -> Vec<MeldProblem> {
+    let mut problems = too_few_cards(cards);
+    let (wildcards, normal_cards): (Vec<_>, Vec<_>) = cards
+        .iter()
+        .enumerate()
+        .partition(|(_, card)| card.is_wildcard());
+    if normal_cards.is_empty() {
+        if !cards.is_empty() {
+            problems.push(MeldProblem::OnlyWildcards);
+        }
+        return problems;
+    }
+
+    let breaks: Vec<_> = normal_cards
+        .windows(2)
+        .filter(|pair| !pair[0].1.same_suit_consecutive_rank(pair[1].1))
+        .map(|pair| {
+            let ((_, previous), (position, card)) = (pair[0], pair[1]);
+            let (rank, previous_rank) = (card.value() / 4, previous.value() / 4);
+            if card.suit != previous.suit {
+                MeldProblem::MixedSuit {
+                    position,
+                    card: card.data(),
+                    suit: previous.suit,
+                }
+            } else if rank == previous_rank {
+                MeldProblem::DuplicateRank {
+                    position,
+                    card: card.data(),
+                }
+            } else if rank < previous_rank {
+                MeldProblem::OutOfOrder {
+                    position,
+                    card: card.data(),
+                }
+            } else {
+                MeldProblem::Gap {
+                    from: previous.data(),
+                    to: card.data(),
+                    missing: usize::from(rank - previous_rank - 1),
+                }
+            }
+        })
+        .collect();
+
+    if breaks.len() > wildcards.len() {
+        let has_wildcards = cards[0].deck_config.wildcard_rank.is_some();
+        if has_wildcards {
+            problems.push(MeldProblem::NotEnoughWildcards {
+                needed: breaks.len(),
+                available: wildcards.len(),
+            });
+        }
+        problems.extend(breaks);
+    }
+    problems
+}
+
+fn too_few_cards(cards: &[Card]) -> Vec<MeldProblem> {
+    match cards.len() < MINIMUM_CARDS {
+        true => vec![MeldProblem::TooFewCards {
+            provided: cards.len(),
+            minimum: MINIMUM_CARDS,
+        }],
+        false => Vec::new(),
+    }
+}
+
+/// Get the most common item, preferring whichever comes first on ties.
+fn most_common<T: Copy + Eq + Hash>(items: impl Iterator<Item = T>) -> Option<T> {
+    let mut counts: Vec<(T, usize)> = Vec::new();
+    for item in items {
+        match counts.iter_mut().find(|(other, _)| *other == item) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((item, 1)),
+        }
+    }
+    counts
+        .iter()
+        .rev()
+        .max_by_key(|(_, count)| *count)
+        .map(|(item, _)| *item)
+}
+
+/// Find every single change which would make `cards` a valid meld.
+///
+/// Only cards which are still in the deck (counting those already in `cards`) are added or swapped in.
+/// A card is only suggested to be added at the first position where it'd work.
+fn fixes(cards: &[Card]) -> Vec<MeldFix> {
+    let Some(deck_config) = cards.first().map(Card::deck_config) else {
+        return Vec::new();
+    };
+    let mut copies: HashMap<CardData, usize> = HashMap::new();
+    for card in Deck::new((*deck_config).clone()).stock() {
+        *copies.entry(card.data()).or_default() += 1;
+    }
+    let mut candidates: Vec<_> = copies.keys().copied().collect();
+    candidates.sort();
+    let available = |cards: &[Card]| {
+        cards.iter().all(|card| {
+            let count = cards.iter().filter(|c| *c == card).count();
+            count <= copies.get(&card.data()).copied().unwrap_or(0)
+        })
+    };
+
+    let mut fixes = Vec::new();
+    let mut try_change = |changed: Vec<Card>, change: MeldChange| {
+        for (kind, problems) in [
+            (MeldKind::Set, set_problems(&changed)),
+            (MeldKind::Run, run_problems(&changed)),
+        ] {
+            if problems.is_empty() && available(&changed) {
+                fixes.push(MeldFix {
+                    change: change.clone(),
+                    kind,
+                });
+            }
+        }
+    };
+
+    for (position, card) in cards.iter().enumerate() {
+        let mut changed = cards.to_vec();
+        changed.remove(position);
+        try_change(
+            changed,
+            MeldChange::Remove {
+                position,
+                card: card.data(),
+            },
+        );
+    }
+    for (position, card) in cards.iter().enumerate() {
+        for &with in candidates.iter().filter(|&&with| with != card.data()) {
+            let mut changed = cards.to_vec();
+            changed[position] = Card::from_card_data(with, deck_config.clone());
+            try_change(
+                changed,
+                MeldChange::Replace {
+                    position,
+                    card: card.data(),
+                    with,
+                },
+            );
+        }
+    }
+    for &card in &candidates {
+        for position in 0..=cards.len() {
+            let mut changed = cards.to_vec();
+            changed.insert(position, Card::from_card_data(card, deck_config.clone()));
+            try_change(changed, MeldChange::Add { position, card });
+        }
+    }
+
+    // only keep the first position a card can be added at, for each kind of meld
+    let mut added = Vec::new();
+    fixes.retain(|fix| match fix.change {
+        MeldChange::Add { card, .. } => {
+            let first = !added.contains(&(card, fix.kind));
+            added.push((card, fix.kind));
+            first
+        }
+        _ => true,
+    });
+    fixes
+}
+
+/// Displays the problems like `5♥ at index 1 isn't a 3`.
+impl Display for MeldProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            MeldProblem::TooFewCards { provided, minimum } => {
+                write!(
+                    f,
+                    "there are {provided} cards, but a meld needs at least {minimum}"
+                )
+            }
+            MeldProblem::OnlyWildcards => write!(f, "every card is a wildcard"),
+            MeldProblem::MixedRank {
+                position,
+                card,
+                rank,
+            } => write!(f, "{card} at index {position} isn't a {}", rank.as_str()),
+            MeldProblem::MixedSuit {
+                position,
+                card,
+                suit,
+            } => write!(f, "{card} at index {position} isn't a {}", suit.as_str()),
+            MeldProblem::DuplicateRank { position, card } => {
+                write!(f, "{card} at index {position} repeats the rank before it")
+            }
+            MeldProblem::OutOfOrder { position, card } => {
+                write!(
+                    f,
+                    "{card} at index {position} is lower than the card before it"
+                )
+            }
+            MeldProblem::Gap { from, to, missing } => {
+                write!(f, "{missing} missing between {from} and {to}")
+            }
+            MeldProblem::NotEnoughWildcards { needed, available } => write!(
+                f,
+                "the breaks need {needed} wildcards, but there's only {available}"
+            ),
+        }
+    }
+}
+
+/// Displays the fix like `replace 5♥ at index 1 with 3♥ to make a set`.
+impl Display for MeldFix {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.change {
+            MeldChange::Remove { position, card } => {
+                write!(f, "remove {card} at index {position}")?
+            }
+            MeldChange::Replace {
+                position,
+                card,
+                with,
+            } => write!(f, "replace {card} at index {position} with {with}")?,
+            MeldChange::Add { position, card } => write!(f, "add {card} at index {position}")?,
+        }
+        write!(f, " to make a {}", self.kind)
+    }
+}
+
+/// Displays the report on one line, like `a valid run`, or
+/// `not a set: ...; not a run: ...; try to remove 5♥ at index 1 to make a set`.
+impl Display for MeldReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(kind) = self.kind() {
+            return write!(f, "a valid {kind}");
+        }
+        let join = |items: Vec<String>| items.join(", ");
+        write!(
+            f,
+            "not a set: {}; not a run: {}",
+            join(self.set_problems.iter().map(ToString::to_string).collect()),
+            join(self.run_problems.iter().map(ToString::to_string).collect())
+        )?;
+        if !self.fixes.is_empty() {
+            let fixes = self.fixes.iter().take(DISPLAYED_FIXES);
+            write!(
+                f,
+                "; try to {}",
+                join(fixes.map(ToString::to_string).collect())
+            )?;
+        }
+        Ok(())
+    }
+}
@@ -3,6 +3,8 @@
 pub mod card;
 pub mod deck;
 pub mod meld;
+pub mod meld_report;
+pub mod notation;
 pub mod suit_rank;
 
 #[cfg(test)]
@@ -0,0 +1,71 @@
+//! Contains a human-readable text notation for hands and melds, building on the `FromStr`/`Display`
+//! impls of `CardData`.
+//!
+//! ## Cards
+//! A card is written as its rank followed by its suit. Both the Unicode form output by `Display`
+//! and ASCII forms are accepted:
+//! - `10♥`, `10h`, `TH` and `th` are all the Ten of Hearts
+//! - `Jk`, `Joker`, `🃏` and `🃏🃏` are all a joker
+//!
+//! ## Hands
+//! A hand is a list of cards separated by whitespace and/or commas, such as `"3♣ 4♣ 5♣"` or `"3c,4c,5c"`.
+//!
+//! ## Melds
+//! A meld is a hand enclosed in square brackets, such as `"[3♣ 4♣ 5♣]"`; this is also what `Meld`'s
+//! `Display` impl outputs, so melds can be round-tripped.
+
+use super::{
+    card::{Card, CardData, ParseCardError},
+    deck::DeckConfig,
+    meld::{Meld, MeldError, Meldable},
+};
+use std::sync::Arc;
+use thiserror::Error;
+
+/// Parse a hand of cards, separated by whitespace and/or commas.
+///
+/// An empty (or whitespace-only) string results in an empty hand.
+pub fn parse_cards(s: &str) -> Result<Vec<CardData>, ParseCardError> {
+    s.split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|token| !token.is_empty())
+        .map(str::parse)
+        .collect()
+}
+
+/// Format a hand of cards as space-separated Unicode notation, which can be parsed back with `parse_cards`.
+pub fn format_cards(cards: &[CardData]) -> String {
+    cards
+        .iter()
+        .map(|card| card.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse a meld enclosed in square brackets (such as `[3♣ 4♣ 5♣]`),
+/// validating it against `deck_config`.
+///
+/// The cards must be given in a valid meld order (ie, ascending for runs), as with `Meld::new`.
+pub fn parse_meld(s: &str, deck_config: Arc<DeckConfig>) -> Result<Meld, ParseMeldError> {
+    let s = s.trim();
+    let inner = s
+        .strip_prefix('[')
+        .and_then(|s| s.strip_suffix(']'))
+        .ok_or_else(|| ParseMeldError::MissingBrackets(s.to_string()))?;
+    let mut cards: Vec<_> = parse_cards(inner)?
+        .into_iter()
+        .map(|card| Card::from_card_data(card, deck_config.clone()))
+        .collect();
+    let indices: Vec<_> = (0..cards.len()).collect();
+    Ok(Meld::new(&mut cards, &indices)?)
+}
+
+/// Errors from parsing a meld from text.
+#[derive(Error, Debug, Clone, PartialEq, Eq)]
+pub enum ParseMeldError {
+    #[error("Expected a meld enclosed in brackets, like [3♣ 4♣ 5♣], but got {0:?}")]
+    MissingBrackets(String),
+    #[error("{0}")]
+    InvalidCard(#[from] ParseCardError),
+    #[error("The cards don't form a valid meld: {0}")]
+    InvalidMeld(#[from] MeldError),
+}
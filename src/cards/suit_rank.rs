@@ -1,6 +1,9 @@
 //! Contains the basic `Suit` and `Rank`,
 //! which essentially define a `Card`.
 
+use std::str::FromStr;
+
+use super::card::ParseCardError;
 use strum::EnumIter;
 
 /// Poker suits.
@@ -27,6 +30,29 @@ impl Suit {
     }
 }
 
+/// Parses a suit from either its Unicode symbol (as output by `as_str`)
+/// or its case-insensitive ASCII letter (`C`, `D`, `H`, `S`).
+impl FromStr for Suit {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "🃏" => Ok(Suit::Joker),
+            "♣" | "♧" => Ok(Suit::Clubs),
+            "♦" | "♢" => Ok(Suit::Diamonds),
+            "♥" | "♡" => Ok(Suit::Hearts),
+            "♠" | "♤" => Ok(Suit::Spades),
+            _ => match s.to_ascii_uppercase().as_str() {
+                "C" => Ok(Suit::Clubs),
+                "D" => Ok(Suit::Diamonds),
+                "H" => Ok(Suit::Hearts),
+                "S" => Ok(Suit::Spades),
+                _ => Err(ParseCardError::InvalidSuit(s.to_string())),
+            },
+        }
+    }
+}
+
 /// Poker ranks.    
 #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, EnumIter)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -68,3 +94,34 @@ impl Rank {
         }
     }
 }
+
+/// Parses a rank from either its short form (as output by `as_str`),
+/// or the ASCII alternatives `T` for `Ten` and `Jk` for `Joker`.
+///
+/// Letters are case-insensitive.
+impl FromStr for Rank {
+    type Err = ParseCardError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "🃏" {
+            return Ok(Rank::Joker);
+        }
+        match s.to_ascii_uppercase().as_str() {
+            "JK" | "JOKER" => Ok(Rank::Joker),
+            "A" => Ok(Rank::Ace),
+            "2" => Ok(Rank::Two),
+            "3" => Ok(Rank::Three),
+            "4" => Ok(Rank::Four),
+            "5" => Ok(Rank::Five),
+            "6" => Ok(Rank::Six),
+            "7" => Ok(Rank::Seven),
+            "8" => Ok(Rank::Eight),
+            "9" => Ok(Rank::Nine),
+            "10" | "T" => Ok(Rank::Ten),
+            "J" => Ok(Rank::Jack),
+            "Q" => Ok(Rank::Queen),
+            "K" => Ok(Rank::King),
+            _ => Err(ParseCardError::InvalidRank(s.to_string())),
+        }
+    }
+}
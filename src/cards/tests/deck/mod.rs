@@ -1,5 +1,6 @@
 #[cfg(test)]
 mod tests {
+    use crate::cards::deck::reshuffle_seed;
     use crate::cards::deck::Deck;
     use crate::cards::deck::DeckConfig;
     use crate::cards::suit_rank::Rank;
@@ -121,4 +122,70 @@ mod tests {
             .windows(2)
             .all(|w| w[0] > w[1]));
     }
+
+    #[test]
+    fn round_seeds() {
+        let mut cfg = DeckConfig::new();
+        assert_eq!(cfg.round_seed(2), None);
+
+        cfg.shuffle_seed = Some(0);
+        assert_eq!(cfg.round_seed(2), Some(0));
+
+        cfg.shuffle_seed = Some(7);
+        assert_eq!(cfg.round_seed(0), Some(7));
+        assert_eq!(cfg.round_seed(1), Some(7));
+        let round_2 = cfg.round_seed(2).unwrap();
+        let round_3 = cfg.round_seed(3).unwrap();
+        assert!(round_2 != 7 && round_3 != 7 && round_2 != round_3);
+    }
+
+    #[test]
+    fn reshuffle_seeds_differ_from_round_seeds() {
+        let mut cfg = DeckConfig::new();
+        cfg.shuffle_seed = Some(7);
+        // round 1 is shuffled with the raw seed, so its reshuffles must not reuse the later rounds' seeds
+        for n in 1..10 {
+            let reshuffle = reshuffle_seed(7, n);
+            assert_ne!(Some(reshuffle), cfg.round_seed(n as usize));
+            assert!((2..10).all(|round| cfg.round_seed(round) != Some(reshuffle)));
+        }
+        assert_ne!(reshuffle_seed(7, 1), reshuffle_seed(7, 2));
+    }
+
+    #[test]
+    fn reset_for_round_deck() {
+        let mut cfg = DeckConfig::new();
+        cfg.shuffle_seed = Some(7);
+        let mut deck = Deck::new(cfg.clone());
+        let mut other_deck = Deck::new(cfg);
+        assert_eq!(deck, other_deck);
+
+        deck.reset_for_round(2);
+        assert_ne!(deck.stock(), other_deck.stock());
+        assert_eq!(deck.seed(), deck.config().round_seed(2));
+
+        other_deck.reset_for_round(2);
+        assert_eq!(deck, other_deck);
+    }
+
+    #[test]
+    fn seeded_shuffle_discarded_deck() {
+        let mut cfg = DeckConfig::new();
+        cfg.shuffle_seed = Some(7);
+        let mut deck = Deck::new(cfg);
+        let mut cards = deck.draw(52).unwrap();
+        deck.add_multiple_to_discard_pile(&mut cards);
+        let mut other_deck = deck.clone();
+
+        // each reshuffle is reproducible, but differs from the last
+        deck.shuffle_discarded();
+        other_deck.shuffle_discarded();
+        assert_eq!(deck, other_deck);
+
+        let first_shuffle = deck.stock().clone();
+        let mut cards = deck.draw(52).unwrap();
+        deck.add_multiple_to_discard_pile(&mut cards);
+        deck.shuffle_discarded();
+        assert_ne!(deck.stock(), &first_shuffle);
+    }
 }
@@ -45,6 +45,6 @@ pub fn create_run_cards(
     // You'll need to implement rank iteration logic based on your Rank enum
     // This is a simplified version
     (0..length)
-        .map(|i| create_card(start_rank, suit, config.clone())) // Placeholder - needs proper rank arithmetic
+        .map(|_| create_card(start_rank, suit, config.clone())) // Placeholder - needs proper rank arithmetic
         .collect()
 }
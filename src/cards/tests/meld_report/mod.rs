@@ -0,0 +1,219 @@
+#[cfg(test)]
+mod tests {
+    use crate::cards::{
+        card::{Card, CardData},
+        deck::DeckConfig,
+        meld::{Meld, MeldError, MeldKind, Meldable, Run},
+        meld_report::{MeldChange, MeldFix, MeldProblem},
+        notation::parse_cards,
+        suit_rank::{Rank, Suit},
+    };
+    use std::sync::Arc;
+
+    fn hand(s: &str, config: &Arc<DeckConfig>) -> Vec<Card> {
+        parse_cards(s)
+            .unwrap()
+            .into_iter()
+            .map(|card| Card::from_card_data(card, config.clone()))
+            .collect()
+    }
+
+    fn card(s: &str) -> CardData {
+        s.parse().unwrap()
+    }
+
+    fn wildcard_config() -> Arc<DeckConfig> {
+        let mut config = DeckConfig::new();
+        config.wildcard_rank = Some(Rank::Jack);
+        Arc::new(config)
+    }
+
+    #[test]
+    fn valid_melds_have_no_problems() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("3♣ 3♦ 3♥ 4♣ 5♣", &config);
+
+        let set = Meld::report(&cards, &[0, 1, 2]).unwrap();
+        assert_eq!(set.kind(), Some(MeldKind::Set));
+        assert!(set.fixes.is_empty());
+        assert_eq!(set.to_string(), "a valid set");
+
+        let run = Meld::report(&cards, &[0, 3, 4]).unwrap();
+        assert_eq!(run.kind(), Some(MeldKind::Run));
+        assert!(run.run_problems.is_empty());
+        assert!(!run.set_problems.is_empty());
+    }
+
+    #[test]
+    fn mixed_suits_and_ranks_name_the_card() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("5♥ 6♥ 7♣ 5♣ 5♦", &config);
+        let report = Meld::report(&cards, &[0, 1, 2]).unwrap();
+        assert!(!report.is_valid());
+        assert_eq!(
+            report.run_problems,
+            vec![MeldProblem::MixedSuit {
+                position: 2,
+                card: card("7♣"),
+                suit: Suit::Hearts,
+            }]
+        );
+        assert!(report.fixes.contains(&MeldFix {
+            change: MeldChange::Replace {
+                position: 2,
+                card: card("7♣"),
+                with: card("7♥"),
+            },
+            kind: MeldKind::Run,
+        }));
+
+        // the odd one out is whichever card doesn't have the most common rank
+        let report = Meld::report(&cards, &[1, 3, 4]).unwrap();
+        assert_eq!(
+            report.set_problems,
+            vec![MeldProblem::MixedRank {
+                position: 0,
+                card: card("6♥"),
+                rank: Rank::Five,
+            }]
+        );
+        assert!(report.fixes.contains(&MeldFix {
+            change: MeldChange::Replace {
+                position: 0,
+                card: card("6♥"),
+                with: card("5♥"),
+            },
+            kind: MeldKind::Set,
+        }));
+        // 5♣ and 5♦ are already in the meld, so they aren't suggested again
+        assert!(report.fixes.iter().all(|fix| !matches!(
+            fix.change,
+            MeldChange::Replace { with, .. } if with == card("5♣") || with == card("5♦")
+        )));
+    }
+
+    #[test]
+    fn a_wildcard_bridges_any_break() {
+        let config = wildcard_config();
+        let cards = hand("5♥ J♣ 8♥ 9♠", &config);
+
+        // one wildcard makes up for two missing ranks, or a change of suit
+        for indices in [[0, 1, 2], [0, 1, 3]] {
+            assert!(Run::valid(&cards, &indices).is_ok());
+            assert!(Meld::report(&cards, &indices).unwrap().is_valid());
+        }
+
+        // but each break needs its own wildcard
+        let report = Meld::report(&cards, &[0, 2, 3, 1]).unwrap();
+        assert_eq!(
+            report.run_problems,
+            vec![
+                MeldProblem::NotEnoughWildcards {
+                    needed: 2,
+                    available: 1,
+                },
+                MeldProblem::Gap {
+                    from: card("5♥"),
+                    to: card("8♥"),
+                    missing: 2,
+                },
+                MeldProblem::MixedSuit {
+                    position: 2,
+                    card: card("9♠"),
+                    suit: Suit::Hearts,
+                },
+            ]
+        );
+        assert!(matches!(
+            Run::valid(&cards, &[0, 2, 3, 1]),
+            Err(MeldError::InsufficientWildcards)
+        ));
+        assert!(report.fixes.contains(&MeldFix {
+            change: MeldChange::Remove {
+                position: 2,
+                card: card("9♠"),
+            },
+            kind: MeldKind::Run,
+        }));
+    }
+
+    #[test]
+    fn gaps_are_invalid_without_wildcards() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("5♥ 6♥ 8♥", &config);
+        let report = Meld::report(&cards, &[0, 1, 2]).unwrap();
+        assert_eq!(
+            report.run_problems,
+            vec![MeldProblem::Gap {
+                from: card("6♥"),
+                to: card("8♥"),
+                missing: 1,
+            }]
+        );
+        assert!(matches!(
+            Run::valid(&cards, &[0, 1, 2]),
+            Err(MeldError::InvalidRun)
+        ));
+    }
+
+    #[test]
+    fn runs_must_ascend_without_repeats() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("7♥ 5♥ 6♥ 6♥", &config);
+
+        let report = Meld::report(&cards, &[1, 2, 3]).unwrap();
+        assert_eq!(
+            report.run_problems,
+            vec![MeldProblem::DuplicateRank {
+                position: 2,
+                card: card("6♥"),
+            }]
+        );
+
+        let report = Meld::report(&cards, &[0, 1, 2]).unwrap();
+        assert!(report.run_problems.contains(&MeldProblem::OutOfOrder {
+            position: 1,
+            card: card("5♥"),
+        }));
+        assert!(matches!(
+            Run::valid(&cards, &[0, 1, 2]),
+            Err(MeldError::InvalidRun)
+        ));
+    }
+
+    #[test]
+    fn reports_display_on_one_line() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("3♣ 3♦ 4♥", &config);
+        let report = Meld::report(&cards, &[0, 1, 2]).unwrap();
+        assert_eq!(
+            report.to_string(),
+            "not a set: 4♥ at index 2 isn't a 3; not a run: 3♦ at index 1 isn't a ♣, \
+            4♥ at index 2 isn't a ♦; try to replace 4♥ at index 2 with 3♥ to make a set, \
+            replace 4♥ at index 2 with 3♠ to make a set"
+        );
+
+        let report = Meld::report(&cards, &[0, 1]).unwrap();
+        assert_eq!(
+            report.set_problems,
+            vec![MeldProblem::TooFewCards {
+                provided: 2,
+                minimum: 3,
+            }]
+        );
+    }
+
+    #[test]
+    fn bad_indices_fail() {
+        let config = Arc::new(DeckConfig::new());
+        let cards = hand("3♣ 3♦ 3♥", &config);
+        assert!(matches!(
+            Meld::report(&cards, &[0, 1, 1]),
+            Err(MeldError::DuplicateCardIndex)
+        ));
+        assert!(matches!(
+            Meld::report(&cards, &[0, 1, 3]),
+            Err(MeldError::InvalidCardIndex)
+        ));
+    }
+}
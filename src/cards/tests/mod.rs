@@ -1,5 +1,7 @@
 mod card;
 mod deck;
 mod meld;
+mod meld_report;
+mod notation;
 mod run;
 mod set;
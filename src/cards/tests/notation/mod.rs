@@ -0,0 +1,135 @@
+#[cfg(test)]
+mod tests {
+    use crate::cards::{
+        card::{CardData, ParseCardError},
+        deck::DeckConfig,
+        meld::Meldable,
+        notation::{format_cards, parse_cards, parse_meld, ParseMeldError},
+        suit_rank::{Rank, Suit},
+    };
+    use std::sync::Arc;
+    use strum::IntoEnumIterator;
+
+    fn card(rank: Rank, suit: Suit) -> CardData {
+        CardData { rank, suit }
+    }
+
+    #[test]
+    fn parse_unicode_and_ascii_cards() {
+        let ten_hearts = card(Rank::Ten, Suit::Hearts);
+        for s in ["10♥", "TH", "th", "10h", "10H", " 10♥ "] {
+            assert_eq!(s.parse::<CardData>().unwrap(), ten_hearts, "{s}");
+        }
+
+        let joker = card(Rank::Joker, Suit::Joker);
+        for s in ["Jk", "JK", "joker", "🃏", "🃏🃏"] {
+            assert_eq!(s.parse::<CardData>().unwrap(), joker, "{s}");
+        }
+
+        assert_eq!(
+            "Qs".parse::<CardData>().unwrap(),
+            card(Rank::Queen, Suit::Spades)
+        );
+        assert_eq!(
+            "A♣".parse::<CardData>().unwrap(),
+            card(Rank::Ace, Suit::Clubs)
+        );
+    }
+
+    #[test]
+    /// Every card's `Display` output parses back into the same card.
+    fn card_display_round_trip() {
+        for rank in Rank::iter().filter(|&r| r != Rank::Joker) {
+            for suit in Suit::iter().filter(|&s| s != Suit::Joker) {
+                let data = card(rank, suit);
+                assert_eq!(data.to_string().parse::<CardData>().unwrap(), data);
+            }
+        }
+        let joker = card(Rank::Joker, Suit::Joker);
+        assert_eq!(joker.to_string().parse::<CardData>().unwrap(), joker);
+    }
+
+    #[test]
+    fn parse_invalid_cards() {
+        assert!(matches!(
+            "1H".parse::<CardData>(),
+            Err(ParseCardError::InvalidRank(_))
+        ));
+        assert!(matches!(
+            "10X".parse::<CardData>(),
+            Err(ParseCardError::InvalidSuit(_))
+        ));
+        assert!(matches!(
+            "H".parse::<CardData>(),
+            Err(ParseCardError::InvalidCard(_))
+        ));
+        assert!(matches!(
+            "".parse::<CardData>(),
+            Err(ParseCardError::InvalidCard(_))
+        ));
+        assert!(matches!(
+            "🃏H".parse::<CardData>(),
+            Err(ParseCardError::InvalidCard(_))
+        ));
+    }
+
+    #[test]
+    fn hand_round_trip() {
+        let hand = parse_cards("3c, 4♣ TH\tJk  As").unwrap();
+        assert_eq!(
+            hand,
+            vec![
+                card(Rank::Three, Suit::Clubs),
+                card(Rank::Four, Suit::Clubs),
+                card(Rank::Ten, Suit::Hearts),
+                card(Rank::Joker, Suit::Joker),
+                card(Rank::Ace, Suit::Spades),
+            ]
+        );
+        assert_eq!(format_cards(&hand), "3♣ 4♣ 10♥ 🃏🃏 A♠");
+        assert_eq!(parse_cards(&format_cards(&hand)).unwrap(), hand);
+        assert!(parse_cards("   ").unwrap().is_empty());
+    }
+
+    #[test]
+    fn meld_round_trip() {
+        let cfg = Arc::new(DeckConfig::new());
+
+        let run = parse_meld("[3c 4c 5c]", cfg.clone()).unwrap();
+        assert!(run.is_run());
+        assert_eq!(run.to_string(), "[3♣ 4♣ 5♣]");
+        assert_eq!(parse_meld(&run.to_string(), cfg.clone()).unwrap(), run);
+
+        let set = parse_meld("[7h 7s 7d]", cfg.clone()).unwrap();
+        assert!(set.is_set());
+        assert_eq!(parse_meld(&set.to_string(), cfg).unwrap(), set);
+    }
+
+    #[test]
+    fn meld_with_wildcards() {
+        let mut cfg = DeckConfig::new();
+        cfg.wildcard_rank = Some(Rank::Joker);
+        let cfg = Arc::new(cfg);
+
+        let run = parse_meld("[3c Jk 5c]", cfg).unwrap();
+        assert!(run.is_run());
+        assert_eq!(run.cards().len(), 3);
+    }
+
+    #[test]
+    fn invalid_melds() {
+        let cfg = Arc::new(DeckConfig::new());
+        assert!(matches!(
+            parse_meld("3c 4c 5c", cfg.clone()),
+            Err(ParseMeldError::MissingBrackets(_))
+        ));
+        assert!(matches!(
+            parse_meld("[3c 4x 5c]", cfg.clone()),
+            Err(ParseMeldError::InvalidCard(_))
+        ));
+        assert!(matches!(
+            parse_meld("[3c 4h 9s]", cfg),
+            Err(ParseMeldError::InvalidMeld(_))
+        ));
+    }
+}
@@ -156,7 +156,7 @@ mod tests {
                 deck_config: cfg.clone(),
             },
         ];
-        let backup_cards = cards.clone();
+        let _backup_cards = cards.clone();
         let indices = vec![2, 0, 1]; // in the wrong order
         let run = Run::new(&mut cards, &indices);
 
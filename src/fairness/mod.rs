@@ -0,0 +1,183 @@
+//! Contains verifiable fair shuffling through commit–reveal, enabled by the `fair-shuffle` feature.
+//!
+//! This lets players check that the server didn't stack the deck:
+//! 1. Before a round is dealt, the server picks a secret (its entropy), and publishes
+//!    the `ShuffleCommitment` to it.
+//! 2. Only then does each player send some entropy of their own. Since the server's entropy is already fixed,
+//!    it can't be picked to steer the shuffle once the players' entropy is known.
+//! 3. The server gathers all of the entropy into a `ShuffleReveal` (one entry per player, in seat order),
+//!    and deals the round from the seed derived from it with `FairShuffleGame::commit_shuffle`.
+//! 4. After the round, the server publishes the `ShuffleReveal`. Each player can check that their own entropy
+//!    was used in their seat with [`verify_player_entropy`], and anyone who knows every player's entropy can check
+//!    the round's deal and stock against the commitment with [`verify_round`].
+//!
+//! Checking the player entropy matters: otherwise, the server could add, drop or reorder entries
+//! after seeing the players' entropy until the shuffle suited it.
+//!
+//! ```rust
+//! // before collecting the players' entropy...
+//! let commitment = ShuffleCommitment::new(&server_secret); // publish this
+//!
+//! // ... then deal from the server's and players' entropy
+//! let reveal = ShuffleReveal::new(server_secret, vec![player_0_entropy.clone(), player_1_entropy.clone()]);
+//! game.commit_shuffle(&reveal).unwrap();
+//! game.next_round().unwrap();
+//!
+//! // ... and after the round, publish `reveal` so that it can be verified
+//! verify_player_entropy(&game, 1, 0, &player_0_entropy, &reveal).unwrap();
+//! verify_round(&game, 1, &commitment, &reveal, &[player_0_entropy, player_1_entropy]).unwrap();
+//! ```
+
+mod verify;
+
+pub use verify::{verify_player_entropy, verify_round};
+
+use crate::{
+    cards::deck::Deck,
+    game::{
+        error::GameError, r#trait::Game, state::GamePhase, variants::basic::game::BasicRummyGame,
+    },
+    wrappers::history::History,
+};
+use sha2::{Digest, Sha256};
+use std::{fmt::Display, str::FromStr};
+use thiserror::Error;
+
+/// The entropy that a fair shuffle is derived from, which is kept secret until the round ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleReveal {
+    pub server_entropy: Vec<u8>,
+    pub player_entropy: Vec<Vec<u8>>,
+}
+
+impl ShuffleReveal {
+    /// Create a new `ShuffleReveal` from the server's and players' entropy.
+    pub fn new(server_entropy: Vec<u8>, player_entropy: Vec<Vec<u8>>) -> Self {
+        Self {
+            server_entropy,
+            player_entropy,
+        }
+    }
+
+    /// Get the commitment to the server's entropy, which is published before the players' entropy is collected.
+    pub fn commitment(&self) -> ShuffleCommitment {
+        ShuffleCommitment::new(&self.server_entropy)
+    }
+
+    /// Get the shuffle seed derived from the server's and players' entropy.
+    ///
+    /// This is never `0`, since that would mean the deck isn't shuffled.
+    pub fn seed(&self) -> u64 {
+        let mut hasher = Sha256::new();
+        hasher.update(b"rummy-shuffle-seed");
+        // length-prefix each part, so that they can't be shifted between
+        hasher.update((self.server_entropy.len() as u64).to_le_bytes());
+        hasher.update(&self.server_entropy);
+        hasher.update((self.player_entropy.len() as u64).to_le_bytes());
+        for entropy in &self.player_entropy {
+            hasher.update((entropy.len() as u64).to_le_bytes());
+            hasher.update(entropy);
+        }
+        let hash: [u8; 32] = hasher.finalize().into();
+        let mut seed = [0; 8];
+        seed.copy_from_slice(&hash[..8]);
+        u64::from_le_bytes(seed).max(1)
+    }
+}
+
+/// A SHA-256 commitment to the server's entropy of a `ShuffleReveal`.
+///
+/// It is displayed and parsed as a lowercase hex string.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ShuffleCommitment(pub [u8; 32]);
+
+impl ShuffleCommitment {
+    /// Commit to the server's entropy.
+    pub fn new(server_entropy: &[u8]) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(b"rummy-shuffle-commitment");
+        hasher.update(server_entropy);
+        Self(hasher.finalize().into())
+    }
+}
+
+impl Display for ShuffleCommitment {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for byte in self.0 {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for ShuffleCommitment {
+    type Err = FairnessError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || FairnessError::InvalidCommitment(s.to_string());
+        if s.len() != 64 || !s.is_ascii() {
+            return Err(invalid());
+        }
+        let mut bytes = [0; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = u8::from_str_radix(&s[i * 2..i * 2 + 2], 16).map_err(|_| invalid())?;
+        }
+        Ok(Self(bytes))
+    }
+}
+
+impl Deck {
+    /// Commit the deck's next `reset_for_round` to the seed derived from `reveal`.
+    pub fn commit_shuffle(&mut self, reveal: &ShuffleReveal) {
+        self.committed_seed = Some(reveal.seed());
+    }
+}
+
+/// A `Game` whose shuffles can be committed to before dealing.
+pub trait FairShuffleGame: Game {
+    /// Commit the next round's shuffle to the seed derived from `reveal`.
+    ///
+    /// The commitment to the server's entropy must already have been published, before the players' entropy was collected.
+    ///
+    /// Returns an `Err` if the game phase isn't `RoundEnd` (ie, the current round was already dealt).
+    fn commit_shuffle(&mut self, reveal: &ShuffleReveal) -> Result<(), GameError>;
+}
+
+impl FairShuffleGame for BasicRummyGame {
+    fn commit_shuffle(&mut self, reveal: &ShuffleReveal) -> Result<(), GameError> {
+        if self.state.phase != GamePhase::RoundEnd {
+            return Err(GameError::WrongGamePhase);
+        }
+        self.state.deck.commit_shuffle(reveal);
+        Ok(())
+    }
+}
+
+impl<G: FairShuffleGame + Clone> FairShuffleGame for History<G> {
+    fn commit_shuffle(&mut self, reveal: &ShuffleReveal) -> Result<(), GameError> {
+        self.current_game.commit_shuffle(reveal)
+    }
+}
+
+/// Errors from verifying a fair shuffle.
+#[derive(Debug, Clone, Error)]
+pub enum FairnessError {
+    #[error("Invalid shuffle commitment: {0:?}")]
+    InvalidCommitment(String),
+    #[error("The revealed server entropy doesn't match the commitment")]
+    CommitmentMismatch,
+    #[error("The history has no round {0}")]
+    UnknownRound(usize),
+    #[error("Player {0} isn't in the round")]
+    UnknownPlayer(usize),
+    #[error("Expected entropy from {expected} players, but {found} were revealed")]
+    EntropyCountMismatch { expected: usize, found: usize },
+    #[error("The revealed entropy for player {player_id} doesn't match what they sent")]
+    PlayerEntropyMismatch { player_id: usize },
+    #[error("Player {player_id} wasn't dealt the cards of the committed shuffle")]
+    DealMismatch { player_id: usize },
+    #[error("The stock doesn't match the committed shuffle")]
+    StockMismatch,
+}
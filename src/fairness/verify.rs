@@ -0,0 +1,123 @@
+//! Contains the verification of a `History` round against its shuffle commitment.
+
+use super::{FairnessError, ShuffleCommitment, ShuffleReveal};
+use crate::{
+    cards::{card::Card, deck::Deck},
+    game::r#trait::Game,
+    player::Player,
+    wrappers::history::History,
+};
+
+/// Verify that `round` of `history` was dealt from the shuffle committed to by `commitment`,
+/// and the entropy sent by each player.
+///
+/// `player_entropy` is the entropy each player sent, in seat order (ie, the order of the round's players).
+///
+/// This checks that `reveal`'s server entropy matches `commitment`, and that its player entropy is exactly
+/// `player_entropy` (so the server couldn't add, drop or reorder any), then re-shuffles a deck with the revealed seed
+/// and checks that every player's hand and the remaining stock are exactly as recorded.
+pub fn verify_round<G: Game + Clone>(
+    history: &History<G>,
+    round: usize,
+    commitment: &ShuffleCommitment,
+    reveal: &ShuffleReveal,
+    player_entropy: &[Vec<u8>],
+) -> Result<(), FairnessError> {
+    if reveal.commitment() != *commitment {
+        return Err(FairnessError::CommitmentMismatch);
+    }
+
+    let state = round_game(history, round)?.get_state();
+    check_entropy_count(state.players(), reveal)?;
+    if player_entropy.len() != reveal.player_entropy.len() {
+        return Err(FairnessError::EntropyCountMismatch {
+            expected: player_entropy.len(),
+            found: reveal.player_entropy.len(),
+        });
+    }
+    for ((player, expected), found) in state
+        .players()
+        .iter()
+        .zip(player_entropy)
+        .zip(&reveal.player_entropy)
+    {
+        if expected != found {
+            return Err(FairnessError::PlayerEntropyMismatch {
+                player_id: player.id(),
+            });
+        }
+    }
+
+    let mut deck = Deck::new(state.deck().config().clone());
+    deck.commit_shuffle(reveal);
+    deck.reset_for_round(round);
+
+    for player in state.players() {
+        let dealt =
+            deck.try_draw(player.cards().len())
+                .map_err(|_| FairnessError::DealMismatch {
+                    player_id: player.id(),
+                })?;
+        if !same_cards(&dealt, player.cards()) {
+            return Err(FairnessError::DealMismatch {
+                player_id: player.id(),
+            });
+        }
+    }
+
+    if !same_cards(deck.stock(), state.deck().stock())
+        || !same_cards(deck.discard_pile(), state.deck().discard_pile())
+    {
+        return Err(FairnessError::StockMismatch);
+    }
+    Ok(())
+}
+
+/// Verify that the entropy sent by the player with ID `player_id` was used for `round` of `history`.
+///
+/// This lets each player check their own entropy without knowing the others': `reveal` must have exactly
+/// one entry per player of the round, with `entropy` in the player's seat.
+/// The rest of the round can then be checked with [`verify_round`], using the revealed player entropy.
+pub fn verify_player_entropy<G: Game + Clone>(
+    history: &History<G>,
+    round: usize,
+    player_id: usize,
+    entropy: &[u8],
+    reveal: &ShuffleReveal,
+) -> Result<(), FairnessError> {
+    let players = round_game(history, round)?.get_state().players();
+    check_entropy_count(players, reveal)?;
+    let seat = players
+        .iter()
+        .position(|player| player.id() == player_id)
+        .ok_or(FairnessError::UnknownPlayer(player_id))?;
+    if reveal.player_entropy[seat] != entropy {
+        return Err(FairnessError::PlayerEntropyMismatch { player_id });
+    }
+    Ok(())
+}
+
+/// Get the game at the start of `round` (which must have been dealt).
+fn round_game<G: Game + Clone>(history: &History<G>, round: usize) -> Result<&G, FairnessError> {
+    history
+        .get_initial_round_states()
+        .get(&round)
+        .filter(|_| round != 0)
+        .ok_or(FairnessError::UnknownRound(round))
+}
+
+/// Check that `reveal` has exactly one entry of player entropy for each of the round's `players`.
+fn check_entropy_count(players: &[Player], reveal: &ShuffleReveal) -> Result<(), FairnessError> {
+    if reveal.player_entropy.len() != players.len() {
+        return Err(FairnessError::EntropyCountMismatch {
+            expected: players.len(),
+            found: reveal.player_entropy.len(),
+        });
+    }
+    Ok(())
+}
+
+/// Whether 2 lists of cards have the same cards in the same order.
+fn same_cards(a: &[Card], b: &[Card]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| a.data() == b.data())
+}
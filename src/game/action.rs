@@ -17,10 +17,16 @@ pub enum GameInteractions {
     PlayerQuit {
         player_id: usize,
     },
+    PlayerReplaced {
+        player_id: usize,
+        new_player_id: usize,
+    },
     HandRearrangement {
         player_id: usize,
         new_arrangement: Vec<CardData>,
     },
+    Pause,
+    Resume,
 }
 
 /// The possible actions taken in a Rummy game.
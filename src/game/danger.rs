@@ -0,0 +1,173 @@
+//! Contains `discard_dangers`, which estimates how likely discarding each card in a hand is to help the next player.
+//!
+//! Only the next player can pick up a discard, so a card is risky if it:
+//! - is a wildcard,
+//! - can be laid off onto a meld on the table,
+//! - goes with a card the next player was seen picking up (the same rank, or the same suit and a nearby rank), or
+//! - has many partners (cards it could form a meld with) still unseen, which the next player could be holding.
+//!
+//! Each of those is given a rough chance of helping the next player, and the chances are combined into a score
+//! from 0 (safe) to 1 (almost certainly helps). The scores are only heuristics, meant for comparing cards in
+//! the same hand (eg, to warn about a risky discard, or for a bot to choose between cards):
+//!
+//! ```rust
+//! let mut tracker = CardTracker::new(player_id, game.get_state().deck().config());
+//! tracker.sync(&game.get_state().view_for(Some(player_id)));
+//! for danger in discard_dangers(&game, &tracker) {
+//!     if danger.score > 0.5 {
+//!         println!("discarding {} is risky: {:?}", danger.card, danger.reasons);
+//!     }
+//! }
+//! ```
+
+use crate::{
+    cards::{
+        card::{Card, CardData},
+        meld::Meldable,
+    },
+    game::{r#trait::Game, tracker::CardTracker},
+};
+use std::sync::Arc;
+
+/// The chance that a discarded wildcard helps the next player.
+const WILDCARD_CHANCE: f64 = 0.9;
+/// The chance that a card which can be laid off helps the next player.
+const LAYOFF_CHANCE: f64 = 0.8;
+/// The chance that a card helps the next player, if they're known to hold a partner for it of the same rank,
+/// or the same suit and an adjacent rank.
+const CLOSE_PARTNER_CHANCE: f64 = 0.5;
+/// The chance that a card helps the next player, if they're known to hold a partner for it of the same suit,
+/// 2 ranks away.
+const FAR_PARTNER_CHANCE: f64 = 0.25;
+/// The chance that a card helps the next player, if every one of its partners is unseen.
+const LIVE_PARTNERS_CHANCE: f64 = 0.4;
+
+/// How risky discarding a card is.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct DiscardDanger {
+    /// The index of the card in the hand.
+    pub card_index: usize,
+    /// The card itself.
+    pub card: CardData,
+    /// How likely discarding the card is to help the next player, from 0 (safe) to 1.
+    pub score: f64,
+    /// Why the card is risky.
+    pub reasons: Vec<DangerReason>,
+}
+
+/// A reason why discarding a card is risky.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DangerReason {
+    /// The card is a wildcard.
+    Wildcard,
+    /// The card can be laid off onto a meld.
+    ExtendsMeld { player_id: usize, meld_index: usize },
+    /// The next player was seen picking up `card`, which the card could form a meld with.
+    NextPlayerHolds { card: CardData },
+    /// `unseen` of the `total` cards in the deck which the card could form a meld with are unseen.
+    LivePartners { unseen: usize, total: usize },
+}
+
+/// Estimate how risky discarding each card in a player's hand is, from the perspective of `tracker`'s viewer.
+///
+/// The tracker should be up to date (see `CardTracker::sync`). Returns a danger for every card in the viewer's hand,
+/// in hand order, or nothing if the viewer isn't in the game.
+pub fn discard_dangers<G: Game>(game: &G, tracker: &CardTracker) -> Vec<DiscardDanger> {
+    let state = game.get_state();
+    let players = state.players();
+    let Some(viewer_index) = players.iter().position(|p| p.id() == tracker.viewer()) else {
+        return Vec::new();
+    };
+    let next_player_id = (1..players.len())
+        .map(|offset| &players[(viewer_index + offset) % players.len()])
+        .find(|p| p.active())
+        .map(|p| p.id());
+
+    let deck_config = Arc::new(state.deck().config().clone());
+    let to_cards = |cards: &[CardData]| -> Vec<Card> {
+        cards
+            .iter()
+            .map(|&c| Card::from_card_data(c, deck_config.clone()))
+            .collect()
+    };
+    let deck = to_cards(tracker.deck());
+    let unseen = to_cards(&tracker.unseen());
+    let next_player_known = to_cards(next_player_id.map_or(&[], |id| tracker.known_hand(id)));
+
+    let hand = players[viewer_index].cards();
+    hand.iter()
+        .enumerate()
+        .map(|(card_index, card)| {
+            let mut chances = Vec::new();
+            let mut reasons = Vec::new();
+
+            if card.is_wildcard() {
+                chances.push(WILDCARD_CHANCE);
+                reasons.push(DangerReason::Wildcard);
+            }
+
+            for player in players {
+                for (meld_index, meld) in player.melds().iter().enumerate() {
+                    let mut meld = meld.clone();
+                    if meld.layoff_card(&mut hand.clone(), card_index).is_ok() {
+                        chances.push(LAYOFF_CHANCE);
+                        reasons.push(DangerReason::ExtendsMeld {
+                            player_id: player.id(),
+                            meld_index,
+                        });
+                    }
+                }
+            }
+
+            for known in &next_player_known {
+                let chance = match partner_distance(card, known) {
+                    Some(0 | 1) => CLOSE_PARTNER_CHANCE,
+                    Some(_) => FAR_PARTNER_CHANCE,
+                    None => continue,
+                };
+                chances.push(chance);
+                reasons.push(DangerReason::NextPlayerHolds { card: known.data() });
+            }
+
+            let total = count_partners(card, &deck).saturating_sub(1);
+            // an out of date tracker can count the card itself as unseen, so this is capped at `total`
+            let unseen = count_partners(card, &unseen).min(total);
+            if unseen > 0 {
+                chances.push(LIVE_PARTNERS_CHANCE * unseen as f64 / total as f64);
+                reasons.push(DangerReason::LivePartners { unseen, total });
+            }
+
+            DiscardDanger {
+                card_index,
+                card: card.data(),
+                score: 1.0 - chances.iter().map(|chance| 1.0 - chance).product::<f64>(),
+                reasons,
+            }
+        })
+        .collect()
+}
+
+/// How far apart in rank two cards are, if they could be in the same meld:
+/// 0 for the same rank, or the rank difference for the same suit (up to 2).
+///
+/// Wildcards and jokers are left out, as they go with anything.
+fn partner_distance(card: &Card, other: &Card) -> Option<u8> {
+    if card.is_wildcard() || other.is_wildcard() || card.value() == 0 || other.value() == 0 {
+        return None;
+    }
+    if card.rank == other.rank {
+        return Some(0);
+    }
+    let distance = (card.value() / 4).abs_diff(other.value() / 4);
+    (card.suit == other.suit && distance <= 2).then_some(distance)
+}
+
+/// Count the cards in `cards` which `card` could be in a meld with (including copies of `card` itself).
+fn count_partners(card: &Card, cards: &[Card]) -> usize {
+    cards
+        .iter()
+        .filter(|other| partner_distance(card, other).is_some())
+        .count()
+}
@@ -2,10 +2,15 @@
 //! - `ActionError`, denoting the failed execution of an action
 //! - `GameError`, denoting other errors pertaining to the game's execution
 //! - `GameSetupError`, denoting failure to set up a game
+//!
+//! There's also `InvariantError`, denoting a `GameState` which is inconsistent (see `GameState::check_invariants`).
 
 use thiserror::Error;
 
-use crate::{cards::meld::MeldError, game::state::GamePhase};
+use crate::{
+    cards::{card::CardData, meld::MeldError},
+    game::state::GamePhase,
+};
 
 /// Errors that may be returned from executing a `GameAction`.
 #[derive(Debug, Clone, Error)]
@@ -34,6 +39,22 @@ pub enum FailedActionError {
     InvalidPlayerIndex,
     #[error("A meld layoff/formation failed")]
     FailedMeld(#[from] MeldError),
+    #[error(
+        "Player {player_id} can't take this action, as it's player {current_player_id}'s turn"
+    )]
+    NotYourTurn {
+        player_id: usize,
+        current_player_id: usize,
+    },
+    #[error("The game is paused")]
+    GamePaused,
+    #[error("Player {player_id}'s turn timed out")]
+    TimedOut { player_id: usize },
+    #[error("Player {player_id} timed out, but couldn't forfeit the round: {error}")]
+    ForfeitFailed {
+        player_id: usize,
+        error: Box<GameError>,
+    },
 }
 
 /// Internal errors encountered during the game.
@@ -49,6 +70,8 @@ pub enum InternalError {
     InvalidCurrentPlayer { current: usize },
     #[error("The round has no winner despite having already ended")]
     RoundHasNoWinner,
+    #[error("The game state became inconsistent: {0}")]
+    InvariantError(InvariantError),
 }
 
 /// Errors pertaining to the game itself.
@@ -62,12 +85,51 @@ pub enum GameError {
     PlayerDoesntExist,
     #[error("Tried to add a player ID when it already exists")]
     AddedPlayerAlreadyExists,
+    #[error("The game is paused")]
+    GamePaused,
+    #[error("Tried to resume a game which isn't paused")]
+    NotPaused,
+    #[error("Tried to replace a player who is still active")]
+    SeatNotVacant,
     #[error("Failed to rearrange the hand")]
     FailedHandRearrangement,
+    #[error("The game variant doesn't support this operation")]
+    NotSupported,
     #[error("The round setup failed: {0}")]
     FailedRoundSetup(#[from] GameSetupError),
     #[error("An internal error occurred {0}")]
     Internal(#[from] InternalError),
+    #[error("Applying the timeout policy to a timed out turn failed: {0}")]
+    TimeoutPolicyFailed(ActionError),
+}
+
+/// Ways in which a `GameState` can be inconsistent, as found by `GameState::check_invariants`.
+///
+/// These can't happen through normal play, so indicate a bug or a state that was edited by hand.
+#[derive(Debug, Clone, PartialEq, Eq, Error)]
+pub enum InvariantError {
+    #[error("There are {found} of {card} across the hands, melds, stock and discard pile, but the deck has {expected}")]
+    CardsNotConserved {
+        card: CardData,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Meld {meld_index} of player {player_id} is invalid: {error}")]
+    InvalidMeld {
+        player_id: usize,
+        meld_index: usize,
+        error: MeldError,
+    },
+    #[error("The current player index {current} is out of range for {player_count} players")]
+    CurrentPlayerOutOfRange { current: usize, player_count: usize },
+    #[error("It's the turn of player {player_id}, who isn't active")]
+    InactiveCurrentPlayer { player_id: usize },
+    #[error("Player {player_id} has cards before the first round")]
+    CardsBeforeFirstRound { player_id: usize },
+    #[error("Player {player_id} has no cards to discard, but is in the play phase")]
+    EmptyHandInPlay { player_id: usize },
+    #[error("The round has ended, but nobody went out")]
+    RoundEndWithoutWinner,
 }
 
 /// Errors while creating a game.
@@ -0,0 +1,125 @@
+//! Contains `GameEvent`, which describes the effects of interacting with a game
+//! (as opposed to `GameInteractions`, which describes the interactions themselves).
+//!
+//! Each event has a `EventVisibility`, so that it can be routed to just the players who may see it.
+
+use crate::{
+    cards::card::CardData,
+    game::{
+        score::{RoundScore, VariantPlayerScore},
+        state::GamePhase,
+    },
+};
+
+/// Something that happened in a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum GameEvent<P: VariantPlayerScore> {
+    /// A new round started.
+    RoundStarted { round: usize },
+    /// A player was dealt their hand for the round.
+    HandDealt {
+        player_id: usize,
+        cards: Vec<CardData>,
+    },
+    /// A player drew cards; drawing from the deck is only visible to that player.
+    CardsDrawn {
+        player_id: usize,
+        source: DrawSource,
+        cards: Vec<CardData>,
+    },
+    /// The public counterpart of a `CardsDrawn` from the deck, which doesn't reveal the cards.
+    HiddenCardsDrawn { player_id: usize, amount: usize },
+    /// A player formed a meld.
+    MeldFormed {
+        player_id: usize,
+        meld_index: usize,
+        cards: Vec<CardData>,
+    },
+    /// A player laid off a card onto a meld.
+    CardLaidOff {
+        player_id: usize,
+        target_player_id: usize,
+        meld_index: usize,
+        card: CardData,
+    },
+    /// A player laid off a card onto a meld, swapping it with a wildcard which went into their hand.
+    WildcardSwapped {
+        player_id: usize,
+        target_player_id: usize,
+        meld_index: usize,
+        card: CardData,
+        wildcard: CardData,
+    },
+    /// A player discarded a card.
+    CardDiscarded { player_id: usize, card: CardData },
+    /// It became a player's turn.
+    TurnChanged { player_id: usize },
+    /// The game phase changed.
+    PhaseChanged { phase: GamePhase },
+    /// A round ended, and its scores were calculated.
+    RoundEnded { round: usize, score: RoundScore<P> },
+    /// A player joined the game.
+    PlayerJoined { player_id: usize },
+    /// A player quit the game.
+    PlayerQuit { player_id: usize },
+    /// A player who quit mid-round had their hand put on the discard pile (see `QuitHandPolicy::Discard`).
+    HandDiscarded {
+        player_id: usize,
+        cards: Vec<CardData>,
+    },
+    /// A player who quit mid-round had their hand returned to the stock (see `QuitHandPolicy::ReturnToStock`).
+    HandReturnedToStock { player_id: usize, amount: usize },
+    /// The game was paused.
+    Paused,
+    /// The game was resumed.
+    Resumed,
+    /// A player took over the seat of a player who quit.
+    PlayerReplaced {
+        player_id: usize,
+        new_player_id: usize,
+    },
+}
+
+impl<P: VariantPlayerScore> GameEvent<P> {
+    /// Get who can see this event.
+    pub fn visibility(&self) -> EventVisibility {
+        match self {
+            GameEvent::HandDealt { player_id, .. }
+            | GameEvent::CardsDrawn {
+                player_id,
+                source: DrawSource::Deck,
+                ..
+            } => EventVisibility::Private {
+                player_id: *player_id,
+            },
+            _ => EventVisibility::Public,
+        }
+    }
+
+    /// Whether the player with `player_id` can see this event.
+    pub fn is_visible_to(&self, player_id: usize) -> bool {
+        match self.visibility() {
+            EventVisibility::Public => true,
+            EventVisibility::Private { player_id: id } => id == player_id,
+        }
+    }
+}
+
+/// Who can see a `GameEvent`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum EventVisibility {
+    /// Every player (and spectator) can see the event.
+    Public,
+    /// Only the given player can see the event.
+    Private { player_id: usize },
+}
+
+/// Where cards were drawn from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum DrawSource {
+    Deck,
+    DiscardPile,
+}
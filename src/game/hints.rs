@@ -0,0 +1,327 @@
+//! Contains `hints`, which suggests moves for the current player, best first, each with why it's suggested.
+//!
+//! Every hint holds a `GameAction` which can be executed as-is, and a `HintReason` explaining it;
+//! hints can also be displayed as text, like `take the 7♥ from the discard pile: completes a run [5♥ 6♥ 7♥]`:
+//!
+//! ```rust
+//! let mut tracker = CardTracker::new(player_id, game.get_state().deck().config());
+//! tracker.sync(&game.get_state().view_for(Some(player_id)));
+//! if let Some(hint) = hints(&game, &tracker).first() {
+//!     println!("hint: {hint}");
+//!     game.execute_action(hint.action.clone())?;
+//! }
+//! ```
+//!
+//! The hints are fairly simple-minded (they're the moves the greedy bot considers),
+//! so they're meant for teaching the rules rather than strategy.
+
+use crate::{
+    cards::{
+        card::{Card, CardData},
+        meld::{Meld, MeldKind, Meldable, Set},
+        notation::format_cards,
+        suit_rank::Suit,
+    },
+    game::{
+        action::{
+            DiscardAction, DrawDeckAction, DrawDiscardPileAction, FormMeldAction, GameAction,
+            LayOffAction,
+        },
+        danger::discard_dangers,
+        r#trait::Game,
+        state::GamePhase,
+        tracker::CardTracker,
+    },
+    player::Player,
+};
+use std::{
+    collections::HashMap,
+    fmt::{self, Display},
+};
+
+/// Discards with at least this danger are pointed out in their hint's text.
+const RISKY_DANGER: f64 = 0.5;
+
+/// A suggested move.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Hint {
+    /// The action to take.
+    pub action: GameAction,
+    /// Why the action is suggested.
+    pub reason: HintReason,
+}
+
+/// Why a move is suggested.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum HintReason {
+    /// Taking `card` from the discard pile lets the player form the meld `cards`.
+    CompletesMeld {
+        card: CardData,
+        kind: MeldKind,
+        cards: Vec<CardData>,
+    },
+    /// Taking `card` from the discard pile lets the player lay it off onto a meld.
+    ExtendsMeld {
+        card: CardData,
+        player_id: usize,
+        kind: MeldKind,
+    },
+    /// Drawing from the deck is always possible; it's the only draw hint if the discard pile is no use.
+    DrawFromDeck,
+    /// The player can form the meld `cards`.
+    FormsMeld {
+        kind: MeldKind,
+        cards: Vec<CardData>,
+    },
+    /// The player can lay `card` off onto a meld.
+    LaysOff {
+        card: CardData,
+        player_id: usize,
+        kind: MeldKind,
+    },
+    /// Discarding `card` gets rid of `points` of deadwood; `danger` is how likely it is to help the next player
+    /// (see `DiscardDanger::score`).
+    Deadwood {
+        card: CardData,
+        points: u8,
+        danger: f64,
+    },
+}
+
+/// Suggest moves for the current player, best first.
+///
+/// `tracker` should be the current player's, and up to date (see `CardTracker::sync`); what it knows about
+/// opponents' hands is used to steer discards away from cards the next player wants.
+///
+/// Returns no hints if it isn't `tracker`'s viewer's turn, or the game is paused or not in a round.
+/// Otherwise, in the draw phase there's a hint for drawing from the deck, preceded by one for taking from the
+/// discard pile if its top card is useful. In the play phase there's a hint for every meld (longest first) and layoff
+/// the player can make, followed by one for discarding each card (from most to least worth discarding).
+pub fn hints<G: Game>(game: &G, tracker: &CardTracker) -> Vec<Hint> {
+    let state = game.get_state();
+    let Ok(player) = state.get_current_player() else {
+        return Vec::new();
+    };
+    if player.id() != tracker.viewer() || state.is_paused() {
+        return Vec::new();
+    }
+    match state.phase() {
+        GamePhase::Draw => draw_hints(player, state.players(), state.deck().discard_pile()),
+        GamePhase::Play => play_hints(game, player, tracker),
+        _ => Vec::new(),
+    }
+}
+
+fn draw_hints(player: &Player, players: &[Player], discard_pile: &[Card]) -> Vec<Hint> {
+    let mut hints = Vec::new();
+    if let Some(top) = discard_pile.last() {
+        let mut hand = player.cards().clone();
+        hand.push(top.clone());
+        let top_index = hand.len() - 1;
+        let meld = find_melds(&hand)
+            .into_iter()
+            .find(|meld| meld.contains(&top_index));
+        let reason = match meld {
+            Some(meld) => Some(HintReason::CompletesMeld {
+                card: top.data(),
+                kind: meld_kind(&hand, &meld),
+                cards: meld.iter().map(|&i| hand[i].data()).collect(),
+            }),
+            None => find_layoffs(&hand, players)
+                .into_iter()
+                .find(|layoff| layoff.card_index == top_index)
+                .map(|layoff| {
+                    let target = &players[layoff.target_player_index];
+                    HintReason::ExtendsMeld {
+                        card: top.data(),
+                        player_id: target.id(),
+                        kind: target.melds()[layoff.target_meld_index].kind(),
+                    }
+                }),
+        };
+        if let Some(reason) = reason {
+            hints.push(Hint {
+                action: GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(1) }),
+                reason,
+            });
+        }
+    }
+    hints.push(Hint {
+        action: GameAction::DrawDeck(DrawDeckAction {}),
+        reason: HintReason::DrawFromDeck,
+    });
+    hints
+}
+
+fn play_hints<G: Game>(game: &G, player: &Player, tracker: &CardTracker) -> Vec<Hint> {
+    let players = game.get_state().players();
+    let hand = player.cards();
+    let mut hints = Vec::new();
+
+    for card_indices in find_melds(hand) {
+        hints.push(Hint {
+            reason: HintReason::FormsMeld {
+                kind: meld_kind(hand, &card_indices),
+                cards: card_indices.iter().map(|&i| hand[i].data()).collect(),
+            },
+            action: GameAction::FormMeld(FormMeldAction { card_indices }),
+        });
+    }
+
+    for layoff in find_layoffs(hand, players) {
+        let target = &players[layoff.target_player_index];
+        hints.push(Hint {
+            reason: HintReason::LaysOff {
+                card: hand[layoff.card_index].data(),
+                player_id: target.id(),
+                kind: target.melds()[layoff.target_meld_index].kind(),
+            },
+            action: GameAction::LayOff(layoff),
+        });
+    }
+
+    let mut discards: Vec<_> = discard_dangers(game, tracker)
+        .into_iter()
+        .map(|danger| {
+            let points = hand[danger.card_index].score_value();
+            (danger.card_index, danger.card, points, danger.score)
+        })
+        .collect();
+    discards.sort_by(
+        |(a_index, _, a_points, a_danger), (b_index, _, b_points, b_danger)| {
+            discard_value(*b_points, *b_danger)
+                .total_cmp(&discard_value(*a_points, *a_danger))
+                .then(a_index.cmp(b_index))
+        },
+    );
+    for (card_index, card, points, danger) in discards {
+        hints.push(Hint {
+            action: GameAction::Discard(DiscardAction {
+                card_index,
+                declare_going_out: None,
+            }),
+            reason: HintReason::Deadwood {
+                card,
+                points,
+                danger,
+            },
+        });
+    }
+    hints
+}
+
+/// How worthwhile discarding a card is; high scoring cards are worth getting rid of, unless they're risky.
+pub(crate) fn discard_value(points: u8, danger: f64) -> f64 {
+    f64::from(points) * (1.0 - danger)
+}
+
+/// Find every layoff of a card in `hand` onto any player's meld, in order of player, meld then card.
+pub(crate) fn find_layoffs(hand: &[Card], players: &[Player]) -> Vec<LayOffAction> {
+    let mut layoffs = Vec::new();
+    for (target_player_index, player) in players.iter().enumerate() {
+        for (target_meld_index, meld) in player.melds().iter().enumerate() {
+            for card_index in 0..hand.len() {
+                let mut meld = meld.clone();
+                if meld.layoff_card(&mut hand.to_vec(), card_index).is_ok() {
+                    layoffs.push(LayOffAction {
+                        card_index,
+                        target_player_index,
+                        target_meld_index,
+                    });
+                }
+            }
+        }
+    }
+    layoffs
+}
+
+/// Find the melds (ignoring wildcards) that can be formed from `hand`, as the indices of their cards.
+///
+/// These are every set of a rank, and every longest run of a suit; they're sorted longest first,
+/// then by their indices.
+pub(crate) fn find_melds(hand: &[Card]) -> Vec<Vec<usize>> {
+    let mut by_rank: HashMap<_, Vec<usize>> = HashMap::new();
+    let mut by_suit: HashMap<Suit, Vec<usize>> = HashMap::new();
+    for (i, card) in hand.iter().enumerate().filter(|(_, c)| !c.is_wildcard()) {
+        by_rank.entry(card.rank).or_default().push(i);
+        by_suit.entry(card.suit).or_default().push(i);
+    }
+
+    let sets = by_rank.into_values();
+    let runs = by_suit.into_values().flat_map(|mut indices| {
+        indices.sort_by_key(|&i| hand[i].value());
+        longest_runs(hand, indices)
+    });
+    let mut melds: Vec<_> = sets
+        .chain(runs)
+        .filter(|indices| Meld::valid(hand, indices).is_ok())
+        .collect();
+    melds.sort_by(|a, b| b.len().cmp(&a.len()).then(a.cmp(b)));
+    melds.dedup();
+    melds
+}
+
+/// Split the indices of same-suited cards (sorted by value) into their consecutive stretches.
+fn longest_runs(hand: &[Card], sorted_indices: Vec<usize>) -> Vec<Vec<usize>> {
+    let mut runs: Vec<Vec<usize>> = Vec::new();
+    for i in sorted_indices {
+        match runs.last_mut() {
+            Some(run) if hand[run[run.len() - 1]].same_suit_consecutive_rank(&hand[i]) => {
+                run.push(i)
+            }
+            _ => runs.push(vec![i]),
+        }
+    }
+    runs
+}
+
+/// Get the kind of the valid meld formed by the cards in `hand` indexed by `indices`.
+fn meld_kind(hand: &[Card], indices: &[usize]) -> MeldKind {
+    match Set::valid(hand, indices) {
+        Ok(()) => MeldKind::Set,
+        Err(_) => MeldKind::Run,
+    }
+}
+
+/// Displays the hint as a short sentence, like `lay off Q♠ onto player 2's set`.
+impl Display for Hint {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            HintReason::CompletesMeld { card, kind, cards } => write!(
+                f,
+                "take the {card} from the discard pile: completes a {kind} [{}]",
+                format_cards(cards)
+            ),
+            HintReason::ExtendsMeld {
+                card,
+                player_id,
+                kind,
+            } => write!(
+                f,
+                "take the {card} from the discard pile: lays off onto player {player_id}'s {kind}"
+            ),
+            HintReason::DrawFromDeck => write!(f, "draw from the deck"),
+            HintReason::FormsMeld { kind, cards } => {
+                write!(f, "form meld [{}]: a {kind}", format_cards(cards))
+            }
+            HintReason::LaysOff {
+                card,
+                player_id,
+                kind,
+            } => write!(f, "lay off {card} onto player {player_id}'s {kind}"),
+            HintReason::Deadwood {
+                card,
+                points,
+                danger,
+            } => {
+                write!(f, "discard {card}: {points} points of deadwood")?;
+                if *danger >= RISKY_DANGER {
+                    write!(f, ", though it may help the next player")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
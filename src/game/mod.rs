@@ -1,9 +1,14 @@
 //! Contains most of the actual game-related code.
 
 pub mod action;
+pub mod danger;
 pub mod error;
+pub mod event;
+pub mod hints;
 pub mod rules;
 pub mod score;
 pub mod state;
+pub mod tracker;
 pub mod r#trait;
 pub mod variants;
+pub mod view;
@@ -6,7 +6,7 @@ use super::action::*;
 use crate::game::{
     error::{ActionError, GameError},
     score::{RoundScore, VariantPlayerScore},
-    state::{GameState, VariantState},
+    state::{GamePhase, GameState, VariantState},
 };
 
 /// Represents the "rule engine" of a Rummy game, handling action execution and score calculation.
@@ -20,20 +20,41 @@ where
     type VariantScore: VariantPlayerScore;
 
     /// Executes an action, returning an `()` or `ActionError`.
+    ///
+    /// If the action ends the round, the round's score is recorded.
+    ///
+    /// In debug builds, this also returns an `InternalError::InvariantError` if the action left the state
+    /// inconsistent (see `GameState::check_invariants`). States which were already inconsistent
+    /// (for eg, ones edited before being deserialized) aren't checked, so they can still be played.
     fn execute_action(
         &self,
         state: &mut GameState<Self::VariantScore, Self>,
         action: GameAction,
     ) -> Result<(), ActionError> {
         state.validate_action(&action)?;
-        match action {
+        #[cfg(debug_assertions)]
+        let was_consistent = state.check_invariants().is_ok();
+        let result = match action {
             GameAction::DrawDeck(action) => self.handle_draw_deck(state, action),
             GameAction::DrawDiscardPile(action) => self.handle_draw_discard_pile(state, action),
             GameAction::LayOff(action) => self.handle_lay_off(state, action),
             GameAction::FormMeld(action) => self.handle_form_meld(state, action),
             GameAction::FormMelds(action) => self.handle_form_melds(state, action),
             GameAction::Discard(action) => self.handle_discard(state, action),
+        };
+        // record the round's score as soon as it ends; if it can't be calculated, `next_round` returns the error
+        if result.is_ok() && state.phase == GamePhase::RoundEnd {
+            if let Ok(score) = self.calculate_round_score(state) {
+                state.round_scores.insert(state.current_round, score);
+            }
         }
+        #[cfg(debug_assertions)]
+        if was_consistent {
+            state
+                .check_invariants()
+                .map_err(super::error::InternalError::InvariantError)?;
+        }
+        result
     }
 
     /// Handle drawing from the deck.
@@ -1,8 +1,14 @@
 //! Contains the `RoundScore`, which just contains a mapping of player IDs to their scores,
 //! as well as the winning player's ID.
+//!
+//! Also contains the `Points` trait, for variant scores which can be compared as numbers (eg, for statistics).
 
+use crate::game::{r#trait::Game, rules::GameRules};
 use std::collections::HashMap;
 
+/// The score type of a `Game`.
+pub(crate) type ScoreOf<G> = <<G as Game>::Rules as GameRules>::VariantScore;
+
 /// Score information for a completed round.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -20,3 +26,9 @@ pub trait VariantPlayerScore: Sized + Clone + PartialEq + Eq {}
 #[cfg(not(feature = "serde"))]
 /// Represents a player's score in a Rummy variant.
 pub trait VariantPlayerScore: Sized + Clone + PartialEq + Eq {}
+
+/// A player's score which can be counted in points, where fewer points is better.
+pub trait Points {
+    /// Get the number of points.
+    fn points(&self) -> u32;
+}
@@ -1,15 +1,19 @@
 //! Contains the `GameState`, which is essentially the entire state of the game.
 
 use crate::{
-    cards::deck::{Deck, DeckConfig},
+    cards::{
+        deck::{Deck, DeckConfig},
+        meld::{Meld, Meldable, Run, Set},
+    },
     game::{
         action::GameAction,
-        error::{ActionError, FailedActionError, GameError, InternalError},
+        error::{ActionError, FailedActionError, GameError, InternalError, InvariantError},
         rules::GameRules,
         score::{RoundScore, VariantPlayerScore},
     },
     player::Player,
 };
+use rand::Rng;
 use std::collections::HashMap;
 use std::fmt::Debug;
 
@@ -27,6 +31,7 @@ pub struct GameState<P: VariantPlayerScore, R: GameRules<VariantScore = P>> {
     pub(crate) current_player: usize,
     pub(crate) current_round: usize,
     pub(crate) round_scores: HashMap<usize, RoundScore<P>>,
+    pub(crate) paused: bool,
     pub(crate) variant_state: R::VariantState,
 }
 
@@ -35,9 +40,12 @@ where
     R::VariantState: VariantState<P, R>,
 {
     /// Initialize the game state.
+    ///
+    /// If `deck_config` has no `shuffle_seed`, a random one is chosen,
+    /// so that every round of the game can be reproduced from it.
     pub fn initialize(
         player_ids: Vec<usize>,
-        deck_config: DeckConfig,
+        mut deck_config: DeckConfig,
         variant_state: R::VariantState,
     ) -> Self {
         let players = player_ids
@@ -50,6 +58,9 @@ where
                 joined_in_round: 0,
             })
             .collect();
+        if deck_config.shuffle_seed.is_none() {
+            deck_config.shuffle_seed = Some(rand::thread_rng().gen_range(1..=u64::MAX));
+        }
         let deck = Deck::new(deck_config);
         Self {
             phase: GamePhase::RoundEnd,
@@ -58,12 +69,16 @@ where
             current_round: 0,
             current_player: 0,
             round_scores: HashMap::new(),
+            paused: false,
             variant_state,
         }
     }
 
     /// Validate if the action is valid in the current gamestate.
     pub fn validate_action(&self, action: &GameAction) -> Result<(), ActionError> {
+        if self.paused {
+            return Err(ActionError::FailedAction(FailedActionError::GamePaused));
+        }
         match (self.phase, action) {
             (GamePhase::Draw, GameAction::DrawDeck(_)) => (),
             (GamePhase::Draw, GameAction::DrawDiscardPile(_)) => (),
@@ -84,7 +99,7 @@ where
     /// Sets up a new round by:
     /// - Incrementing `current_round`
     /// - Setting players who joined in the last round as active
-    /// - Resetting the deck and dealing new hands
+    /// - Resetting the deck with the new round's seed and dealing new hands
     /// - Setting the current player as `starting_player_index`
     ///
     /// Returns an `Err` if the game phase isn't `RoundEnded`.
@@ -97,7 +112,7 @@ where
             return Err(GameError::WrongGamePhase);
         }
 
-        self.deck.reset();
+        self.deck.reset_for_round(self.current_round + 1);
 
         for player in &mut self.players {
             if !player.active && player.joined_in_round == self.current_round {
@@ -107,10 +122,17 @@ where
             player.cards = self
                 .deck
                 .draw(cards_to_deal)
-                .map_err(|err| InternalError::NoCardsInDeckOrDiscardPile)?;
+                .map_err(|_| InternalError::NoCardsInDeckOrDiscardPile)?;
         }
 
         self.current_player = starting_player_index;
+        if !self
+            .players
+            .get(starting_player_index)
+            .is_some_and(|p| p.active)
+        {
+            self.to_next_player();
+        }
         self.phase = GamePhase::Draw;
         self.current_round += 1;
 
@@ -140,12 +162,115 @@ where
     }
 
     /// Increment `current_player` to the next active player.
+    ///
+    /// If no other player is active, `current_player` is left unchanged.
     pub fn to_next_player(&mut self) {
-        let mut next_player = (self.current_player + 1) % self.players.len();
-        while !self.players[next_player].active {
-            next_player = (self.current_player + 1) % self.players.len();
+        let player_count = self.players.len();
+        if let Some(next_player) = (1..=player_count)
+            .map(|offset| (self.current_player + offset) % player_count)
+            .find(|&index| self.players[index].active)
+        {
+            self.current_player = next_player;
+        }
+    }
+
+    /// Mark the player with ID `player_id` as inactive.
+    ///
+    /// If a round is in progress, their hand is handled according to `hand_policy` (their melds stay in play),
+    /// and if it was their turn, the turn passes to the next active player.
+    ///
+    /// Returns an `Err` if such player doesn't exist.
+    pub fn quit_player(
+        &mut self,
+        player_id: usize,
+        hand_policy: QuitHandPolicy,
+    ) -> Result<(), GameError> {
+        let index = self
+            .players
+            .iter()
+            .position(|p| p.id == player_id)
+            .ok_or(GameError::PlayerDoesntExist)?;
+        let player = &mut self.players[index];
+        player.active = false;
+
+        if !matches!(self.phase, GamePhase::Draw | GamePhase::Play) {
+            return Ok(());
         }
-        self.current_player = next_player;
+        match hand_policy {
+            QuitHandPolicy::Keep => (),
+            QuitHandPolicy::ReturnToStock => self.deck.return_to_stock(&mut player.cards),
+            QuitHandPolicy::Discard => self.deck.add_multiple_to_discard_pile(&mut player.cards),
+        }
+        if index == self.current_player {
+            self.to_next_player();
+            self.phase = GamePhase::Draw;
+        }
+        Ok(())
+    }
+
+    /// Pause the game, so that no actions can be taken until it's resumed.
+    ///
+    /// Returns an `Err` if the game is already paused or has ended.
+    pub fn pause(&mut self) -> Result<(), GameError> {
+        if self.phase == GamePhase::GameEnd {
+            return Err(GameError::WrongGamePhase);
+        }
+        if self.paused {
+            return Err(GameError::GamePaused);
+        }
+        self.paused = true;
+        Ok(())
+    }
+
+    /// Resume the paused game.
+    ///
+    /// Returns an `Err` if the game isn't paused.
+    pub fn resume(&mut self) -> Result<(), GameError> {
+        if !self.paused {
+            return Err(GameError::NotPaused);
+        }
+        self.paused = false;
+        Ok(())
+    }
+
+    /// Let the player with ID `new_player_id` take over the seat of the inactive player with ID `player_id`,
+    /// along with their hand and melds. `new_player_id` can be the same as `player_id` (for eg, when a bot
+    /// plays on for a player who left).
+    ///
+    /// If a round is in progress and the seat has no hand left (for eg, because it was discarded when they quit),
+    /// the new player only becomes active from the next round.
+    ///
+    /// Returns an `Err` if the game ended, `player_id` doesn't exist or is still active,
+    /// or another player already has the ID `new_player_id`.
+    pub fn replace_player(
+        &mut self,
+        player_id: usize,
+        new_player_id: usize,
+    ) -> Result<(), GameError> {
+        if self.phase == GamePhase::GameEnd {
+            return Err(GameError::WrongGamePhase);
+        }
+        if new_player_id != player_id && self.players.iter().any(|p| p.id == new_player_id) {
+            return Err(GameError::AddedPlayerAlreadyExists);
+        }
+        let in_round = matches!(self.phase, GamePhase::Draw | GamePhase::Play);
+        let current_round = self.current_round;
+        let player = self
+            .players
+            .iter_mut()
+            .find(|p| p.id == player_id)
+            .ok_or(GameError::PlayerDoesntExist)?;
+        if player.active {
+            return Err(GameError::SeatNotVacant);
+        }
+
+        player.id = new_player_id;
+        if in_round && player.cards.is_empty() {
+            player.joined_in_round = current_round;
+        } else {
+            player.active = true;
+        }
+        Ok(())
     }
 
     /// Get the game's phase.
@@ -173,6 +298,11 @@ where
         self.current_round
     }
 
+    /// Whether the game is paused.
+    pub fn is_paused(&self) -> bool {
+        self.paused
+    }
+
     /// Get the round scores.
     pub fn round_scores(&self) -> &HashMap<usize, RoundScore<P>> {
         &self.round_scores
@@ -182,6 +312,99 @@ where
     pub fn variant_state(&self) -> &R::VariantState {
         &self.variant_state
     }
+
+    /// Check that the state is consistent, returning the first inconsistency found as an `Err`:
+    /// - Every card of the deck is in exactly one hand, meld, the stock or the discard pile
+    /// - Every meld is still valid
+    /// - `current_player` points at a player, who is active while a round is being played
+    /// - The phase is consistent with the hands; nobody has cards before the first round,
+    ///   the current player has a card to discard in the play phase, and has gone out if the round has ended
+    ///
+    /// This is run after every action in debug builds, but is worth running on games loaded from elsewhere.
+    pub fn check_invariants(&self) -> Result<(), InvariantError> {
+        self.check_cards_conserved()?;
+
+        for player in &self.players {
+            for (meld_index, meld) in player.melds.iter().enumerate() {
+                let indices: Vec<_> = (0..meld.cards().len()).collect();
+                let valid = match meld {
+                    Meld::Set(set) => Set::valid(set.cards(), &indices),
+                    Meld::Run(run) => Run::valid(run.cards(), &indices),
+                };
+                valid.map_err(|error| InvariantError::InvalidMeld {
+                    player_id: player.id,
+                    meld_index,
+                    error,
+                })?;
+            }
+        }
+
+        let current_player = self.players.get(self.current_player).ok_or(
+            InvariantError::CurrentPlayerOutOfRange {
+                current: self.current_player,
+                player_count: self.players.len(),
+            },
+        )?;
+        let in_round = matches!(self.phase, GamePhase::Draw | GamePhase::Play);
+        if in_round && !current_player.active {
+            return Err(InvariantError::InactiveCurrentPlayer {
+                player_id: current_player.id,
+            });
+        }
+
+        if self.current_round == 0 {
+            if let Some(player) = self
+                .players
+                .iter()
+                .find(|p| !p.cards.is_empty() || !p.melds.is_empty())
+            {
+                return Err(InvariantError::CardsBeforeFirstRound {
+                    player_id: player.id,
+                });
+            }
+        } else if self.phase == GamePhase::Play && current_player.cards.is_empty() {
+            return Err(InvariantError::EmptyHandInPlay {
+                player_id: current_player.id,
+            });
+        } else if self.phase == GamePhase::RoundEnd && !current_player.cards.is_empty() {
+            return Err(InvariantError::RoundEndWithoutWinner);
+        }
+        Ok(())
+    }
+
+    /// Check that the cards in play are exactly the deck's cards.
+    fn check_cards_conserved(&self) -> Result<(), InvariantError> {
+        let mut deck_cards = Vec::new();
+        Deck::generate_cards(&mut deck_cards, &self.deck.config);
+        let mut counts: HashMap<_, (usize, usize)> = HashMap::new();
+        for card in &deck_cards {
+            counts.entry(card.data()).or_default().0 += 1;
+        }
+
+        let in_play = self
+            .players
+            .iter()
+            .flat_map(|p| p.cards.iter().chain(p.melds.iter().flat_map(|m| m.cards())))
+            .chain(&self.deck.stock)
+            .chain(&self.deck.discard_pile);
+        for card in in_play {
+            counts.entry(card.data()).or_default().1 += 1;
+        }
+
+        let mut counts: Vec<_> = counts.into_iter().collect();
+        counts.sort();
+        match counts
+            .into_iter()
+            .find(|(_, (expected, found))| expected != found)
+        {
+            Some((card, (expected, found))) => Err(InvariantError::CardsNotConserved {
+                card,
+                expected,
+                found,
+            }),
+            None => Ok(()),
+        }
+    }
 }
 
 /// Represents the unique state held by a Rummy variant.
@@ -202,11 +425,24 @@ pub trait VariantState<P: VariantPlayerScore, R: GameRules<VariantState = Self,
     /// ## Note
     /// This should not be used for validating specific actions (ie, whether forming a meld is valid).
     /// That should be done in the `GameRules` action handler instead.
-    fn validate_action(state: &GameState<P, R>, action: &GameAction) -> Result<(), ActionError> {
+    fn validate_action(_state: &GameState<P, R>, _action: &GameAction) -> Result<(), ActionError> {
         Ok(())
     }
 }
 
+/// What happens to a player's hand when they quit mid-round.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum QuitHandPolicy {
+    /// The hand stays with their seat (out of play), so that another player can take over the seat with it.
+    #[default]
+    Keep,
+    /// The hand is returned to the bottom of the stock.
+    ReturnToStock,
+    /// The hand is added to the discard pile.
+    Discard,
+}
+
 /// The phases of a Rummy game.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
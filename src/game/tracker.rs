@@ -0,0 +1,269 @@
+//! Contains `CardTracker`, which remembers what a single player has seen of where every card is.
+//!
+//! A tracker only ever uses what its player could see at the table: the `GameEvent`s visible to them
+//! (eg, from an `Observed` game), and their `PlayerView`. So it's safe to use for bots and hints,
+//! which shouldn't know what's in an opponent's hand unless they saw it picked up:
+//!
+//! ```rust
+//! let tracker = Arc::new(Mutex::new(CardTracker::new(0, game.get_state().deck().config())));
+//! let mut game = Observed::new(game);
+//! let observer = tracker.clone();
+//! game.subscribe(move |event| observer.lock().unwrap().observe(event));
+//! // ...play...
+//! let mut tracker = tracker.lock().unwrap();
+//! tracker.sync(&game.get_state().view_for(Some(0)));
+//! println!("player 1 has {:?}, and these cards are still out there: {:?}", tracker.known_hand(1), tracker.unseen());
+//! ```
+
+use crate::{
+    cards::{
+        card::CardData,
+        deck::{Deck, DeckConfig},
+    },
+    game::{
+        event::{DrawSource, GameEvent},
+        score::VariantPlayerScore,
+        view::PlayerView,
+    },
+};
+use std::collections::HashMap;
+
+/// What a single player knows about where every card is, in the current round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CardTracker {
+    viewer: usize,
+    deck: Vec<CardData>,
+    hand: Vec<CardData>,
+    known_hands: HashMap<usize, Vec<CardData>>,
+    discard_pile: Vec<CardData>,
+    discarded: Vec<CardData>,
+    melded: Vec<CardData>,
+}
+
+impl CardTracker {
+    /// Create a tracker for the player with ID `viewer`, in a game whose deck is configured by `deck_config`.
+    pub fn new(viewer: usize, deck_config: &DeckConfig) -> Self {
+        let deck = Deck::new(deck_config.clone())
+            .stock()
+            .iter()
+            .map(|card| card.data())
+            .collect();
+        Self {
+            viewer,
+            deck,
+            hand: Vec::new(),
+            known_hands: HashMap::new(),
+            discard_pile: Vec::new(),
+            discarded: Vec::new(),
+            melded: Vec::new(),
+        }
+    }
+
+    /// Update the tracker with an event.
+    ///
+    /// Events which aren't visible to the viewer are ignored, so every event of a game can be passed in.
+    pub fn observe<P: VariantPlayerScore>(&mut self, event: &GameEvent<P>) {
+        if !event.is_visible_to(self.viewer) {
+            return;
+        }
+        match event {
+            GameEvent::RoundStarted { .. } => {
+                self.hand.clear();
+                self.known_hands.clear();
+                self.discard_pile.clear();
+                self.discarded.clear();
+                self.melded.clear();
+            }
+            GameEvent::HandDealt { cards, .. } => self.hand = cards.clone(),
+            GameEvent::CardsDrawn {
+                player_id,
+                source,
+                cards,
+            } => {
+                if *source == DrawSource::DiscardPile {
+                    for card in cards {
+                        take(&mut self.discard_pile, card);
+                    }
+                }
+                if *player_id == self.viewer {
+                    self.hand.extend(cards);
+                } else if *source == DrawSource::DiscardPile {
+                    self.known_hands
+                        .entry(*player_id)
+                        .or_default()
+                        .extend(cards);
+                }
+            }
+            GameEvent::MeldFormed {
+                player_id, cards, ..
+            } => {
+                for card in cards {
+                    self.play(*player_id, card);
+                }
+                self.melded.extend(cards);
+            }
+            GameEvent::CardLaidOff {
+                player_id, card, ..
+            } => {
+                self.play(*player_id, card);
+                self.melded.push(*card);
+            }
+            GameEvent::WildcardSwapped {
+                player_id,
+                card,
+                wildcard,
+                ..
+            } => {
+                self.play(*player_id, card);
+                self.melded.push(*card);
+                take(&mut self.melded, wildcard);
+                match *player_id == self.viewer {
+                    true => self.hand.push(*wildcard),
+                    false => self
+                        .known_hands
+                        .entry(*player_id)
+                        .or_default()
+                        .push(*wildcard),
+                }
+            }
+            GameEvent::CardDiscarded { player_id, card } => {
+                self.play(*player_id, card);
+                self.discard_pile.push(*card);
+                self.discarded.push(*card);
+            }
+            GameEvent::PlayerQuit { player_id } => {
+                self.known_hands.remove(player_id);
+            }
+            GameEvent::HandDiscarded { cards, .. } => {
+                self.discard_pile.extend(cards);
+                self.discarded.extend(cards);
+            }
+            GameEvent::PlayerReplaced {
+                player_id,
+                new_player_id,
+            } => {
+                if let Some(known) = self.known_hands.remove(player_id) {
+                    self.known_hands.insert(*new_player_id, known);
+                }
+            }
+            GameEvent::HiddenCardsDrawn { .. }
+            | GameEvent::TurnChanged { .. }
+            | GameEvent::PhaseChanged { .. }
+            | GameEvent::RoundEnded { .. }
+            | GameEvent::PlayerJoined { .. }
+            | GameEvent::HandReturnedToStock { .. }
+            | GameEvent::Paused
+            | GameEvent::Resumed => {}
+        }
+    }
+
+    /// Bring the tracker in line with what the viewer can currently see.
+    ///
+    /// Events don't cover everything that can move cards around (eg, the stock being replenished from the
+    /// discard pile), so this should be called every so often, such as at the start of the viewer's turn.
+    pub fn sync<P: VariantPlayerScore>(&mut self, view: &PlayerView<P>) {
+        if view.viewer == Some(self.viewer) {
+            self.hand = view.hand.clone();
+        }
+        self.discard_pile = view.discard_pile.clone();
+        self.melded = view
+            .players
+            .iter()
+            .flat_map(|player| player.melds.iter().flatten().copied())
+            .collect();
+        self.known_hands.retain(|id, known| {
+            let Some(player) = view.players.iter().find(|p| p.id == *id && p.active) else {
+                return false;
+            };
+            known.truncate(player.card_count);
+            true
+        });
+    }
+
+    /// Get the ID of the player whose perspective this is.
+    pub fn viewer(&self) -> usize {
+        self.viewer
+    }
+
+    /// Get every card in the deck.
+    pub fn deck(&self) -> &[CardData] {
+        &self.deck
+    }
+
+    /// Get the viewer's hand.
+    pub fn hand(&self) -> &[CardData] {
+        &self.hand
+    }
+
+    /// Get the cards known to be in a player's hand, because they were seen picking them up and haven't played them.
+    pub fn known_hand(&self, player_id: usize) -> &[CardData] {
+        self.known_hands
+            .get(&player_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Get the cards known to be in each player's hand (except the viewer's).
+    pub fn known_hands(&self) -> &HashMap<usize, Vec<CardData>> {
+        &self.known_hands
+    }
+
+    /// Get the discard pile, from bottom to top.
+    pub fn discard_pile(&self) -> &[CardData] {
+        &self.discard_pile
+    }
+
+    /// Get every card seen discarded this round, in order, including those since picked up.
+    pub fn discarded(&self) -> &[CardData] {
+        &self.discarded
+    }
+
+    /// Get every card in a meld on the table.
+    pub fn melded(&self) -> &[CardData] {
+        &self.melded
+    }
+
+    /// Get every card whose whereabouts the viewer doesn't know; each is either in the stock,
+    /// or in an opponent's hand without the viewer having seen it go there.
+    pub fn unseen(&self) -> Vec<CardData> {
+        let mut unseen = self.deck.clone();
+        let seen = self
+            .hand
+            .iter()
+            .chain(self.known_hands.values().flatten())
+            .chain(&self.discard_pile)
+            .chain(&self.melded);
+        for card in seen {
+            take(&mut unseen, card);
+        }
+        unseen
+    }
+
+    /// Get the number of unseen copies of a card.
+    pub fn unseen_count(&self, card: &CardData) -> usize {
+        self.unseen().iter().filter(|c| *c == card).count()
+    }
+
+    /// Remove a card that a player played from wherever the viewer knows it to be.
+    fn play(&mut self, player_id: usize, card: &CardData) {
+        match player_id == self.viewer {
+            true => take(&mut self.hand, card),
+            false => self
+                .known_hands
+                .get_mut(&player_id)
+                .is_some_and(|known| take(known, card)),
+        };
+    }
+}
+
+/// Remove one copy of `card` from `cards`, returning whether there was one.
+fn take(cards: &mut Vec<CardData>, card: &CardData) -> bool {
+    match cards.iter().position(|c| c == card) {
+        Some(index) => {
+            cards.remove(index);
+            true
+        }
+        None => false,
+    }
+}
@@ -8,7 +8,7 @@ use super::action::*;
 use crate::{
     cards::card::CardData,
     game::{
-        error::{ActionError, GameError},
+        error::{ActionError, FailedActionError, GameError},
         rules::GameRules,
         state::GameState,
     },
@@ -24,6 +24,42 @@ pub trait Game {
     /// Returns an `Err` if the action couldn't be executed for some reason.
     fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError>;
 
+    /// Attempt to execute the `GameAction` for the player with ID `player_id`.
+    ///
+    /// If it's that player's turn, this is the same as `execute_action`;
+    /// otherwise, the action is handled by `execute_out_of_turn_action`.
+    fn execute_action_as(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        if self.get_state().is_paused() {
+            return Err(ActionError::FailedAction(FailedActionError::GamePaused));
+        }
+        let current_player_id = self.get_state().get_current_player()?.id();
+        if player_id == current_player_id {
+            self.execute_action(action)
+        } else {
+            self.execute_out_of_turn_action(player_id, action)
+        }
+    }
+
+    /// Attempt to execute the `GameAction` for the player with ID `player_id`, whose turn it isn't.
+    ///
+    /// By default, this returns a `NotYourTurn` error. Variants which allow out-of-turn actions
+    /// (for eg, buying the discard in Contract Rummy, or calling "Rummy!" on a missed layoff) can override this.
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        _action: GameAction,
+    ) -> Result<(), ActionError> {
+        let current_player_id = self.get_state().get_current_player()?.id();
+        Err(ActionError::FailedAction(FailedActionError::NotYourTurn {
+            player_id,
+            current_player_id,
+        }))
+    }
+
     /// Inspect the game's current state.
     fn get_state(
         &self,
@@ -34,6 +70,22 @@ pub trait Game {
     /// Returns an `Err` if such player doesn't exist, or the game already ended.
     fn quit_player(&mut self, player_id: usize) -> Result<(), GameError>;
 
+    /// Let the player with ID `new_player_id` take over the seat (including the hand and melds)
+    /// of the player with ID `player_id`, who has quit.
+    ///
+    /// Returns an `Err` if such player doesn't exist or hasn't quit, the game already ended,
+    /// or another player already has the ID `new_player_id`.
+    ///
+    /// By default, this returns a `NotSupported` error. Variants which allow seats to be taken over
+    /// can override this (for eg, with `GameState::replace_player`).
+    fn replace_player(
+        &mut self,
+        _player_id: usize,
+        _new_player_id: usize,
+    ) -> Result<(), GameError> {
+        Err(GameError::NotSupported)
+    }
+
     /// Add a player using the given `player_id`.
     ///
     /// Returns an `Err` if the maximum number of players has been reached,
@@ -51,9 +103,28 @@ pub trait Game {
         new_arrangement: Vec<CardData>,
     ) -> Result<(), GameError>;
 
+    /// Pause the game (for eg, while a player reconnects), so that no actions can be taken until it's resumed.
+    ///
+    /// Returns an `Err` if the game is already paused or has ended.
+    ///
+    /// By default, this returns a `NotSupported` error. Variants which can be paused
+    /// can override this (for eg, with `GameState::pause`).
+    fn pause(&mut self) -> Result<(), GameError> {
+        Err(GameError::NotSupported)
+    }
+
+    /// Resume the paused game.
+    ///
+    /// Returns an `Err` if the game isn't paused.
+    ///
+    /// By default, this returns a `NotSupported` error, like `pause`.
+    fn resume(&mut self) -> Result<(), GameError> {
+        Err(GameError::NotSupported)
+    }
+
     /// Calculate and store round scores and start the next round.
     ///
-    /// Returns an `Err` if the game phase is not `RoundEnd`,
+    /// Returns an `Err` if the game phase is not `RoundEnd`, the game is paused,
     /// or the setup failed for some reason.
     fn next_round(&mut self) -> Result<(), GameError>;
 }
@@ -1,5 +1,7 @@
 //! Contains the configuration for a [`BasicRummyGame`](super::game::BasicRummyGame).
 
+use crate::game::state::QuitHandPolicy;
+
 /// Holds override configuration values for basic Rummy.
 #[derive(Clone, Debug, PartialEq, Eq)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
@@ -10,6 +12,8 @@ pub struct BasicConfig {
     pub draw_deck_amount: Option<usize>,
     /// Overrides the default number of cards a player draws from the discard pile.
     pub draw_discard_pile_amount: Option<DrawDiscardPileOverride>,
+    /// Overrides what happens to a player's hand when they quit mid-round (by default, it stays with their seat).
+    pub quit_hand_policy: Option<QuitHandPolicy>,
 }
 
 /// The type of discard pile draw behaviour.
@@ -92,13 +92,8 @@ impl Game for BasicRummyGame {
     }
 
     fn quit_player(&mut self, player_id: usize) -> Result<(), GameError> {
-        match self.state.players.iter_mut().find(|p| p.id == player_id) {
-            Some(player) => {
-                player.active = false;
-                Ok(())
-            }
-            None => Err(GameError::PlayerDoesntExist),
-        }?;
+        let hand_policy = self.rules.config().quit_hand_policy.unwrap_or_default();
+        self.state.quit_player(player_id, hand_policy)?;
 
         // End the game if only 1 active player is remaining
         let num_active_players = self
@@ -113,17 +108,15 @@ impl Game for BasicRummyGame {
         Ok(())
     }
 
+    fn replace_player(&mut self, player_id: usize, new_player_id: usize) -> Result<(), GameError> {
+        self.state.replace_player(player_id, new_player_id)
+    }
+
     fn add_player(&mut self, player_id: usize) -> Result<(), GameError> {
         match self.state.players.iter().find(|p| p.id == player_id) {
             Some(_) => Err(GameError::AddedPlayerAlreadyExists),
             None => {
-                let new_player = Player {
-                    id: player_id,
-                    cards: Vec::new(),
-                    melds: Vec::new(),
-                    active: false,
-                    joined_in_round: self.state.current_round,
-                };
+                let new_player = Player::new(player_id, false, self.state.current_round);
                 self.state.players.push(new_player);
                 Ok(())
             }
@@ -162,11 +155,7 @@ impl Game for BasicRummyGame {
                 if count.is_empty() {
                     player.cards = new_arrangement
                         .into_iter()
-                        .map(|c| Card {
-                            rank: c.rank,
-                            suit: c.suit,
-                            deck_config: deck_config.clone(),
-                        })
+                        .map(|c| Card::from_card_data(c, deck_config.clone()))
                         .collect();
                     Ok(())
                 } else {
@@ -177,14 +166,31 @@ impl Game for BasicRummyGame {
         }
     }
 
+    fn pause(&mut self) -> Result<(), GameError> {
+        self.state.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), GameError> {
+        self.state.resume()
+    }
+
     fn next_round(&mut self) -> Result<(), GameError> {
         if self.state.phase != GamePhase::RoundEnd {
             return Err(GameError::WrongGamePhase);
         }
+        if self.state.paused {
+            return Err(GameError::GamePaused);
+        }
 
         self.validate_setup()?;
 
-        if self.state.current_round != 0 {
+        // the score is usually recorded as the round ends, but not in games saved before that was done
+        if self.state.current_round != 0
+            && !self
+                .state
+                .round_scores
+                .contains_key(&self.state.current_round)
+        {
             let round_score = self.rules.calculate_round_score(&self.state)?;
             self.state
                 .round_scores
@@ -30,6 +30,11 @@ impl BasicRules {
         Self { config }
     }
 
+    /// Get the rules' configuration.
+    pub fn config(&self) -> &BasicConfig {
+        &self.config
+    }
+
     /// The number of cards to deal at the start of a round.
     pub(super) fn cards_to_deal(&self, state: &GameState<BasicScore, BasicRules>) -> usize {
         if let Some(count) = self.config.deal_amount {
@@ -52,7 +57,7 @@ impl BasicRules {
     /// The number of cards to draw from the deck.
     pub(super) fn cards_to_draw_from_deck(
         &self,
-        state: &GameState<BasicScore, BasicRules>,
+        _state: &GameState<BasicScore, BasicRules>,
     ) -> usize {
         if let Some(value) = &self.config.draw_deck_amount {
             *value
@@ -92,12 +97,12 @@ impl GameRules for BasicRules {
     fn handle_draw_deck(
         &self,
         state: &mut GameState<BasicScore, BasicRules>,
-        action: DrawDeckAction,
+        _action: DrawDeckAction,
     ) -> Result<(), ActionError> {
         let mut card = state
             .deck
             .draw(self.cards_to_draw_from_deck(state))
-            .map_err(|err| InternalError::NoCardsInDeckOrDiscardPile)?;
+            .map_err(|_| InternalError::NoCardsInDeckOrDiscardPile)?;
         let player = state.get_current_player_mut()?;
         player.cards.append(&mut card);
 
@@ -121,7 +126,7 @@ impl GameRules for BasicRules {
         let mut card = state
             .deck
             .draw_discard_pile(draw_amount)
-            .map_err(|err| FailedActionError::DiscardPileTooSmall)?;
+            .map_err(|_| FailedActionError::DiscardPileTooSmall)?;
         let player = state.get_current_player_mut()?;
         player.cards.append(&mut card);
 
@@ -159,7 +164,7 @@ impl GameRules for BasicRules {
         } else {
             // Different players - use `split_at_mut`, otherwise we get multiple mut references
             let (current_idx, target_idx) = (state.current_player, action.target_player_index);
-            let (min_idx, max_idx) = if current_idx < target_idx {
+            let (_, max_idx) = if current_idx < target_idx {
                 (current_idx, target_idx)
             } else {
                 (target_idx, current_idx)
@@ -226,7 +231,7 @@ impl GameRules for BasicRules {
         action: DiscardAction,
     ) -> Result<(), ActionError> {
         let player = state.get_current_player_mut()?;
-        if action.card_index > player.cards.len() {
+        if action.card_index >= player.cards.len() {
             let err = FailedActionError::InvalidCardIndex;
             return Err(ActionError::FailedAction(err));
         }
@@ -257,11 +262,14 @@ impl GameRules for BasicRules {
             .iter()
             .map(|player| (player.id(), BasicScore::score_player(player)))
             .collect();
-        let winner_id = *player_scores
-            .iter()
-            .find(|(_, score)| score.score() == 0)
+        // the round ends as soon as the current player goes out, so they're the winner
+        // (players who quit can also be left with an empty hand, so the scores can't be relied on)
+        let winner_id = state
+            .players
+            .get(state.current_player)
+            .filter(|player| player.cards.is_empty())
             .ok_or(InternalError::RoundHasNoWinner)?
-            .0;
+            .id();
         let round_score = RoundScore {
             player_scores,
             winner_id,
@@ -1,6 +1,9 @@
 //! Contains the representation for a player's score in basic Rummy.
 
-use crate::{game::score::VariantPlayerScore, player::Player};
+use crate::{
+    game::score::{Points, VariantPlayerScore},
+    player::Player,
+};
 
 /// A single player's score in basic Rummy.
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10,15 +13,18 @@ pub struct BasicScore {
 }
 
 impl BasicScore {
+    /// Create a score directly from its value.
+    pub(crate) fn new(score: u32) -> Self {
+        Self { score }
+    }
+
     /// Score a player.
     pub fn score_player(player: &Player) -> Self {
         let score = player
             .cards
             .iter()
             .fold(0, |score, card| score + card.score_value());
-        Self {
-            score: score.into(),
-        }
+        Self::new(score.into())
     }
 
     /// Get the score.
@@ -28,3 +34,10 @@ impl BasicScore {
 }
 
 impl VariantPlayerScore for BasicScore {}
+
+/// The points are the score value of the cards left in the player's hand.
+impl Points for BasicScore {
+    fn points(&self) -> u32 {
+        self.score
+    }
+}
@@ -0,0 +1,84 @@
+//! Contains `PlayerView`, the part of a game's state that a single player (or spectator) can see.
+//!
+//! Unlike the `GameState`, this doesn't contain other players' hands or the order of the stock,
+//! so it's safe to send to clients.
+
+use crate::{
+    cards::{card::CardData, meld::Meldable},
+    game::{
+        rules::GameRules,
+        score::{RoundScore, VariantPlayerScore},
+        state::{GamePhase, GameState},
+    },
+};
+use std::collections::HashMap;
+
+/// What a single player (or spectator) can see of a game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerView<P: VariantPlayerScore> {
+    /// The ID of the viewing player, or `None` for a spectator.
+    pub viewer: Option<usize>,
+    pub phase: GamePhase,
+    pub paused: bool,
+    pub current_round: usize,
+    /// The ID of the player whose turn it is, if any.
+    pub current_player_id: Option<usize>,
+    /// The viewer's own hand (empty for spectators).
+    pub hand: Vec<CardData>,
+    /// What can be seen of every player.
+    pub players: Vec<PlayerSummary>,
+    pub stock_size: usize,
+    pub discard_pile: Vec<CardData>,
+    pub round_scores: HashMap<usize, RoundScore<P>>,
+}
+
+/// What can be seen of any player.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerSummary {
+    pub id: usize,
+    pub card_count: usize,
+    pub melds: Vec<Vec<CardData>>,
+    pub active: bool,
+}
+
+impl<P: VariantPlayerScore, R: GameRules<VariantScore = P>> GameState<P, R> {
+    /// Get the view of the game for the player with ID `viewer`, or a spectator if `None`.
+    ///
+    /// If no player has the ID `viewer`, the view is the same as a spectator's.
+    pub fn view_for(&self, viewer: Option<usize>) -> PlayerView<P> {
+        let hand = self
+            .players
+            .iter()
+            .find(|p| Some(p.id) == viewer)
+            .map(|p| p.cards.iter().map(|c| c.data()).collect())
+            .unwrap_or_default();
+        let players = self
+            .players
+            .iter()
+            .map(|p| PlayerSummary {
+                id: p.id,
+                card_count: p.cards.len(),
+                melds: p
+                    .melds
+                    .iter()
+                    .map(|m| m.cards().iter().map(|c| c.data()).collect())
+                    .collect(),
+                active: p.active,
+            })
+            .collect();
+        PlayerView {
+            viewer,
+            phase: self.phase,
+            paused: self.paused,
+            current_round: self.current_round,
+            current_player_id: self.players.get(self.current_player).map(|p| p.id),
+            hand,
+            players,
+            stock_size: self.deck.stock.len(),
+            discard_pile: self.deck.discard_pile.iter().map(|c| c.data()).collect(),
+            round_scores: self.round_scores.clone(),
+        }
+    }
+}
@@ -1,8 +1,21 @@
 #![doc = include_str!("../README.md")]
+#![allow(clippy::needless_doctest_main)]
 
+#[cfg(feature = "binary")]
+pub mod binary;
 pub mod cards;
+#[cfg(feature = "fair-shuffle")]
+pub mod fairness;
 pub mod game;
 pub mod player;
+pub mod ratings;
+#[cfg(feature = "server")]
+pub mod server;
+#[cfg(feature = "session")]
+pub mod session;
+pub mod simulation;
+pub mod stats;
+pub mod tournament;
 pub mod wrappers;
 
 #[cfg(feature = "serde")]
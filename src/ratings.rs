@@ -0,0 +1,195 @@
+//! Contains the `RatingStore`, which keeps players' ratings, updated from how they finish their games.
+//!
+//! Ratings use Elo. A finish with more than 2 players is rated as if every pair of them had played a 2-player game,
+//! with the K-factor shared out between each player's opponents. So a 2-player game is rated as plain Elo, and coming
+//! 2nd out of 4 counts as beating 2 players and losing to 1, rather than as a loss:
+//!
+//! ```rust
+//! let mut ratings = RatingStore::new(EloConfig::default());
+//! ratings.record_game(&game)?;
+//! for (player_id, rating) in ratings.leaderboard() {
+//!     println!("{player_id}: {:.0}", rating.value);
+//! }
+//! ```
+//!
+//! With the `serde` feature, the store can be (de)serialized to keep a leaderboard between games.
+
+use crate::{
+    game::{
+        r#trait::Game,
+        score::{Points, RoundScore, ScoreOf, VariantPlayerScore},
+    },
+    tournament::{ScoredRound, TableResult},
+};
+use std::{cmp::Ordering, collections::HashMap};
+use thiserror::Error;
+
+/// How ratings are calculated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EloConfig {
+    /// The rating of a player who hasn't played yet.
+    pub initial_rating: f64,
+    /// The most a rating can change from a single finish.
+    pub k_factor: f64,
+    /// The rating difference at which the higher rated player is expected to win 10 times out of 11.
+    pub scale: f64,
+}
+
+impl Default for EloConfig {
+    fn default() -> Self {
+        Self {
+            initial_rating: 1500.0,
+            k_factor: 32.0,
+            scale: 400.0,
+        }
+    }
+}
+
+/// A player's rating.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rating {
+    /// The rating itself.
+    pub value: f64,
+    /// The number of finishes the rating is based on.
+    pub finishes: usize,
+}
+
+/// Keeps every player's rating.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RatingStore {
+    config: EloConfig,
+    ratings: HashMap<usize, Rating>,
+}
+
+impl RatingStore {
+    /// Create a store with nobody rated yet.
+    pub fn new(config: EloConfig) -> Self {
+        Self {
+            config,
+            ratings: HashMap::new(),
+        }
+    }
+
+    /// Get the store's config.
+    pub fn config(&self) -> &EloConfig {
+        &self.config
+    }
+
+    /// Get a player's rating, which is the initial rating if they haven't played yet.
+    pub fn rating(&self, player_id: usize) -> Rating {
+        self.ratings.get(&player_id).copied().unwrap_or(Rating {
+            value: self.config.initial_rating,
+            finishes: 0,
+        })
+    }
+
+    /// Get every rated player's rating.
+    pub fn ratings(&self) -> &HashMap<usize, Rating> {
+        &self.ratings
+    }
+
+    /// Get every rated player's ID and rating, from highest to lowest rated.
+    pub fn leaderboard(&self) -> Vec<(usize, Rating)> {
+        let mut leaderboard: Vec<_> = self.ratings.iter().map(|(&id, &r)| (id, r)).collect();
+        leaderboard
+            .sort_by(|(a_id, a), (b_id, b)| b.value.total_cmp(&a.value).then(a_id.cmp(b_id)));
+        leaderboard
+    }
+
+    /// Update ratings from a finish, given as the player IDs at each place from first to last,
+    /// where players at the same place tied.
+    ///
+    /// Returns each player's rating change, or an `Err` if there are fewer than 2 players or a player is in it twice.
+    pub fn record_finish(
+        &mut self,
+        finish: &[Vec<usize>],
+    ) -> Result<HashMap<usize, f64>, RatingError> {
+        let places: Vec<(usize, usize)> = finish
+            .iter()
+            .enumerate()
+            .flat_map(|(place, ids)| ids.iter().map(move |&id| (id, place)))
+            .collect();
+        if places.len() < 2 {
+            return Err(RatingError::NotEnoughPlayers(places.len()));
+        }
+        for (i, (id, _)) in places.iter().enumerate() {
+            if places[..i].iter().any(|(other, _)| other == id) {
+                return Err(RatingError::DuplicatePlayer(*id));
+            }
+        }
+
+        let ratings: Vec<_> = places.iter().map(|&(id, _)| self.rating(id)).collect();
+        let k_factor = self.config.k_factor / (places.len() - 1) as f64;
+        let changes: HashMap<_, _> = places
+            .iter()
+            .zip(&ratings)
+            .map(|(&(id, place), rating)| {
+                let change: f64 = places
+                    .iter()
+                    .zip(&ratings)
+                    .filter(|((other_id, _), _)| *other_id != id)
+                    .map(|(&(_, other_place), other_rating)| {
+                        let actual = match place.cmp(&other_place) {
+                            Ordering::Less => 1.0,
+                            Ordering::Equal => 0.5,
+                            Ordering::Greater => 0.0,
+                        };
+                        actual - self.expected_score(rating.value, other_rating.value)
+                    })
+                    .sum();
+                (id, k_factor * change)
+            })
+            .collect();
+
+        for (&id, change) in &changes {
+            let rating = self.rating(id);
+            self.ratings.insert(
+                id,
+                Rating {
+                    value: rating.value + change,
+                    finishes: rating.finishes + 1,
+                },
+            );
+        }
+        Ok(changes)
+    }
+
+    /// Update ratings from a single round, placing players by their points (with the winner ahead of any ties).
+    pub fn record_round<P: VariantPlayerScore + Points>(
+        &mut self,
+        score: &RoundScore<P>,
+    ) -> Result<HashMap<usize, f64>, RatingError> {
+        let result = TableResult {
+            rounds: vec![ScoredRound::from(score)],
+        };
+        self.record_finish(&result.finish())
+    }
+
+    /// Update ratings from a game, placing players by their total points over every completed round.
+    ///
+    /// See `TableResult::placings` for exactly how they're placed.
+    pub fn record_game<G: Game>(&mut self, game: &G) -> Result<HashMap<usize, f64>, RatingError>
+    where
+        ScoreOf<G>: Points,
+    {
+        self.record_finish(&TableResult::from_game(game).finish())
+    }
+
+    /// The expected score (from 0 for a sure loss, to 1 for a sure win) of a player rated `rating`
+    /// against a player rated `other_rating`.
+    fn expected_score(&self, rating: f64, other_rating: f64) -> f64 {
+        1.0 / (1.0 + 10f64.powf((other_rating - rating) / self.config.scale))
+    }
+}
+
+/// Errors from updating ratings.
+#[derive(Debug, Clone, Error)]
+pub enum RatingError {
+    #[error("A finish needs at least 2 players, but there are {0}")]
+    NotEnoughPlayers(usize),
+    #[error("Player {0} is in the finish more than once")]
+    DuplicatePlayer(usize),
+}
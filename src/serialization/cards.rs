@@ -96,6 +96,15 @@ impl SerializableMeld {
 pub(super) struct SerializableDeck {
     pub stock: Vec<CardData>,
     pub discard_pile: Vec<CardData>,
+    /// Added in schema version 2.
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Added in schema version 2.
+    #[serde(default)]
+    pub reshuffles: u64,
+    /// Added in schema version 3.
+    #[serde(default)]
+    pub committed_seed: Option<u64>,
 }
 
 impl SerializableDeck {
@@ -115,6 +124,9 @@ impl SerializableDeck {
             stock,
             discard_pile,
             config: deck_config,
+            seed: self.seed,
+            reshuffles: self.reshuffles,
+            committed_seed: self.committed_seed,
         }
     }
 
@@ -125,6 +137,9 @@ impl SerializableDeck {
         Self {
             stock,
             discard_pile,
+            seed: deck.seed,
+            reshuffles: deck.reshuffles,
+            committed_seed: deck.committed_seed,
         }
     }
 }
@@ -6,22 +6,30 @@ use crate::{
         state::{GamePhase, GameState},
         variants::basic::{game::BasicRummyGame, rules::BasicRules, score::BasicScore},
     },
-    serialization::{cards::SerializableDeck, player::SerializablePlayer},
+    serialization::{
+        cards::SerializableDeck, migrations::VersionedBasicRummyGame, player::SerializablePlayer,
+        SCHEMA_VERSION,
+    },
 };
 use serde::{Deserialize, Serialize};
 use std::{collections::HashMap, sync::Arc};
 
 /// A serializable version of a `BasicRummyGame`.
-#[derive(Serialize, Deserialize)]
+///
+/// This is always serialized with the current `SCHEMA_VERSION`,
+/// but can be deserialized from any older version; see `migrations`.
+#[derive(Serialize)]
 pub(super) struct SerializableBasicRummyGame {
-    state: SerializableGameState<BasicScore, BasicRules>,
-    rules: BasicRules,
+    pub version: u32,
+    pub state: SerializableGameState<BasicScore, BasicRules>,
+    pub rules: BasicRules,
 }
 
 impl SerializableBasicRummyGame {
     /// Convert this from a `BasicRummyGame`.
     pub fn from_game(game: &BasicRummyGame) -> Self {
         Self {
+            version: SCHEMA_VERSION,
             state: SerializableGameState::from_gamestate(&game.state),
             rules: game.rules.clone(),
         }
@@ -52,14 +60,51 @@ impl<'de> serde::Deserialize<'de> for BasicRummyGame {
     where
         D: serde::Deserializer<'de>,
     {
-        let serialized_game = SerializableBasicRummyGame::deserialize(deserializer)?;
+        let serialized_game = VersionedBasicRummyGame::deserialize(deserializer)?
+            .migrate()
+            .map_err(serde::de::Error::custom)?;
         let game = serialized_game.into_game();
         Ok(game)
     }
 }
 
+// Any `GameState` whose score and variant state are (de)serializable can be (de)serialized,
+// so custom variants can simply derive `Serialize`/`Deserialize` on their `Game` (provided their rules are serializable too).
+impl<P, R> Serialize for GameState<P, R>
+where
+    P: VariantPlayerScore + Serialize,
+    R: GameRules<VariantScore = P>,
+    R::VariantState: Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        SerializableGameState::from_gamestate(self).serialize(serializer)
+    }
+}
+
+impl<'de, P, R> Deserialize<'de> for GameState<P, R>
+where
+    P: VariantPlayerScore + Deserialize<'de>,
+    R: GameRules<VariantScore = P>,
+    R::VariantState: Deserialize<'de>,
+{
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let state = SerializableGameState::deserialize(deserializer)?;
+        Ok(state.into_gamestate())
+    }
+}
+
 /// A serializable version of a `GameState`.
 #[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "P: Serialize, R::VariantState: Serialize",
+    deserialize = "P: Deserialize<'de>, R::VariantState: Deserialize<'de>"
+))]
 pub(super) struct SerializableGameState<P: VariantPlayerScore, R: GameRules<VariantScore = P>> {
     pub phase: GamePhase,
     pub players: Vec<SerializablePlayer>,
@@ -68,6 +113,9 @@ pub(super) struct SerializableGameState<P: VariantPlayerScore, R: GameRules<Vari
     pub current_player: usize,
     pub current_round: usize,
     pub round_scores: HashMap<usize, RoundScore<P>>,
+    /// Added in schema version 4.
+    #[serde(default)]
+    pub paused: bool,
     pub variant_state: R::VariantState,
 }
 
@@ -88,6 +136,7 @@ impl<P: VariantPlayerScore, R: GameRules<VariantScore = P>> SerializableGameStat
             current_player: self.current_player,
             current_round: self.current_round,
             round_scores: self.round_scores,
+            paused: self.paused,
             variant_state: self.variant_state,
         }
     }
@@ -108,6 +157,7 @@ impl<P: VariantPlayerScore, R: GameRules<VariantScore = P>> SerializableGameStat
             current_player: state.current_player,
             current_round: state.current_round,
             round_scores: state.round_scores.clone(),
+            paused: state.paused,
             variant_state: state.variant_state.clone(),
         }
     }
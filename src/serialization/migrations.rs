@@ -0,0 +1,76 @@
+//! Contains the migration of older schema versions into the current types.
+//!
+//! Serialized games are first read into a `VersionedBasicRummyGame`, which must be able to hold the data of
+//! every supported version (ie, fields added in later versions have a `#[serde(default)]`), and then migrated
+//! one version at a time up to `SCHEMA_VERSION`.
+//!
+//! This means the `version` field can appear anywhere in the serialized data, which is important as
+//! some formats/databases (for eg, `serde_json::Value` or Postgres' `jsonb`) reorder fields.
+//!
+//! ## Version history
+//! - `0`: The unversioned schema (`rummy` <= 0.2.0).
+//! - `1`: Adds the `version` field; otherwise identical to `0`.
+//! - `2`: Adds the deck's `seed` and `reshuffles`, for seed-derived shuffling of every round.
+//! - `3`: Adds the deck's `committed_seed`, for fair shuffles.
+//! - `4`: Adds the state's `paused` flag and the rules' `quit_hand_policy`.
+//!
+//! ## Adding a version
+//! When the serialized shape of a game changes:
+//! 1. Bump `SCHEMA_VERSION`.
+//! 2. Ensure the older shape can still be deserialized (for eg, by giving new fields a `#[serde(default)]`).
+//! 3. Add a migration step to `VersionedBasicRummyGame::migrate`.
+//! 4. Add a regression fixture for the new version under `tests/fixtures/serialization`.
+
+use crate::{
+    game::variants::basic::{rules::BasicRules, score::BasicScore},
+    serialization::{
+        game::{SerializableBasicRummyGame, SerializableGameState},
+        SCHEMA_VERSION,
+    },
+};
+use serde::Deserialize;
+
+/// A serialized `BasicRummyGame` of any supported schema version.
+#[derive(Deserialize)]
+pub(super) struct VersionedBasicRummyGame {
+    /// Unversioned data is from before versioning was introduced, so we treat it as version 0.
+    #[serde(default)]
+    version: u32,
+    state: SerializableGameState<BasicScore, BasicRules>,
+    rules: BasicRules,
+}
+
+impl VersionedBasicRummyGame {
+    /// Migrate the game to the current schema version.
+    ///
+    /// Returns an `Err` if the game is from a newer (unsupported) version.
+    pub fn migrate(mut self) -> Result<SerializableBasicRummyGame, String> {
+        if self.version > SCHEMA_VERSION {
+            return Err(format!(
+                "unsupported schema version {} (the latest supported version is {SCHEMA_VERSION})",
+                self.version
+            ));
+        }
+
+        // 0 -> 1: only the `version` field was added, so there's nothing to migrate
+
+        // 1 -> 2: decks didn't store their seed, and every round was shuffled with the config's seed as-is
+        if self.version < 2 {
+            let state = &mut self.state;
+            state.deck.seed = state.deck_config.shuffle_seed;
+            state.deck.reshuffles = 0;
+        }
+
+        // 2 -> 3: decks without a committed seed were shuffled from their own seed, which is the default,
+        // so there's nothing to migrate
+
+        // 3 -> 4: the new fields' defaults (not paused, no quit hand policy override) match the old behaviour,
+        // so there's nothing to migrate
+
+        Ok(SerializableBasicRummyGame {
+            version: SCHEMA_VERSION,
+            state: self.state,
+            rules: self.rules,
+        })
+    }
+}
@@ -1,3 +1,32 @@
+//! Contains the (de)serialization of games, enabled by the `serde` feature.
+//!
+//! Since a `Card` holds an `Arc` to its deck's config, games are (de)serialized through private
+//! "serializable" mirrors of their types, which store `CardData` and a single copy of the `DeckConfig`.
+//!
+//! ## Custom variants
+//! `GameState<P, R>` implements `Serialize`/`Deserialize` whenever the score `P` and `R::VariantState` do,
+//! so a custom variant's `Game` can simply derive them (as long as its rules are serializable too):
+//! ```ignore
+//! #[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+//! pub struct MyRummyGame {
+//!     state: GameState<MyScore, MyRules>,
+//!     rules: MyRules,
+//! }
+//! ```
+//! Wrappers like `History<MyRummyGame>` and `Replay<MyRummyGame>` are then (de)serializable as well.
+//!
+//! ## Versioning
+//! Serialized games include a schema `version` (currently [`SCHEMA_VERSION`]).
+//! Games serialized by older versions of this crate can still be deserialized, and are migrated
+//! into the current types as they're loaded.
+
 mod cards;
 mod game;
+mod migrations;
 mod player;
+
+/// The current version of the serialized schema.
+///
+/// This is bumped whenever the serialized shape of a game changes.
+/// Games serialized before versioning was introduced have no `version` field, and are treated as version `0`.
+pub const SCHEMA_VERSION: u32 = 4;
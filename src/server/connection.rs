@@ -0,0 +1,439 @@
+//! Contains the handling of a single client connection, over TCP or WebSocket.
+
+use super::{
+    lobby::{Disconnected, Forfeited, Joined, SharedLobby, Started, TakenSeat},
+    protocol::{ClientMessage, GameId, ServerMessage},
+    ServerError,
+};
+use crate::{
+    game::{
+        action::GameInteractions, event::GameEvent, state::GamePhase,
+        variants::basic::game::BasicRummyGame,
+    },
+    session::{SessionError, SessionHandle},
+};
+use futures_util::{sink, stream, Sink, SinkExt, Stream, StreamExt};
+use std::{io, pin::pin, sync::Arc};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::TcpStream,
+    sync::{mpsc, watch, Notify},
+    task::JoinHandle,
+    time,
+};
+use tokio_tungstenite::tungstenite::{Error as WsError, Message};
+
+/// The number of messages that can be queued for a client before it's disconnected for reading too slowly.
+const OUTGOING_BUFFER: usize = 256;
+
+/// What a connection is taking part in.
+struct Membership {
+    game_id: GameId,
+    /// The connection's seat, or `None` for a spectator.
+    player_id: Option<usize>,
+    /// The task forwarding the game's events and views to the connection.
+    forwarder: JoinHandle<()>,
+}
+
+/// A client connection.
+struct Connection {
+    lobby: SharedLobby,
+    outgoing: Outgoing,
+    membership: Option<Membership>,
+}
+
+/// The queue of a connection's outgoing messages.
+///
+/// It's bounded, so a client which stops reading can't make the server's memory grow;
+/// once it fills up, the connection is closed instead.
+#[derive(Clone)]
+struct Outgoing {
+    sender: mpsc::Sender<ServerMessage>,
+    /// Notified once the queue fills up.
+    full: Arc<Notify>,
+}
+
+impl Outgoing {
+    /// Queue a message, returning `false` if the connection is closing.
+    fn send(&self, message: ServerMessage) -> bool {
+        match self.sender.try_send(message) {
+            Ok(()) => true,
+            Err(mpsc::error::TrySendError::Full(_)) => {
+                self.full.notify_one();
+                false
+            }
+            Err(mpsc::error::TrySendError::Closed(_)) => false,
+        }
+    }
+}
+
+/// Serve a client over TCP, where each message is a line, until it disconnects.
+pub(super) async fn handle_tcp(stream: TcpStream, lobby: SharedLobby) {
+    let (reader, writer) = stream.into_split();
+    let incoming = stream::unfold(BufReader::new(reader).lines(), |mut lines| async move {
+        // an `Err` also means the client disconnected
+        let line = lines.next_line().await.ok().flatten()?;
+        Some((line, lines))
+    });
+    let outgoing = sink::unfold(writer, |mut writer, mut line: String| async move {
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await?;
+        Ok::<_, io::Error>(writer)
+    });
+    serve(incoming, outgoing, lobby).await;
+}
+
+/// Serve a client over WebSocket, where each message is a text message, until it disconnects.
+pub(super) async fn handle_websocket(stream: TcpStream, lobby: SharedLobby) {
+    let Ok(websocket) = tokio_tungstenite::accept_async(stream).await else {
+        return;
+    };
+    let (writer, reader) = websocket.split();
+    let incoming = stream::unfold(reader, |mut reader| async move {
+        loop {
+            match reader.next().await? {
+                Ok(Message::Text(text)) => return Some((text, reader)),
+                Ok(Message::Close(_)) | Err(_) => return None,
+                // pings are answered by `tungstenite`, and other messages aren't part of the protocol
+                Ok(_) => continue,
+            }
+        }
+    });
+    let outgoing = writer.with(|text: String| async move { Ok::<_, WsError>(Message::Text(text)) });
+    serve(incoming, outgoing, lobby).await;
+}
+
+/// Serve a client, whose messages arrive through `incoming` and are sent through `outgoing`, until it disconnects.
+async fn serve<I, O>(incoming: I, outgoing: O, lobby: SharedLobby)
+where
+    I: Stream<Item = String>,
+    O: Sink<String> + Send + 'static,
+{
+    let (sender, mut receiver) = mpsc::channel::<ServerMessage>(OUTGOING_BUFFER);
+    let writer_task = tokio::spawn(async move {
+        let mut outgoing = pin!(outgoing);
+        while let Some(message) = receiver.recv().await {
+            // UNWRAP: server messages always serialize
+            let line = serde_json::to_string(&message).unwrap();
+            if outgoing.send(line).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let full = Arc::new(Notify::new());
+    let mut connection = Connection {
+        lobby,
+        outgoing: Outgoing {
+            sender,
+            full: full.clone(),
+        },
+        membership: None,
+    };
+    let mut incoming = pin!(incoming);
+    loop {
+        let line = tokio::select! {
+            line = incoming.next() => match line {
+                Some(line) => line,
+                None => break,
+            },
+            _ = full.notified() => {
+                // the client isn't reading, so the writer may never finish
+                writer_task.abort();
+                break;
+            }
+        };
+        if line.trim().is_empty() {
+            continue;
+        }
+        let reply = match serde_json::from_str(&line) {
+            Ok(message) => connection.handle(message).await,
+            Err(err) => Err(ServerError::InvalidMessage(err.to_string())),
+        };
+        let reply = reply.unwrap_or_else(|err| ServerMessage::Error {
+            message: err.to_string(),
+        });
+        connection.outgoing.send(reply);
+    }
+
+    connection.disconnect().await;
+    drop(connection);
+    let _ = writer_task.await;
+}
+
+impl Connection {
+    /// Handle a message, returning the reply.
+    async fn handle(&mut self, message: ClientMessage) -> Result<ServerMessage, ServerError> {
+        match message {
+            ClientMessage::CreateGame => {
+                let game_id = self.lobby.lock().create();
+                self.remove_if_empty_after_timeout(game_id);
+                Ok(ServerMessage::GameCreated { game_id })
+            }
+            ClientMessage::ListGames => Ok(ServerMessage::Games {
+                games: self.lobby.lock().summaries(),
+            }),
+            ClientMessage::JoinGame { game_id } => {
+                self.ensure_not_member()?;
+                let joined = self.lobby.lock().join(game_id)?;
+                let seat = match joined {
+                    Joined::Seated(seat) => seat,
+                    Joined::Started { player_id, host } => {
+                        // the seat is only taken once the player is in the game
+                        host.add_player(player_id).await?;
+                        let seated = self.lobby.lock().seat(game_id, player_id);
+                        if seated.is_err() {
+                            // the game was removed meanwhile, so nobody could quit the seatless player
+                            let _ = host.quit_player(player_id).await;
+                        }
+                        seated?
+                    }
+                };
+                Ok(self.take_seat(game_id, seat))
+            }
+            ClientMessage::Reconnect { game_id, token } => {
+                self.ensure_not_member()?;
+                let (seat, resume) = self.lobby.lock().reconnect(game_id, &token)?;
+                let player_id = seat.player_id;
+                let reply = self.take_seat(game_id, seat);
+                if let Some(session) = resume {
+                    // this fails if the game ended while the seat was disconnected, which is fine
+                    let _ = session.resume(player_id).await;
+                }
+                Ok(reply)
+            }
+            ClientMessage::Spectate { game_id } => {
+                self.ensure_not_member()?;
+                let started = self.lobby.lock().spectate(game_id)?;
+                self.membership = Some(Membership {
+                    game_id,
+                    player_id: None,
+                    forwarder: self.forward(started, None),
+                });
+                Ok(ServerMessage::Spectating { game_id })
+            }
+            ClientMessage::Leave => self.leave().await,
+            ClientMessage::StartGame => {
+                let (game_id, player_id) = self.seat()?;
+                let session = self.lobby.lock().start(game_id)?;
+                self.remove_when_ended(game_id, session.clone());
+                session.next_round(player_id).await?;
+                Ok(ServerMessage::Ok)
+            }
+            ClientMessage::NextRound => {
+                let (game_id, player_id) = self.seat()?;
+                let session = self.lobby.lock().session(game_id)?;
+                session.next_round(player_id).await?;
+                Ok(ServerMessage::Ok)
+            }
+            ClientMessage::Interact { interaction } => {
+                let (game_id, player_id) = self.seat()?;
+                let session = self.lobby.lock().session(game_id)?;
+                match interaction {
+                    GameInteractions::Action(action) => session.act(player_id, action).await?,
+                    GameInteractions::HandRearrangement {
+                        player_id: id,
+                        new_arrangement,
+                    } => {
+                        session
+                            .rearrange_player_hand(player_id, id, new_arrangement)
+                            .await?
+                    }
+                    GameInteractions::PlayerQuit { player_id: id } if id == player_id => {
+                        return self.leave().await;
+                    }
+                    _ => return Err(ServerError::InteractionNotAllowed),
+                }
+                Ok(ServerMessage::Ok)
+            }
+        }
+    }
+
+    /// Give up the connection's seat (quitting the game if it started), or stop spectating.
+    async fn leave(&mut self) -> Result<ServerMessage, ServerError> {
+        let membership = self.membership.take().ok_or(ServerError::NotSeated)?;
+        membership.forwarder.abort();
+        if let Some(player_id) = membership.player_id {
+            let host = self.lobby.lock().leave(membership.game_id, player_id);
+            if let Some(host) = host {
+                host.quit_player(player_id).await?;
+            }
+        }
+        Ok(ServerMessage::Left)
+    }
+
+    /// Mark the connection's seat as disconnected, pausing its game if it started,
+    /// and forfeit the seat if it doesn't reconnect in time.
+    async fn disconnect(&mut self) {
+        let Some(membership) = self.membership.take() else {
+            return;
+        };
+        membership.forwarder.abort();
+        let Some(player_id) = membership.player_id else {
+            return;
+        };
+        let game_id = membership.game_id;
+        let Some(Disconnected {
+            started,
+            timeout,
+            disconnects,
+        }) = self.lobby.lock().disconnect(game_id, player_id)
+        else {
+            return;
+        };
+        if let Some(session) = started {
+            // the game may already be paused for another disconnected player
+            let _ = session.pause(player_id).await;
+        }
+
+        let lobby = self.lobby.clone();
+        tokio::spawn(async move {
+            time::sleep(timeout).await;
+            let forfeited = lobby.lock().forfeit(game_id, player_id, disconnects);
+            let Some(Forfeited {
+                host: Some(host),
+                resume,
+            }) = forfeited
+            else {
+                return;
+            };
+            // these fail if the game already ended, which is fine
+            let _ = host.quit_player(player_id).await;
+            if resume {
+                let _ = host.resume().await;
+            }
+        });
+    }
+
+    /// Spawn a task which removes a game from the lobby if nobody is seated in it after the empty game timeout.
+    fn remove_if_empty_after_timeout(&self, game_id: GameId) {
+        let lobby = self.lobby.clone();
+        let timeout = self.lobby.lock().empty_game_timeout;
+        tokio::spawn(async move {
+            time::sleep(timeout).await;
+            lobby.lock().remove_if_empty(game_id);
+        });
+    }
+
+    /// Spawn a task which removes a started game from the lobby once it ends.
+    fn remove_when_ended(&self, game_id: GameId, session: SessionHandle<BasicRummyGame>) {
+        let lobby = self.lobby.clone();
+        let Ok(mut started) = self.lobby.lock().spectate(game_id) else {
+            return;
+        };
+        tokio::spawn(async move {
+            let Ok(mut events) = session.subscribe(None).await else {
+                return;
+            };
+            let ended = async {
+                loop {
+                    match events.next_event().await {
+                        Ok(GameEvent::PhaseChanged {
+                            phase: GamePhase::GameEnd,
+                        }) => return true,
+                        Ok(_) => continue,
+                        // the missed events may have ended the game
+                        Err(SessionError::Lagged(_)) => {
+                            let view = session.view(None).await;
+                            if view.is_ok_and(|view| view.phase == GamePhase::GameEnd) {
+                                return true;
+                            }
+                        }
+                        Err(_) => return false,
+                    }
+                }
+            };
+            // stop watching if the game was already removed (for eg, because everyone left),
+            // so that the session isn't kept alive
+            let removed = async { while started.changed().await.is_ok() {} };
+            let ended = tokio::select! {
+                ended = ended => ended,
+                _ = removed => false,
+            };
+            if ended {
+                lobby.lock().remove(game_id);
+            }
+        });
+    }
+
+    /// Seat the connection, returning the reply.
+    fn take_seat(&mut self, game_id: GameId, seat: TakenSeat) -> ServerMessage {
+        self.membership = Some(Membership {
+            game_id,
+            player_id: Some(seat.player_id),
+            forwarder: self.forward(seat.started, Some(seat.player_id)),
+        });
+        ServerMessage::Joined {
+            game_id,
+            player_id: seat.player_id,
+            token: seat.token,
+        }
+    }
+
+    /// Get the game ID and player ID of the connection's seat.
+    fn seat(&self) -> Result<(GameId, usize), ServerError> {
+        match &self.membership {
+            Some(Membership {
+                game_id,
+                player_id: Some(player_id),
+                ..
+            }) => Ok((*game_id, *player_id)),
+            _ => Err(ServerError::NotSeated),
+        }
+    }
+
+    fn ensure_not_member(&self) -> Result<(), ServerError> {
+        match self.membership {
+            Some(_) => Err(ServerError::AlreadyInGame),
+            None => Ok(()),
+        }
+    }
+
+    /// Spawn a task which waits for the game to start, then forwards its events and views for `viewer`.
+    fn forward(
+        &self,
+        mut started: watch::Receiver<Started>,
+        viewer: Option<usize>,
+    ) -> JoinHandle<()> {
+        let outgoing = self.outgoing.clone();
+        tokio::spawn(async move {
+            let session = loop {
+                if let Some(session) = started.borrow_and_update().clone() {
+                    break session;
+                }
+                if started.changed().await.is_err() {
+                    return;
+                }
+            };
+            // events and views are received separately, so they each get a subscription
+            let (Ok(mut events), Ok(mut views)) = (
+                session.subscribe(viewer).await,
+                session.subscribe(viewer).await,
+            ) else {
+                return;
+            };
+            outgoing.send(ServerMessage::View { view: views.view() });
+
+            let forward_events = async {
+                loop {
+                    let event = match events.next_event().await {
+                        Ok(event) => event,
+                        Err(SessionError::Lagged(_)) => continue,
+                        Err(_) => break,
+                    };
+                    if !outgoing.send(ServerMessage::Event { event }) {
+                        break;
+                    }
+                }
+            };
+            let forward_views = async {
+                while let Ok(view) = views.view_changed().await {
+                    if !outgoing.send(ServerMessage::View { view }) {
+                        break;
+                    }
+                }
+            };
+            tokio::join!(forward_events, forward_views);
+        })
+    }
+}
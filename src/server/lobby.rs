@@ -0,0 +1,337 @@
+//! Contains the `Lobby`, which keeps track of the server's games and their seats.
+//!
+//! The lobby is shared between connections behind a mutex, so none of its methods are async;
+//! anything that has to wait on a game's session is returned for the caller to do after unlocking.
+
+use super::{
+    protocol::{GameId, GameSummary},
+    ServerError,
+};
+use crate::{
+    cards::deck::DeckConfig,
+    game::variants::basic::{config::BasicConfig, game::BasicRummyGame},
+    session::{SessionHandle, SessionHost},
+};
+use rand::Rng;
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard},
+    time::Duration,
+};
+use tokio::sync::watch;
+
+/// How long a disconnected seat is kept for by default, before it forfeits.
+pub(super) const DEFAULT_RECONNECT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// How long a game is kept for by default while nobody is seated in it.
+pub(super) const DEFAULT_EMPTY_GAME_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// The handle to a game's session, once it has started.
+pub(super) type Started = Option<SessionHandle<BasicRummyGame>>;
+
+/// The lobby, shared between the server, its connections, and their timers.
+#[derive(Clone, Default)]
+pub(super) struct SharedLobby(Arc<Mutex<Lobby>>);
+
+impl SharedLobby {
+    /// Lock the lobby.
+    pub fn lock(&self) -> MutexGuard<'_, Lobby> {
+        // UNWRAP: none of the lobby's methods panic, so the lock is never poisoned
+        self.0.lock().unwrap()
+    }
+}
+
+/// The games hosted by the server.
+pub(super) struct Lobby {
+    games: HashMap<GameId, LobbyGame>,
+    next_game_id: GameId,
+    pub reconnect_timeout: Duration,
+    pub empty_game_timeout: Duration,
+}
+
+/// A game in the lobby.
+struct LobbyGame {
+    seats: Vec<Seat>,
+    next_player_id: usize,
+    /// The host's handle to the game's session once it has started, which is kept from connections.
+    host: Option<SessionHost<BasicRummyGame>>,
+    /// Holds the game's session once it has started, so that seated connections can subscribe to it.
+    started: watch::Sender<Started>,
+}
+
+/// A player's seat in a game.
+struct Seat {
+    player_id: usize,
+    token: String,
+    connected: bool,
+    /// How many times the seat disconnected, so that a forfeit timer can tell if it reconnected since.
+    disconnects: u64,
+}
+
+/// A seat that was taken.
+pub(super) struct TakenSeat {
+    pub player_id: usize,
+    pub token: String,
+    pub started: watch::Receiver<Started>,
+}
+
+/// A seat that disconnected.
+pub(super) struct Disconnected {
+    /// The game's session, if it started, so that it can be paused.
+    pub started: Started,
+    /// How long until the seat forfeits, if it doesn't reconnect.
+    pub timeout: Duration,
+    /// Identifies this disconnect, to pass to `Lobby::forfeit` once the timeout elapses.
+    pub disconnects: u64,
+}
+
+/// A seat that forfeited after disconnecting.
+pub(super) struct Forfeited {
+    /// The game's host, if it started, so that the player can be quit from it.
+    pub host: Option<SessionHost<BasicRummyGame>>,
+    /// Whether every remaining seat is connected, so that the game can be resumed.
+    pub resume: bool,
+}
+
+/// The result of joining a game.
+pub(super) enum Joined {
+    /// The seat was taken.
+    Seated(TakenSeat),
+    /// The game already started, so the player has to be added to its session through the host
+    /// before taking their seat with `Lobby::seat`.
+    Started {
+        player_id: usize,
+        host: SessionHost<BasicRummyGame>,
+    },
+}
+
+impl Default for Lobby {
+    fn default() -> Self {
+        Self {
+            games: HashMap::new(),
+            next_game_id: 0,
+            reconnect_timeout: DEFAULT_RECONNECT_TIMEOUT,
+            empty_game_timeout: DEFAULT_EMPTY_GAME_TIMEOUT,
+        }
+    }
+}
+
+impl Lobby {
+    /// Create a game, returning its ID.
+    ///
+    /// The caller should remove it with `remove_if_empty` after `empty_game_timeout`, in case nobody joins.
+    pub fn create(&mut self) -> GameId {
+        let game_id = self.next_game_id;
+        self.next_game_id += 1;
+        let (started, _) = watch::channel(None);
+        self.games.insert(
+            game_id,
+            LobbyGame {
+                seats: Vec::new(),
+                next_player_id: 0,
+                host: None,
+                started,
+            },
+        );
+        game_id
+    }
+
+    /// Summarize every game, ordered by ID.
+    pub fn summaries(&self) -> Vec<GameSummary> {
+        let mut games: Vec<_> = self
+            .games
+            .iter()
+            .map(|(&game_id, game)| GameSummary {
+                game_id,
+                player_ids: game.seats.iter().map(|s| s.player_id).collect(),
+                started: game.started.borrow().is_some(),
+            })
+            .collect();
+        games.sort_by_key(|g| g.game_id);
+        games
+    }
+
+    /// Join a game with a new player ID, taking a seat straight away unless the game already started.
+    pub fn join(&mut self, game_id: GameId) -> Result<Joined, ServerError> {
+        let game = self.game_mut(game_id)?;
+        let player_id = game.next_player_id;
+        game.next_player_id += 1;
+        match game.host.clone() {
+            Some(host) => Ok(Joined::Started { player_id, host }),
+            None => self.seat(game_id, player_id).map(Joined::Seated),
+        }
+    }
+
+    /// Take a new seat in a game for the player with ID `player_id`, who joined it (see `join`).
+    pub fn seat(&mut self, game_id: GameId, player_id: usize) -> Result<TakenSeat, ServerError> {
+        let game = self.game_mut(game_id)?;
+        let token = new_token();
+        game.seats.push(Seat {
+            player_id,
+            token: token.clone(),
+            connected: true,
+            disconnects: 0,
+        });
+        Ok(TakenSeat {
+            player_id,
+            token,
+            started: game.started.subscribe(),
+        })
+    }
+
+    /// Take back a seat using its token.
+    ///
+    /// If the game started and every seat is now connected, the session is also returned, so that it can be resumed.
+    pub fn reconnect(
+        &mut self,
+        game_id: GameId,
+        token: &str,
+    ) -> Result<(TakenSeat, Started), ServerError> {
+        let game = self.game_mut(game_id)?;
+        let seat = game
+            .seats
+            .iter_mut()
+            .find(|s| s.token == token)
+            .ok_or(ServerError::InvalidToken)?;
+        if seat.connected {
+            return Err(ServerError::SeatInUse);
+        }
+        seat.connected = true;
+        let seat = TakenSeat {
+            player_id: seat.player_id,
+            token: seat.token.clone(),
+            started: game.started.subscribe(),
+        };
+        let all_connected = game.seats.iter().all(|s| s.connected);
+        let resume = game.started.borrow().clone().filter(|_| all_connected);
+        Ok((seat, resume))
+    }
+
+    /// Watch a game.
+    pub fn spectate(&self, game_id: GameId) -> Result<watch::Receiver<Started>, ServerError> {
+        let game = self
+            .games
+            .get(&game_id)
+            .ok_or(ServerError::GameDoesntExist)?;
+        Ok(game.started.subscribe())
+    }
+
+    /// Give up a seat, returning the game's host if it started, so that the player can be quit from it
+    /// (even if they joined mid-round, and aren't active in the game yet).
+    ///
+    /// The game is removed once its last seat is given up.
+    pub fn leave(
+        &mut self,
+        game_id: GameId,
+        player_id: usize,
+    ) -> Option<SessionHost<BasicRummyGame>> {
+        let game = self.games.get_mut(&game_id)?;
+        game.seats.retain(|s| s.player_id != player_id);
+        let host = game.host.clone();
+        self.remove_if_empty(game_id);
+        host
+    }
+
+    /// Mark a seat as disconnected.
+    ///
+    /// Returns `None` if the seat (or its game) no longer exists.
+    pub fn disconnect(&mut self, game_id: GameId, player_id: usize) -> Option<Disconnected> {
+        let game = self.games.get_mut(&game_id)?;
+        let seat = game.seats.iter_mut().find(|s| s.player_id == player_id)?;
+        seat.connected = false;
+        seat.disconnects += 1;
+        Some(Disconnected {
+            started: game.started.borrow().clone(),
+            timeout: self.reconnect_timeout,
+            disconnects: seat.disconnects,
+        })
+    }
+
+    /// Give up a seat which disconnected (see `disconnect`) and didn't reconnect in time,
+    /// removing the game if it has no seats left.
+    ///
+    /// Returns `None` if the seat reconnected or disconnected again since, or no longer exists.
+    pub fn forfeit(
+        &mut self,
+        game_id: GameId,
+        player_id: usize,
+        disconnects: u64,
+    ) -> Option<Forfeited> {
+        let game = self.games.get_mut(&game_id)?;
+        game.seats
+            .iter()
+            .find(|s| s.player_id == player_id)
+            .filter(|s| !s.connected && s.disconnects == disconnects)?;
+        game.seats.retain(|s| s.player_id != player_id);
+        let forfeited = Forfeited {
+            host: game.host.clone(),
+            resume: game.seats.iter().all(|s| s.connected),
+        };
+        self.remove_if_empty(game_id);
+        Some(forfeited)
+    }
+
+    /// Remove a game if nobody is seated in it.
+    pub fn remove_if_empty(&mut self, game_id: GameId) {
+        if self
+            .games
+            .get(&game_id)
+            .is_some_and(|game| game.seats.is_empty())
+        {
+            self.games.remove(&game_id);
+        }
+    }
+
+    /// Remove a game (for eg, once it has ended).
+    pub fn remove(&mut self, game_id: GameId) {
+        self.games.remove(&game_id);
+    }
+
+    /// Start a game with its current seats, returning its session.
+    ///
+    /// Every seat has to be connected, as the game is only paused when a seat disconnects after it starts.
+    /// The first round still has to be started through the session.
+    pub fn start(&mut self, game_id: GameId) -> Result<SessionHandle<BasicRummyGame>, ServerError> {
+        let game = self.game_mut(game_id)?;
+        if game.host.is_some() {
+            return Err(ServerError::AlreadyStarted);
+        }
+        if game.seats.iter().any(|s| !s.connected) {
+            return Err(ServerError::SeatDisconnected);
+        }
+        let player_ids = game.seats.iter().map(|s| s.player_id).collect();
+        let config = BasicConfig {
+            deal_amount: None,
+            draw_deck_amount: None,
+            draw_discard_pile_amount: None,
+            quit_hand_policy: None,
+        };
+        // without a shuffle seed, the game picks a random one
+        let host = SessionHost::spawn(BasicRummyGame::new(player_ids, config, DeckConfig::new())?);
+        let session = host.handle();
+        game.host = Some(host);
+        game.started.send_replace(Some(session.clone()));
+        Ok(session)
+    }
+
+    /// Get the session of a started game.
+    pub fn session(&self, game_id: GameId) -> Result<SessionHandle<BasicRummyGame>, ServerError> {
+        let game = self
+            .games
+            .get(&game_id)
+            .ok_or(ServerError::GameDoesntExist)?;
+        game.started.borrow().clone().ok_or(ServerError::NotStarted)
+    }
+
+    fn game_mut(&mut self, game_id: GameId) -> Result<&mut LobbyGame, ServerError> {
+        self.games
+            .get_mut(&game_id)
+            .ok_or(ServerError::GameDoesntExist)
+    }
+}
+
+/// Generate a random seat token.
+fn new_token() -> String {
+    let mut rng = rand::thread_rng();
+    format!("{:016x}{:016x}", rng.gen::<u64>(), rng.gen::<u64>())
+}
@@ -0,0 +1,110 @@
+//! Contains a game server, enabled by the `server` feature, which hosts many concurrent basic Rummy games
+//! using a JSON protocol (see [`protocol`]), over TCP (one message per line) or WebSocket (one message per text message).
+//! It's also available as the `rummy-server` binary.
+//!
+//! Clients create and join games through a lobby. Joining a game gives a per-seat token, which can be used to
+//! take the seat back after a disconnect; while any seated player is disconnected, the game is paused.
+//! A seat which isn't taken back within the reconnect timeout forfeits, quitting the game.
+//! A game can only be started while every seat is connected.
+//! Spectators can watch any game, and only receive its public events.
+//!
+//! Games are removed from the lobby once they end, or nobody is seated in them.
+//! Clients which stop reading the server's messages are disconnected once too many are queued for them.
+//!
+//! Each game runs in its own session (see [`session`](crate::session)), so a seated client can only act as its seat.
+//!
+//! ```rust
+//! let server = Server::new().reconnect_timeout(Duration::from_secs(30));
+//! let listener = TcpListener::bind("127.0.0.1:7878").await.unwrap();
+//! let websocket_listener = TcpListener::bind("127.0.0.1:7879").await.unwrap();
+//! tokio::try_join!(
+//!     server.clone().run(listener),
+//!     server.run_websocket(websocket_listener)
+//! )
+//! .unwrap();
+//! ```
+
+mod connection;
+mod lobby;
+pub mod protocol;
+
+use crate::{game::error::GameSetupError, session::SessionError};
+use lobby::SharedLobby;
+use std::{io, time::Duration};
+use thiserror::Error;
+use tokio::net::TcpListener;
+
+/// A server hosting games for its clients.
+#[derive(Clone, Default)]
+pub struct Server {
+    lobby: SharedLobby,
+}
+
+impl Server {
+    /// Create a server with an empty lobby.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set how long a disconnected seat is kept for before it forfeits (60 seconds by default).
+    pub fn reconnect_timeout(self, timeout: Duration) -> Self {
+        self.lobby.lock().reconnect_timeout = timeout;
+        self
+    }
+
+    /// Set how long a created game is kept for if nobody joins it (60 seconds by default).
+    pub fn empty_game_timeout(self, timeout: Duration) -> Self {
+        self.lobby.lock().empty_game_timeout = timeout;
+        self
+    }
+
+    /// Accept and serve TCP clients from `listener`, each in its own task.
+    ///
+    /// This only returns if accepting a connection fails.
+    pub async fn run(self, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(connection::handle_tcp(stream, self.lobby.clone()));
+        }
+    }
+
+    /// Accept and serve WebSocket clients from `listener`, each in its own task.
+    ///
+    /// This can run alongside `run` on a clone of the server, so that both kinds of clients share the lobby.
+    /// It only returns if accepting a connection fails.
+    pub async fn run_websocket(self, listener: TcpListener) -> io::Result<()> {
+        loop {
+            let (stream, _) = listener.accept().await?;
+            tokio::spawn(connection::handle_websocket(stream, self.lobby.clone()));
+        }
+    }
+}
+
+/// Errors from handling a client's message, which are sent back to the client.
+#[derive(Debug, Clone, Error)]
+pub enum ServerError {
+    #[error("Invalid message: {0}")]
+    InvalidMessage(String),
+    #[error("The game doesn't exist")]
+    GameDoesntExist,
+    #[error("The game has already started")]
+    AlreadyStarted,
+    #[error("The game can't start while a seated player is disconnected")]
+    SeatDisconnected,
+    #[error("The game hasn't started yet")]
+    NotStarted,
+    #[error("You're already in a game")]
+    AlreadyInGame,
+    #[error("You're not seated in a game")]
+    NotSeated,
+    #[error("The token doesn't match any seat in the game")]
+    InvalidToken,
+    #[error("The seat is already connected")]
+    SeatInUse,
+    #[error("Only actions, rearranging your own hand and quitting yourself are allowed")]
+    InteractionNotAllowed,
+    #[error("The game couldn't be set up: {0}")]
+    Setup(#[from] GameSetupError),
+    #[error("{0}")]
+    Session(#[from] SessionError),
+}
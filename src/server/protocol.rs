@@ -0,0 +1,80 @@
+//! Contains the messages of the server's JSON protocol.
+//!
+//! Each message sent by a client is a `ClientMessage`, and each message sent by the server is a `ServerMessage`;
+//! over TCP, each message is a line, and over WebSocket, each message is a text message.
+//! Every client message gets exactly one reply (either its result or an `Error`), in the order they were sent.
+//! Once a game starts, its `Event`s and `View`s are also sent to its players and spectators as they happen.
+
+use crate::game::{
+    action::GameInteractions, event::GameEvent, variants::basic::score::BasicScore,
+    view::PlayerView,
+};
+use serde::{Deserialize, Serialize};
+
+/// The ID of a game hosted by the server.
+pub type GameId = u64;
+
+/// A message from a client to the server.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ClientMessage {
+    /// Create a new game in the lobby.
+    ///
+    /// Its shuffle seed is picked by the server, so that clients can't stack the deck.
+    CreateGame,
+    /// List the games in the lobby.
+    ListGames,
+    /// Take a seat in a game. If the game already started, the seat is dealt in from the next round.
+    JoinGame { game_id: GameId },
+    /// Take back a seat (for eg, after reconnecting) using the token it was given when joining.
+    Reconnect { game_id: GameId, token: String },
+    /// Watch a game, receiving only its public events.
+    Spectate { game_id: GameId },
+    /// Give up your seat (quitting the game if it already started), or stop spectating.
+    Leave,
+    /// Start the game you're seated in.
+    StartGame,
+    /// Start the next round of the game you're seated in.
+    NextRound,
+    /// Interact with the game you're seated in, as your seat.
+    ///
+    /// Only actions, rearranging your own hand and quitting yourself are allowed.
+    Interact { interaction: GameInteractions },
+}
+
+/// A message from the server to a client.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ServerMessage {
+    /// A game was created.
+    GameCreated { game_id: GameId },
+    /// The games in the lobby.
+    Games { games: Vec<GameSummary> },
+    /// You took a seat. Keep the `token` to reconnect to it.
+    Joined {
+        game_id: GameId,
+        player_id: usize,
+        token: String,
+    },
+    /// You started spectating a game.
+    Spectating { game_id: GameId },
+    /// You left your seat or stopped spectating.
+    Left,
+    /// The request succeeded.
+    Ok,
+    /// The request failed.
+    Error { message: String },
+    /// Something happened in your game.
+    Event { event: GameEvent<BasicScore> },
+    /// Your latest view of your game.
+    View { view: PlayerView<BasicScore> },
+}
+
+/// A game in the lobby.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GameSummary {
+    pub game_id: GameId,
+    /// The IDs of the seated players.
+    pub player_ids: Vec<usize>,
+    pub started: bool,
+}
@@ -0,0 +1,513 @@
+//! Contains an async session driver for turn-based multiplayer, enabled by the `session` feature.
+//!
+//! A session owns a `Game` in its own task, and runs commands from any number of player tasks one at a time
+//! through a channel, so no locking is needed. Actions are executed for the player who sent them
+//! (see `Game::execute_action_as`), so one player's connection can't act on another's turn.
+//! Other commands also carry the acting player's ID, so players can only quit or rearrange their own seat,
+//! and only active players in the game (ie, who haven't quit) can pause, resume or start rounds.
+//!
+//! Seating players (adding them, or replacing one who quit) isn't done by a player in the game,
+//! so it's only available through the `SessionHost` that spawned the session, not the `SessionHandle`s
+//! given to player tasks.
+//!
+//! After each command, the resulting `GameEvent`s are broadcast to subscribers (filtered by their visibility),
+//! and each subscriber's `PlayerView` is updated.
+//!
+//! ```rust
+//! let host = SessionHost::spawn(game);
+//! let session = host.handle(); // give clones of this to the player tasks
+//! let mut subscription = session.subscribe(Some(0)).await.unwrap();
+//!
+//! session.next_round(0).await.unwrap();
+//! session.act(0, GameAction::DrawDeck(DrawDeckAction {})).await.unwrap();
+//! let event = subscription.next_event().await.unwrap(); // only events visible to player 0 arrive here...
+//! let view = subscription.view(); // ... and this is always player 0's latest view
+//! ```
+
+use crate::{
+    cards::card::CardData,
+    game::{
+        action::GameAction,
+        error::{ActionError, GameError},
+        event::{EventVisibility, GameEvent},
+        r#trait::Game,
+        score::{ScoreOf, VariantPlayerScore},
+        view::PlayerView,
+    },
+    wrappers::observed::Observed,
+};
+use thiserror::Error;
+use tokio::sync::{broadcast, mpsc, oneshot, watch};
+
+/// The sender of a subscriber's view.
+type ViewSender<G> = watch::Sender<PlayerView<ScoreOf<G>>>;
+
+/// The number of commands that can be queued before senders wait.
+const COMMAND_BUFFER: usize = 64;
+
+/// The number of events kept for slow subscribers before they start lagging.
+const EVENT_BUFFER: usize = 256;
+
+/// A handle to a running session, which can be cloned and shared between player tasks.
+///
+/// The session stops once every handle (including the `SessionHost`) is dropped.
+pub struct SessionHandle<G: Game> {
+    commands: mpsc::Sender<Command<G>>,
+}
+
+impl<G: Game> Clone for SessionHandle<G> {
+    fn clone(&self) -> Self {
+        Self {
+            commands: self.commands.clone(),
+        }
+    }
+}
+
+/// The host's handle to a running session, which can also seat players.
+///
+/// This should be kept by whatever decides who plays (for eg, a server's lobby), and not given to player tasks.
+pub struct SessionHost<G: Game> {
+    handle: SessionHandle<G>,
+}
+
+impl<G: Game> Clone for SessionHost<G> {
+    fn clone(&self) -> Self {
+        Self {
+            handle: self.handle.clone(),
+        }
+    }
+}
+
+impl<G> SessionHost<G>
+where
+    G: Game + Send + 'static,
+    ScoreOf<G>: Send + Sync + 'static,
+{
+    /// Spawn a session for `game` onto the current Tokio runtime.
+    ///
+    /// ## Panics
+    /// Panics if called outside of a Tokio runtime.
+    pub fn spawn(game: G) -> Self {
+        let (commands, receiver) = mpsc::channel(COMMAND_BUFFER);
+        let (events, _) = broadcast::channel(EVENT_BUFFER);
+        let mut game = Observed::new(game);
+        let event_sender = events.clone();
+        game.subscribe(move |event| {
+            // an `Err` just means there are no subscribers right now
+            let _ = event_sender.send(event.clone());
+        });
+        let session = Session {
+            game,
+            events,
+            views: Vec::new(),
+        };
+        tokio::spawn(session.run(receiver));
+        Self {
+            handle: SessionHandle { commands },
+        }
+    }
+
+    /// Get a handle to the session for player tasks, which can't seat players.
+    pub fn handle(&self) -> SessionHandle<G> {
+        self.handle.clone()
+    }
+
+    /// Add a player with ID `player_id` to the game.
+    pub async fn add_player(&self, player_id: usize) -> Result<(), SessionError> {
+        self.handle
+            .send(|reply| Command::AddPlayer { player_id, reply })
+            .await?
+    }
+
+    /// Let the player with ID `new_player_id` take over the seat of the player with ID `player_id`, who quit.
+    pub async fn replace_player(
+        &self,
+        player_id: usize,
+        new_player_id: usize,
+    ) -> Result<(), SessionError> {
+        self.handle
+            .send(|reply| Command::ReplacePlayer {
+                player_id,
+                new_player_id,
+                reply,
+            })
+            .await?
+    }
+
+    /// Quit the player with ID `player_id` from the game (for eg, forfeiting a player who didn't reconnect in time).
+    pub async fn quit_player(&self, player_id: usize) -> Result<(), SessionError> {
+        self.handle
+            .send(|reply| Command::QuitPlayer {
+                actor: None,
+                player_id,
+                reply,
+            })
+            .await?
+    }
+
+    /// Resume the paused game.
+    pub async fn resume(&self) -> Result<(), SessionError> {
+        self.handle
+            .send(|reply| Command::Resume { actor: None, reply })
+            .await?
+    }
+}
+
+impl<G> SessionHandle<G>
+where
+    G: Game + Send + 'static,
+    ScoreOf<G>: Send + Sync + 'static,
+{
+    /// Execute `action` for the player with ID `player_id`.
+    ///
+    /// Returns an `Err` if the action failed, including if it isn't that player's turn
+    /// (unless the game allows the action out of turn).
+    pub async fn act(&self, player_id: usize, action: GameAction) -> Result<(), SessionError> {
+        self.send(|reply| Command::Act {
+            player_id,
+            action,
+            reply,
+        })
+        .await?
+    }
+
+    /// Quit the player with ID `player_id` from the game, on behalf of the player with ID `actor`.
+    ///
+    /// Returns an `Err` if `actor` isn't `player_id`, since players can only quit themselves.
+    pub async fn quit_player(&self, actor: usize, player_id: usize) -> Result<(), SessionError> {
+        self.send(|reply| Command::QuitPlayer {
+            actor: Some(actor),
+            player_id,
+            reply,
+        })
+        .await?
+    }
+
+    /// Rearrange the hand of the player with ID `player_id`, on behalf of the player with ID `actor`.
+    ///
+    /// Returns an `Err` if `actor` isn't `player_id`, since players can only rearrange their own hand.
+    pub async fn rearrange_player_hand(
+        &self,
+        actor: usize,
+        player_id: usize,
+        new_arrangement: Vec<CardData>,
+    ) -> Result<(), SessionError> {
+        self.send(|reply| Command::RearrangeHand {
+            actor: Some(actor),
+            player_id,
+            new_arrangement,
+            reply,
+        })
+        .await?
+    }
+
+    /// Pause the game (for eg, while a player reconnects) on behalf of the player with ID `actor`,
+    /// rejecting actions until it's resumed.
+    ///
+    /// Returns an `Err` if `actor` isn't an active player in the game.
+    pub async fn pause(&self, actor: usize) -> Result<(), SessionError> {
+        self.send(|reply| Command::Pause {
+            actor: Some(actor),
+            reply,
+        })
+        .await?
+    }
+
+    /// Resume the paused game on behalf of the player with ID `actor`.
+    ///
+    /// Returns an `Err` if `actor` isn't an active player in the game.
+    pub async fn resume(&self, actor: usize) -> Result<(), SessionError> {
+        self.send(|reply| Command::Resume {
+            actor: Some(actor),
+            reply,
+        })
+        .await?
+    }
+
+    /// Calculate the round's scores and start the next round, on behalf of the player with ID `actor`.
+    ///
+    /// Returns an `Err` if `actor` isn't an active player in the game.
+    pub async fn next_round(&self, actor: usize) -> Result<(), SessionError> {
+        self.send(|reply| Command::NextRound {
+            actor: Some(actor),
+            reply,
+        })
+        .await?
+    }
+
+    /// Get the current view of the player with ID `viewer`, or a spectator if `None`.
+    pub async fn view(
+        &self,
+        viewer: Option<usize>,
+    ) -> Result<PlayerView<ScoreOf<G>>, SessionError> {
+        self.send(|reply| Command::View { viewer, reply }).await
+    }
+
+    /// Subscribe to the events and view of the player with ID `viewer`, or a spectator if `None`.
+    pub async fn subscribe(
+        &self,
+        viewer: Option<usize>,
+    ) -> Result<Subscription<ScoreOf<G>>, SessionError> {
+        self.send(|reply| Command::Subscribe { viewer, reply })
+            .await
+    }
+
+    /// Send a command built by `command`, and wait for its reply.
+    async fn send<T>(
+        &self,
+        command: impl FnOnce(oneshot::Sender<T>) -> Command<G>,
+    ) -> Result<T, SessionError> {
+        let (reply, receiver) = oneshot::channel();
+        self.commands
+            .send(command(reply))
+            .await
+            .map_err(|_| SessionError::Closed)?;
+        receiver.await.map_err(|_| SessionError::Closed)
+    }
+}
+
+/// A subscription to a session's events and view, for a single player or spectator.
+pub struct Subscription<P: VariantPlayerScore> {
+    viewer: Option<usize>,
+    events: broadcast::Receiver<GameEvent<P>>,
+    view: watch::Receiver<PlayerView<P>>,
+}
+
+impl<P: VariantPlayerScore> Subscription<P> {
+    /// Get the ID of the subscribed player, or `None` for a spectator.
+    pub fn viewer(&self) -> Option<usize> {
+        self.viewer
+    }
+
+    /// Wait for the next event visible to the subscriber.
+    ///
+    /// Returns an `Err` if the session stopped, or the subscriber fell too far behind and missed some events.
+    pub async fn next_event(&mut self) -> Result<GameEvent<P>, SessionError> {
+        loop {
+            let event = self.events.recv().await.map_err(|err| match err {
+                broadcast::error::RecvError::Closed => SessionError::Closed,
+                broadcast::error::RecvError::Lagged(missed) => SessionError::Lagged(missed),
+            })?;
+            let visible = match self.viewer {
+                Some(player_id) => event.is_visible_to(player_id),
+                None => event.visibility() == EventVisibility::Public,
+            };
+            if visible {
+                return Ok(event);
+            }
+        }
+    }
+
+    /// Get the subscriber's latest view.
+    pub fn view(&self) -> PlayerView<P> {
+        self.view.borrow().clone()
+    }
+
+    /// Wait for the subscriber's view to change, then return it.
+    ///
+    /// Returns an `Err` if the session stopped.
+    pub async fn view_changed(&mut self) -> Result<PlayerView<P>, SessionError> {
+        self.view
+            .changed()
+            .await
+            .map_err(|_| SessionError::Closed)?;
+        Ok(self.view.borrow_and_update().clone())
+    }
+}
+
+/// Errors from interacting with a session.
+#[derive(Debug, Clone, Error)]
+pub enum SessionError {
+    #[error("The action failed: {0}")]
+    Action(#[from] ActionError),
+    #[error("The command failed: {0}")]
+    Game(#[from] GameError),
+    #[error("Player {actor} isn't allowed to do that")]
+    NotAllowed { actor: usize },
+    #[error("The session has stopped")]
+    Closed,
+    #[error("The subscriber fell behind and missed {0} events")]
+    Lagged(u64),
+}
+
+/// The commands that a session runs.
+///
+/// An `actor` of `None` means the command was sent by the `SessionHost`, which is always allowed.
+enum Command<G: Game> {
+    Act {
+        player_id: usize,
+        action: GameAction,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    AddPlayer {
+        player_id: usize,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    QuitPlayer {
+        actor: Option<usize>,
+        player_id: usize,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    ReplacePlayer {
+        player_id: usize,
+        new_player_id: usize,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    RearrangeHand {
+        actor: Option<usize>,
+        player_id: usize,
+        new_arrangement: Vec<CardData>,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    Pause {
+        actor: Option<usize>,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    Resume {
+        actor: Option<usize>,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    NextRound {
+        actor: Option<usize>,
+        reply: oneshot::Sender<Result<(), SessionError>>,
+    },
+    View {
+        viewer: Option<usize>,
+        reply: oneshot::Sender<PlayerView<ScoreOf<G>>>,
+    },
+    Subscribe {
+        viewer: Option<usize>,
+        reply: oneshot::Sender<Subscription<ScoreOf<G>>>,
+    },
+}
+
+/// The task-side of a session, which owns the game.
+struct Session<G: Game> {
+    game: Observed<G>,
+    events: broadcast::Sender<GameEvent<ScoreOf<G>>>,
+    views: Vec<(Option<usize>, ViewSender<G>)>,
+}
+
+impl<G: Game> Session<G> {
+    /// Run commands until every handle is dropped.
+    async fn run(mut self, mut commands: mpsc::Receiver<Command<G>>) {
+        while let Some(command) = commands.recv().await {
+            // replies can fail if the sender stopped waiting, which we don't care about
+            match command {
+                Command::Act {
+                    player_id,
+                    action,
+                    reply,
+                } => {
+                    let result = self
+                        .game
+                        .execute_action_as(player_id, action)
+                        .map_err(SessionError::from);
+                    let _ = reply.send(result);
+                }
+                Command::AddPlayer { player_id, reply } => {
+                    let _ = reply.send(self.game.add_player(player_id).map_err(SessionError::from));
+                }
+                Command::QuitPlayer {
+                    actor,
+                    player_id,
+                    reply,
+                } => {
+                    let result = self
+                        .check_actor(actor, Some(player_id))
+                        .and_then(|_| self.game.quit_player(player_id).map_err(SessionError::from));
+                    let _ = reply.send(result);
+                }
+                Command::ReplacePlayer {
+                    player_id,
+                    new_player_id,
+                    reply,
+                } => {
+                    let result = self
+                        .game
+                        .replace_player(player_id, new_player_id)
+                        .map_err(SessionError::from);
+                    let _ = reply.send(result);
+                }
+                Command::RearrangeHand {
+                    actor,
+                    player_id,
+                    new_arrangement,
+                    reply,
+                } => {
+                    let result = self.check_actor(actor, Some(player_id)).and_then(|_| {
+                        self.game
+                            .rearrange_player_hand(player_id, new_arrangement)
+                            .map_err(SessionError::from)
+                    });
+                    let _ = reply.send(result);
+                }
+                Command::Pause { actor, reply } => {
+                    let result = self
+                        .check_actor(actor, None)
+                        .and_then(|_| self.game.pause().map_err(SessionError::from));
+                    let _ = reply.send(result);
+                }
+                Command::Resume { actor, reply } => {
+                    let result = self
+                        .check_actor(actor, None)
+                        .and_then(|_| self.game.resume().map_err(SessionError::from));
+                    let _ = reply.send(result);
+                }
+                Command::NextRound { actor, reply } => {
+                    let result = self
+                        .check_actor(actor, None)
+                        .and_then(|_| self.game.next_round().map_err(SessionError::from));
+                    let _ = reply.send(result);
+                }
+                Command::View { viewer, reply } => {
+                    let _ = reply.send(self.game.get_state().view_for(viewer));
+                }
+                Command::Subscribe { viewer, reply } => {
+                    let (sender, view) = watch::channel(self.game.get_state().view_for(viewer));
+                    self.views.push((viewer, sender));
+                    let _ = reply.send(Subscription {
+                        viewer,
+                        events: self.events.subscribe(),
+                        view,
+                    });
+                }
+            }
+            self.update_views();
+        }
+    }
+
+    /// Check that `actor` is an active player in the game (ie, one who hasn't quit),
+    /// and is acting on their own seat if it's `target`.
+    ///
+    /// The host (an `actor` of `None`) can always act.
+    fn check_actor(&self, actor: Option<usize>, target: Option<usize>) -> Result<(), SessionError> {
+        let Some(actor) = actor else {
+            return Ok(());
+        };
+        let in_game = self
+            .game
+            .get_state()
+            .players()
+            .iter()
+            .any(|player| player.id() == actor && player.active());
+        if !in_game || target.is_some_and(|target| target != actor) {
+            return Err(SessionError::NotAllowed { actor });
+        }
+        Ok(())
+    }
+
+    /// Update every subscriber's view, dropping those without any subscribers left.
+    fn update_views(&mut self) {
+        let state = self.game.get_state();
+        self.views.retain(|(viewer, sender)| {
+            sender.send_if_modified(|view| {
+                let new_view = state.view_for(*viewer);
+                let modified = *view != new_view;
+                *view = new_view;
+                modified
+            });
+            !sender.is_closed()
+        });
+    }
+}
@@ -0,0 +1,130 @@
+//! Contains the `Bot` trait for automated players, and some simple bots which can play any variant.
+
+use crate::{
+    cards::card::Card,
+    game::{
+        action::{
+            DiscardAction, DrawDeckAction, DrawDiscardPileAction, FormMeldAction, GameAction,
+        },
+        danger::discard_dangers,
+        hints::{discard_value, find_layoffs, find_melds},
+        r#trait::Game,
+        state::GamePhase,
+        tracker::CardTracker,
+    },
+    player::Player,
+};
+use rand::{rngs::StdRng, Rng, SeedableRng};
+
+/// An automated player.
+pub trait Bot<G: Game> {
+    /// Choose the next action for the player with ID `player_id`, whose turn it is in `game`.
+    fn next_action(&mut self, game: &G, player_id: usize) -> GameAction;
+}
+
+/// A bot which lays off and melds whenever it can, only draws from the discard pile when it can use the top card
+/// right away, and discards its highest scoring card, weighed against how risky discarding it is (see `discard_dangers`).
+///
+/// The bot doesn't remember anything between turns, so it only knows what's visible on the table.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct GreedyBot;
+
+impl<G: Game> Bot<G> for GreedyBot {
+    fn next_action(&mut self, game: &G, player_id: usize) -> GameAction {
+        let state = game.get_state();
+        let Some(player) = find_player(state.players(), player_id) else {
+            return GameAction::DrawDeck(DrawDeckAction {});
+        };
+        match state.phase() {
+            GamePhase::Draw => {
+                let top_card_is_useful = state.deck().discard_pile().last().is_some_and(|top| {
+                    let mut hand = player.cards().clone();
+                    hand.push(top.clone());
+                    let top_index = hand.len() - 1;
+                    find_melds(&hand)
+                        .first()
+                        .is_some_and(|meld| meld.contains(&top_index))
+                        || find_layoffs(&hand, state.players())
+                            .first()
+                            .is_some_and(|layoff| layoff.card_index == top_index)
+                });
+                match top_card_is_useful {
+                    true => GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(1) }),
+                    false => GameAction::DrawDeck(DrawDeckAction {}),
+                }
+            }
+            _ => play_or_discard(player, state.players(), |hand| {
+                let mut tracker = CardTracker::new(player_id, state.deck().config());
+                tracker.sync(&state.view_for(Some(player_id)));
+                let dangers = discard_dangers(game, &tracker);
+                let value = |i: usize| {
+                    let danger = dangers.get(i).map_or(0.0, |d| d.score);
+                    discard_value(hand[i].score_value(), danger)
+                };
+                (0..hand.len())
+                    .max_by(|&a, &b| value(a).total_cmp(&value(b)))
+                    .unwrap_or(0)
+            }),
+        }
+    }
+}
+
+/// A bot which lays off and melds whenever it can, but otherwise plays randomly;
+/// it draws from a random pile, and discards a random card.
+#[derive(Clone, Debug)]
+pub struct RandomBot {
+    rng: StdRng,
+}
+
+impl RandomBot {
+    /// Create a bot whose choices are seeded by `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            rng: StdRng::seed_from_u64(seed),
+        }
+    }
+}
+
+impl<G: Game> Bot<G> for RandomBot {
+    fn next_action(&mut self, game: &G, player_id: usize) -> GameAction {
+        let state = game.get_state();
+        let Some(player) = find_player(state.players(), player_id) else {
+            return GameAction::DrawDeck(DrawDeckAction {});
+        };
+        match state.phase() {
+            GamePhase::Draw => {
+                if !state.deck().discard_pile().is_empty() && self.rng.gen_bool(0.5) {
+                    GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(1) })
+                } else {
+                    GameAction::DrawDeck(DrawDeckAction {})
+                }
+            }
+            _ => play_or_discard(player, state.players(), |hand| {
+                self.rng.gen_range(0..hand.len().max(1))
+            }),
+        }
+    }
+}
+
+fn find_player(players: &[Player], player_id: usize) -> Option<&Player> {
+    players.iter().find(|p| p.id() == player_id)
+}
+
+/// Lay off or meld if possible, otherwise discard the card chosen by `choose_discard`.
+fn play_or_discard(
+    player: &Player,
+    players: &[Player],
+    choose_discard: impl FnOnce(&[Card]) -> usize,
+) -> GameAction {
+    let hand = player.cards();
+    if let Some(layoff) = find_layoffs(hand, players).into_iter().next() {
+        return GameAction::LayOff(layoff);
+    }
+    if let Some(card_indices) = find_melds(hand).into_iter().next() {
+        return GameAction::FormMeld(FormMeldAction { card_indices });
+    }
+    GameAction::Discard(DiscardAction {
+        card_index: choose_discard(hand),
+        declare_going_out: None,
+    })
+}
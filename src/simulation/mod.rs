@@ -0,0 +1,469 @@
+//! Contains a harness for batch self-play, which plays many seeded games between bots in parallel
+//! and reports statistics about them (also available as the `rummy-sim` binary).
+//!
+//! This is useful for balancing house rules; for example, to see how drawing the whole discard pile changes
+//! the length of rounds:
+//!
+//! ```rust
+//! let config = SimulationConfig { games: 1000, ..SimulationConfig::default() };
+//! let new_game = |seed| {
+//!     let config = BasicConfig {
+//!         deal_amount: None,
+//!         draw_deck_amount: None,
+//!         draw_discard_pile_amount: Some(DrawDiscardPileOverride::WholePile),
+//!         quit_hand_policy: None,
+//!     };
+//!     let deck_config = DeckConfig { shuffle_seed: Some(seed), ..DeckConfig::new() };
+//!     BasicRummyGame::new(vec![0, 1], config, deck_config).unwrap()
+//! };
+//! let bots: Vec<BotFactory<_>> = vec![
+//!     Box::new(|_| Box::new(GreedyBot)),
+//!     Box::new(|seed| Box::new(RandomBot::new(seed))),
+//! ];
+//! let report = Simulation::new(config, new_game, bots).run().unwrap();
+//! println!("{report}");
+//! ```
+
+pub mod bots;
+
+use crate::game::{
+    action::{DiscardAction, DrawDeckAction, GameAction},
+    error::GameError,
+    r#trait::Game,
+    score::{Points, ScoreOf},
+    state::GamePhase,
+};
+use bots::Bot;
+use std::{fmt::Display, num::NonZeroUsize, thread};
+use thiserror::Error;
+
+/// Creates the bot for a seat, given a seed for any of its random choices.
+pub type BotFactory<G> = Box<dyn Fn(u64) -> Box<dyn Bot<G>> + Send + Sync>;
+
+/// How to run a simulation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SimulationConfig {
+    /// The number of games to play.
+    pub games: usize,
+    /// The number of rounds in each game.
+    pub rounds_per_game: usize,
+    /// The seed of the first game; each following game's seed is one more than the last.
+    pub seed: u64,
+    /// The number of turns after which a round is abandoned (along with the rest of its game).
+    pub max_turns_per_round: usize,
+    /// The number of threads to play games on, or `None` to use all available cores.
+    pub threads: Option<NonZeroUsize>,
+}
+
+impl Default for SimulationConfig {
+    fn default() -> Self {
+        Self {
+            games: 100,
+            rounds_per_game: 1,
+            seed: 1,
+            max_turns_per_round: 1000,
+            threads: None,
+        }
+    }
+}
+
+/// A batch of seeded games played between bots.
+pub struct Simulation<G: Game, F: Fn(u64) -> G> {
+    config: SimulationConfig,
+    new_game: F,
+    bots: Vec<BotFactory<G>>,
+}
+
+impl<G: Game, F: Fn(u64) -> G + Sync> Simulation<G, F>
+where
+    ScoreOf<G>: Points,
+{
+    /// Create a simulation, where each game is created by `new_game` from its seed.
+    ///
+    /// The bot for each seat (in the order of the game's players) is created by the matching factory in `bots`.
+    pub fn new(config: SimulationConfig, new_game: F, bots: Vec<BotFactory<G>>) -> Self {
+        Self {
+            config,
+            new_game,
+            bots,
+        }
+    }
+
+    /// Play every game, returning a report of them.
+    ///
+    /// Returns an `Err` if a game doesn't have a bot for each seat, couldn't start a round,
+    /// or got stuck on a turn where neither the bot's action nor the fallback worked.
+    pub fn run(&self) -> Result<SimulationReport, SimulationError> {
+        let threads = self
+            .config
+            .threads
+            .or_else(|| thread::available_parallelism().ok())
+            .map_or(1, NonZeroUsize::get)
+            .min(self.config.games.max(1));
+
+        let results = thread::scope(|scope| {
+            let handles: Vec<_> = (0..threads)
+                .map(|thread| {
+                    scope.spawn(move || {
+                        (thread..self.config.games)
+                            .step_by(threads)
+                            .map(|i| self.play_game(self.config.seed.wrapping_add(i as u64)))
+                            .collect::<Result<Vec<_>, _>>()
+                    })
+                })
+                .collect();
+            handles
+                .into_iter()
+                // UNWRAP: only panics if a game or bot panicked
+                .map(|handle| handle.join().unwrap())
+                .collect::<Result<Vec<_>, _>>()
+        })?;
+
+        // put the games back in order, so the report doesn't depend on how they were split between threads
+        let mut games: Vec<Option<GameResult>> = vec![None; self.config.games];
+        for (thread, thread_results) in results.into_iter().enumerate() {
+            for (n, result) in thread_results.into_iter().enumerate() {
+                games[thread + n * threads] = Some(result);
+            }
+        }
+        let mut report = SimulationReport::new(self.bots.len());
+        for game in games.into_iter().flatten() {
+            report.add_game(game);
+        }
+        Ok(report)
+    }
+
+    /// Play a game from its seed.
+    fn play_game(&self, seed: u64) -> Result<GameResult, SimulationError> {
+        let mut game = (self.new_game)(seed);
+        let player_ids: Vec<_> = game.get_state().players().iter().map(|p| p.id()).collect();
+        if player_ids.len() != self.bots.len() {
+            return Err(SimulationError::WrongBotCount {
+                seats: player_ids.len(),
+                bots: self.bots.len(),
+            });
+        }
+        let mut bots: Vec<_> = self
+            .bots
+            .iter()
+            .enumerate()
+            .map(|(seat, new_bot)| new_bot(seed.wrapping_add(seat as u64)))
+            .collect();
+        let seat_of = |player_id| player_ids.iter().position(|&id| id == player_id);
+
+        let mut result = GameResult {
+            rounds: Vec::new(),
+            invalid_actions: 0,
+        };
+        game.next_round()
+            .map_err(|err| SimulationError::FailedRoundStart { seed, err })?;
+        for _ in 0..self.config.rounds_per_game {
+            let state = game.get_state();
+            let round = state.current_round();
+            let mut round_result = RoundResult {
+                turns: 0,
+                starting_seat: state.current_player_index(),
+                stock_ran_out: false,
+                scores: None,
+            };
+
+            loop {
+                let state = game.get_state();
+                if state.phase() == GamePhase::RoundEnd
+                    || state.phase() == GamePhase::GameEnd
+                    || round_result.turns >= self.config.max_turns_per_round
+                {
+                    break;
+                }
+                let seat = state.current_player_index();
+                let player_id = state.players()[seat].id();
+                let drawing = state.phase() == GamePhase::Draw;
+                let stock_size = state.deck().stock().len();
+
+                let action = bots[seat].next_action(&game, player_id);
+                if game.execute_action(action).is_err() {
+                    result.invalid_actions += 1;
+                    game.execute_action(fallback_action(&game))
+                        .map_err(|_| SimulationError::StuckTurn { seed, round })?;
+                }
+
+                // the stock is turned over when a draw needs more cards than it has
+                let new_stock_size = game.get_state().deck().stock().len();
+                if new_stock_size > stock_size || new_stock_size == 0 {
+                    round_result.stock_ran_out = true;
+                }
+                round_result.turns += drawing as usize;
+            }
+
+            if game.get_state().phase() != GamePhase::RoundEnd {
+                // the round was abandoned, so it has no scores, and the game can't continue
+                result.rounds.push(round_result);
+                break;
+            }
+            game.next_round()
+                .map_err(|err| SimulationError::FailedRoundStart { seed, err })?;
+            let score = &game.get_state().round_scores()[&round];
+            round_result.scores = Some(RoundScores {
+                winner_seat: seat_of(score.winner_id).unwrap_or_default(),
+                points: player_ids
+                    .iter()
+                    .map(|id| score.player_scores.get(id).map_or(0, Points::points))
+                    .collect(),
+            });
+            result.rounds.push(round_result);
+        }
+        Ok(result)
+    }
+}
+
+/// The action played when a bot's action fails: drawing from the deck, or discarding the last card in hand.
+fn fallback_action<G: Game>(game: &G) -> GameAction {
+    let state = game.get_state();
+    match state.phase() {
+        GamePhase::Draw => GameAction::DrawDeck(DrawDeckAction {}),
+        _ => GameAction::Discard(DiscardAction {
+            card_index: state.players()[state.current_player_index()]
+                .cards()
+                .len()
+                .saturating_sub(1),
+            declare_going_out: None,
+        }),
+    }
+}
+
+/// The result of a single game.
+#[derive(Clone, Debug)]
+struct GameResult {
+    rounds: Vec<RoundResult>,
+    invalid_actions: usize,
+}
+
+/// The result of a single round.
+#[derive(Clone, Debug)]
+struct RoundResult {
+    turns: usize,
+    starting_seat: usize,
+    stock_ran_out: bool,
+    /// `None` if the round was abandoned.
+    scores: Option<RoundScores>,
+}
+
+#[derive(Clone, Debug)]
+struct RoundScores {
+    winner_seat: usize,
+    /// The points of each seat.
+    points: Vec<u32>,
+}
+
+/// Statistics about the games played in a simulation, by seat (in the order of the game's players).
+#[derive(Clone, Debug, PartialEq)]
+pub struct SimulationReport {
+    /// The number of games played.
+    pub games: usize,
+    /// The number of games where every round was finished.
+    pub completed_games: usize,
+    /// The number of rounds finished.
+    pub rounds: usize,
+    /// The number of rounds abandoned after reaching the turn limit.
+    pub abandoned_rounds: usize,
+    /// The number of completed games won by each seat, by having the fewest total points (ties go to the earliest seat).
+    pub game_wins: Vec<usize>,
+    /// The number of rounds won by each seat.
+    pub round_wins: Vec<usize>,
+    /// The number of rounds won by the seat which started them.
+    pub starting_seat_wins: usize,
+    /// The number of rounds (finished or not) in which the stock ran out.
+    pub stock_exhaustions: usize,
+    /// The number of bot actions which failed, and were replaced by drawing from the deck or discarding.
+    pub invalid_actions: usize,
+    /// The number of turns in each finished round.
+    pub round_lengths: Vec<usize>,
+    /// The points of each seat in each finished round.
+    pub points: Vec<Vec<u32>>,
+}
+
+impl SimulationReport {
+    fn new(seats: usize) -> Self {
+        Self {
+            games: 0,
+            completed_games: 0,
+            rounds: 0,
+            abandoned_rounds: 0,
+            game_wins: vec![0; seats],
+            round_wins: vec![0; seats],
+            starting_seat_wins: 0,
+            stock_exhaustions: 0,
+            invalid_actions: 0,
+            round_lengths: Vec::new(),
+            points: vec![Vec::new(); seats],
+        }
+    }
+
+    fn add_game(&mut self, game: GameResult) {
+        self.games += 1;
+        self.invalid_actions += game.invalid_actions;
+        let mut totals = vec![0; self.game_wins.len()];
+        let mut completed = true;
+        for round in game.rounds {
+            self.stock_exhaustions += round.stock_ran_out as usize;
+            let Some(scores) = round.scores else {
+                self.abandoned_rounds += 1;
+                completed = false;
+                continue;
+            };
+            self.rounds += 1;
+            self.round_wins[scores.winner_seat] += 1;
+            self.starting_seat_wins += (scores.winner_seat == round.starting_seat) as usize;
+            self.round_lengths.push(round.turns);
+            for (seat, points) in scores.points.into_iter().enumerate() {
+                totals[seat] += points;
+                self.points[seat].push(points);
+            }
+        }
+        if completed {
+            self.completed_games += 1;
+            // `min_by_key` returns the first minimum, so ties go to the earliest seat
+            if let Some((seat, _)) = totals.iter().enumerate().min_by_key(|&(_, total)| total) {
+                self.game_wins[seat] += 1;
+            }
+        }
+    }
+
+    /// The fraction of completed games won by `seat`.
+    pub fn game_win_rate(&self, seat: usize) -> f64 {
+        ratio(self.game_wins[seat], self.completed_games)
+    }
+
+    /// The fraction of finished rounds won by `seat`.
+    pub fn round_win_rate(&self, seat: usize) -> f64 {
+        ratio(self.round_wins[seat], self.rounds)
+    }
+
+    /// The fraction of finished rounds won by the seat which started them.
+    ///
+    /// Compare this to `1 / seats` to see the first player's advantage.
+    pub fn starting_seat_win_rate(&self) -> f64 {
+        ratio(self.starting_seat_wins, self.rounds)
+    }
+
+    /// The fraction of rounds (finished or not) in which the stock ran out.
+    pub fn stock_exhaustion_rate(&self) -> f64 {
+        ratio(self.stock_exhaustions, self.rounds + self.abandoned_rounds)
+    }
+
+    /// The mean number of turns in a finished round.
+    pub fn average_round_length(&self) -> f64 {
+        ratio(self.round_lengths.iter().sum(), self.round_lengths.len())
+    }
+
+    /// Summarise the distribution of `seat`'s points per round.
+    pub fn points_summary(&self, seat: usize) -> Summary {
+        Summary::of(&self.points[seat])
+    }
+}
+
+fn ratio(numerator: usize, denominator: usize) -> f64 {
+    match denominator {
+        0 => 0.0,
+        _ => numerator as f64 / denominator as f64,
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(
+            f,
+            "Games: {} ({} completed), rounds: {} ({} abandoned)",
+            self.games, self.completed_games, self.rounds, self.abandoned_rounds
+        )?;
+        writeln!(
+            f,
+            "Average round length: {:.2} turns",
+            self.average_round_length()
+        )?;
+        writeln!(
+            f,
+            "Stock ran out in {:.1}% of rounds",
+            100.0 * self.stock_exhaustion_rate()
+        )?;
+        writeln!(
+            f,
+            "Starting seat won {:.1}% of rounds (even odds: {:.1}%)",
+            100.0 * self.starting_seat_win_rate(),
+            100.0 * ratio(1, self.round_wins.len())
+        )?;
+        if self.invalid_actions > 0 {
+            writeln!(f, "Invalid bot actions: {}", self.invalid_actions)?;
+        }
+        for seat in 0..self.round_wins.len() {
+            let points = self.points_summary(seat);
+            writeln!(
+                f,
+                "Seat {seat}: won {:.1}% of games, {:.1}% of rounds; points per round: {points}",
+                100.0 * self.game_win_rate(seat),
+                100.0 * self.round_win_rate(seat),
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// A summary of a distribution of values.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Summary {
+    pub mean: f64,
+    pub std_dev: f64,
+    pub min: u32,
+    pub median: u32,
+    /// The 90th percentile.
+    pub p90: u32,
+    pub max: u32,
+}
+
+impl Summary {
+    /// Summarise `values`, or return all zeroes if there are none.
+    pub fn of(values: &[u32]) -> Self {
+        if values.is_empty() {
+            return Self::default();
+        }
+        let mut sorted = values.to_vec();
+        sorted.sort_unstable();
+        let n = sorted.len() as f64;
+        let mean = sorted.iter().map(|&v| v as f64).sum::<f64>() / n;
+        let variance = sorted
+            .iter()
+            .map(|&v| (v as f64 - mean).powi(2))
+            .sum::<f64>()
+            / n;
+        let percentile = |p: usize| sorted[(sorted.len() - 1) * p / 100];
+        Self {
+            mean,
+            std_dev: variance.sqrt(),
+            min: sorted[0],
+            median: percentile(50),
+            p90: percentile(90),
+            max: sorted[sorted.len() - 1],
+        }
+    }
+}
+
+impl Display for Summary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "mean {:.1} (sd {:.1}), min {}, median {}, p90 {}, max {}",
+            self.mean, self.std_dev, self.min, self.median, self.p90, self.max
+        )
+    }
+}
+
+/// Errors from running a simulation.
+#[derive(Debug, Clone, Error)]
+pub enum SimulationError {
+    #[error("The game has {seats} seats, but there are {bots} bots")]
+    WrongBotCount { seats: usize, bots: usize },
+    #[error("The game with seed {seed} couldn't start a round: {err}")]
+    FailedRoundStart { seed: u64, err: GameError },
+    #[error("In the game with seed {seed}, round {round} got stuck: neither the bot's action nor the fallback worked")]
+    StuckTurn { seed: u64, round: usize },
+}
@@ -0,0 +1,217 @@
+//! Contains `CareerStats`, which aggregates each player's statistics over many games from their `History`.
+//!
+//! Games are added one at a time, so stats can be kept up to date as games finish:
+//!
+//! ```rust
+//! let mut stats = CareerStats::new();
+//! stats.add_game(&history);
+//! let player = &stats.players()[&0];
+//! println!("won {:.0}% of games, going out {:.0}% of rounds", player.win_rate() * 100.0, player.go_out_rate() * 100.0);
+//! ```
+//!
+//! With the `serde` feature, the stats can be (de)serialized to keep them between sessions.
+
+use crate::{
+    cards::suit_rank::Rank,
+    game::{
+        action::{GameAction, GameInteractions},
+        r#trait::Game,
+        score::{Points, ScoreOf},
+    },
+    tournament::TableResult,
+    wrappers::history::{apply_interaction, History},
+};
+use std::collections::HashMap;
+
+/// Statistics for every player over many games.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct CareerStats {
+    games: usize,
+    players: HashMap<usize, PlayerStats>,
+}
+
+/// A single player's statistics over many games.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PlayerStats {
+    /// The number of games played.
+    pub games_played: usize,
+    /// The number of games won (ie, finished with the fewest total points), including ties.
+    pub games_won: usize,
+    /// The number of completed rounds played.
+    pub rounds_played: usize,
+    /// The number of rounds won by going out.
+    pub rounds_won: usize,
+    /// The total points left in hand at the end of each completed round.
+    pub deadwood: u64,
+    /// The number of draws.
+    pub draws: usize,
+    /// The number of draws from the discard pile.
+    pub discard_pile_draws: usize,
+    /// The number of melds formed.
+    pub melds: usize,
+    /// The number of cards laid off onto the player's own melds.
+    pub own_layoffs: usize,
+    /// The number of cards laid off onto opponents' melds.
+    pub opponent_layoffs: usize,
+    /// The number of discards of each rank.
+    pub discards: HashMap<Rank, usize>,
+}
+
+impl CareerStats {
+    /// Create stats with no games yet.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Get the number of games added.
+    pub fn games(&self) -> usize {
+        self.games
+    }
+
+    /// Get every player's stats.
+    pub fn players(&self) -> &HashMap<usize, PlayerStats> {
+        &self.players
+    }
+
+    /// Add a game's stats, by replaying its history.
+    ///
+    /// Only completed rounds (ie, rounds whose score is recorded) count towards the stats, other than games played.
+    /// The game is counted as won by whoever has the fewest points over those rounds;
+    /// see `TableResult::placings` for exactly how that's decided.
+    pub fn add_game<G: Game + Clone>(&mut self, history: &History<G>)
+    where
+        ScoreOf<G>: Points,
+    {
+        self.games += 1;
+
+        let completed = history.get_game().get_state().round_scores();
+        let mut rounds: Vec<_> = history.get_initial_round_states().keys().copied().collect();
+        rounds.sort();
+        let mut players = Vec::new();
+        for round in rounds {
+            let round_completed = completed.contains_key(&round);
+            let mut game = history.get_initial_round_states()[&round].clone();
+            players.extend(game.get_state().players().iter().map(|p| p.id()));
+            for entry in history.get_histories().get(&round).into_iter().flatten() {
+                if !entry.took_effect() {
+                    continue;
+                }
+                match &entry.entry {
+                    GameInteractions::Action(action) if round_completed => {
+                        let actor = entry
+                            .actor
+                            .or_else(|| game.get_state().get_current_player().ok().map(|p| p.id()));
+                        if let Some(actor) = actor {
+                            self.add_action(&game, actor, action);
+                        }
+                    }
+                    GameInteractions::PlayerJoin { player_id }
+                    | GameInteractions::PlayerReplaced {
+                        new_player_id: player_id,
+                        ..
+                    } => players.push(*player_id),
+                    _ => {}
+                }
+                apply_interaction(&mut game, entry.entry.clone(), entry.actor);
+            }
+        }
+
+        let result = TableResult::from_game(history.get_game());
+        for round in &result.rounds {
+            for (&id, &points) in &round.points {
+                let stats = self.players.entry(id).or_default();
+                stats.rounds_played += 1;
+                stats.deadwood += u64::from(points);
+                if id == round.winner_id {
+                    stats.rounds_won += 1;
+                }
+            }
+        }
+        if let Some(winners) = result.finish().first() {
+            for &id in winners {
+                self.players.entry(id).or_default().games_won += 1;
+            }
+        }
+
+        players.sort();
+        players.dedup();
+        for id in players {
+            self.players.entry(id).or_default().games_played += 1;
+        }
+    }
+
+    /// Add an action taken by `actor`, given the game just before it's applied.
+    fn add_action<G: Game>(&mut self, game: &G, actor: usize, action: &GameAction) {
+        let state = game.get_state();
+        let stats = self.players.entry(actor).or_default();
+        match action {
+            GameAction::DrawDeck(_) => stats.draws += 1,
+            GameAction::DrawDiscardPile(_) => {
+                stats.draws += 1;
+                stats.discard_pile_draws += 1;
+            }
+            GameAction::FormMeld(_) => stats.melds += 1,
+            GameAction::FormMelds(action) => stats.melds += action.melds.len(),
+            GameAction::LayOff(action) => match state.players().get(action.target_player_index) {
+                Some(target) if target.id() == actor => stats.own_layoffs += 1,
+                _ => stats.opponent_layoffs += 1,
+            },
+            GameAction::Discard(action) => {
+                let card = state
+                    .players()
+                    .iter()
+                    .find(|p| p.id() == actor)
+                    .and_then(|p| p.cards().get(action.card_index));
+                if let Some(card) = card {
+                    *stats.discards.entry(card.rank).or_default() += 1;
+                }
+            }
+        }
+    }
+}
+
+impl PlayerStats {
+    /// The fraction of games won.
+    pub fn win_rate(&self) -> f64 {
+        ratio(self.games_won, self.games_played)
+    }
+
+    /// The fraction of completed rounds in which the player went out.
+    pub fn go_out_rate(&self) -> f64 {
+        ratio(self.rounds_won, self.rounds_played)
+    }
+
+    /// The average points left in hand at the end of a round.
+    pub fn average_deadwood(&self) -> f64 {
+        match self.rounds_played {
+            0 => 0.0,
+            rounds => self.deadwood as f64 / rounds as f64,
+        }
+    }
+
+    /// The fraction of draws taken from the discard pile.
+    pub fn discard_pile_draw_rate(&self) -> f64 {
+        ratio(self.discard_pile_draws, self.draws)
+    }
+
+    /// The average number of melds formed in a completed round.
+    pub fn average_melds_per_round(&self) -> f64 {
+        ratio(self.melds, self.rounds_played)
+    }
+
+    /// Get the discarded ranks, from most to least discarded.
+    pub fn most_discarded_ranks(&self) -> Vec<(Rank, usize)> {
+        let mut ranks: Vec<_> = self.discards.iter().map(|(&r, &n)| (r, n)).collect();
+        ranks.sort_by(|(a_rank, a), (b_rank, b)| b.cmp(a).then(a_rank.cmp(b_rank)));
+        ranks
+    }
+}
+
+fn ratio(count: usize, total: usize) -> f64 {
+    match total {
+        0 => 0.0,
+        total => count as f64 / total as f64,
+    }
+}
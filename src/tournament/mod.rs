@@ -0,0 +1,360 @@
+//! Contains the `Tournament`, which runs a tournament of any variant over many tables and rounds.
+//!
+//! A tournament only handles the seating and the standings; each table's game is played separately (at a real table,
+//! over the `server`, between bots...) with the table's entrant IDs as its player IDs, and its result is then recorded:
+//!
+//! ```rust
+//! let config = TournamentConfig {
+//!     format: TournamentFormat::Swiss { rounds: 3 },
+//!     ..TournamentConfig::default()
+//! };
+//! let mut tournament = Tournament::new(config, vec!["Ann".into(), "Bo".into(), "Cy".into(), "Di".into()])?;
+//! while !tournament.is_finished() {
+//!     let round = tournament.start_round()?.clone();
+//!     for (table_index, table) in round.tables.iter().enumerate() {
+//!         let mut game = BasicRummyGame::new(table.players.clone(), game_config.clone(), deck_config.clone())?;
+//!         // ...play a few rounds of the game...
+//!         tournament.record_result(table_index, TableResult::from_game(&game))?;
+//!     }
+//! }
+//! let winner = tournament.winner().unwrap();
+//! println!("{} won!", tournament.entrants()[winner]);
+//! ```
+//!
+//! With the `serde` feature, a tournament can be saved at any point and resumed later.
+
+mod pairing;
+mod result;
+mod standings;
+
+pub use result::{ScoredRound, TableResult};
+pub use standings::{Standing, TieBreak};
+
+use std::{cmp::Reverse, collections::HashSet};
+use thiserror::Error;
+
+/// How players are seated each round, and how long the tournament lasts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TournamentFormat {
+    /// Players with similar standings are seated together, avoiding rematches where possible, for a set number of rounds.
+    Swiss { rounds: usize },
+    /// Every player plays every other player once, head-to-head.
+    RoundRobin,
+    /// Only the winner of each table goes through to the next round, until one player is left.
+    Knockout,
+}
+
+/// How to run a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentConfig {
+    /// The pairing format.
+    pub format: TournamentFormat,
+    /// The most players at a table; players are spread over as few tables as possible, as evenly as possible.
+    ///
+    /// Round-robins are always played head-to-head, so this must be 2 for them.
+    pub table_size: usize,
+    /// How to order entrants with the same match points, in order of priority.
+    ///
+    /// Any ties left after these go to the earlier entrant.
+    pub tie_breaks: Vec<TieBreak>,
+}
+
+impl Default for TournamentConfig {
+    fn default() -> Self {
+        Self {
+            format: TournamentFormat::Swiss { rounds: 3 },
+            table_size: 2,
+            tie_breaks: vec![
+                TieBreak::OpponentMatchPoints,
+                TieBreak::RoundsWon,
+                TieBreak::FewestPoints,
+            ],
+        }
+    }
+}
+
+/// A single round of a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TournamentRound {
+    /// The tables played at this round.
+    pub tables: Vec<Table>,
+    /// The entrants sitting this round out, who score as if they'd won.
+    pub byes: Vec<usize>,
+}
+
+impl TournamentRound {
+    /// Whether every table has a result.
+    pub fn is_complete(&self) -> bool {
+        self.tables.iter().all(|table| table.result.is_some())
+    }
+}
+
+/// A table in a tournament round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Table {
+    /// The entrant IDs at the table, in seat order; these should be the player IDs of its game.
+    ///
+    /// The first seat rotates between tournament rounds, going to whoever's had it the fewest times.
+    pub players: Vec<usize>,
+    /// The result of the table's game, once it's recorded.
+    pub result: Option<TableResult>,
+}
+
+impl Table {
+    /// Get the players from first to last place, if the result's recorded.
+    ///
+    /// See `TableResult::placings` for how they're ranked.
+    pub fn placings(&self) -> Option<Vec<usize>> {
+        self.result
+            .as_ref()
+            .map(|result| result.placings(&self.players))
+    }
+}
+
+/// A tournament between many entrants, played over many tables.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Tournament {
+    config: TournamentConfig,
+    entrants: Vec<String>,
+    rounds: Vec<TournamentRound>,
+}
+
+impl Tournament {
+    /// Create a tournament, with each entrant's ID being their index in `entrants`.
+    ///
+    /// Entrants are seeded in the order given, for the first round's seating and for any ties left in the standings.
+    pub fn new(config: TournamentConfig, entrants: Vec<String>) -> Result<Self, TournamentError> {
+        if entrants.len() < 2 {
+            return Err(TournamentError::NotEnoughEntrants(entrants.len()));
+        }
+        if config.table_size < 2 {
+            return Err(TournamentError::InvalidTableSize(config.table_size));
+        }
+        if config.format == TournamentFormat::RoundRobin && config.table_size != 2 {
+            return Err(TournamentError::RoundRobinTableSize(config.table_size));
+        }
+        Ok(Self {
+            config,
+            entrants,
+            rounds: Vec::new(),
+        })
+    }
+
+    /// Get the tournament's config.
+    pub fn config(&self) -> &TournamentConfig {
+        &self.config
+    }
+
+    /// Get the entrants' names, indexed by their ID.
+    pub fn entrants(&self) -> &[String] {
+        &self.entrants
+    }
+
+    /// Get every round started so far.
+    pub fn rounds(&self) -> &[TournamentRound] {
+        &self.rounds
+    }
+
+    /// Get the current round, if it's still missing some results.
+    pub fn current_round(&self) -> Option<&TournamentRound> {
+        self.rounds.last().filter(|round| !round.is_complete())
+    }
+
+    /// Whether every round has been played.
+    pub fn is_finished(&self) -> bool {
+        if self.current_round().is_some() {
+            return false;
+        }
+        match self.config.format {
+            TournamentFormat::Swiss { rounds } => self.rounds.len() >= rounds,
+            TournamentFormat::RoundRobin => {
+                self.rounds.len() >= pairing::round_robin_rounds(self.entrants.len())
+            }
+            TournamentFormat::Knockout => self.remaining().len() <= 1,
+        }
+    }
+
+    /// Get the IDs of the entrants still in the tournament.
+    ///
+    /// This is everyone, except in a knockout, where it's the players who went through in the last complete round.
+    pub fn remaining(&self) -> Vec<usize> {
+        let last_round = match self.config.format {
+            TournamentFormat::Knockout => self.rounds.iter().rev().find(|r| r.is_complete()),
+            _ => None,
+        };
+        let Some(round) = last_round else {
+            return (0..self.entrants.len()).collect();
+        };
+        let mut remaining = round.byes.clone();
+        remaining.extend(
+            round
+                .tables
+                .iter()
+                .filter_map(|table| table.placings().map(|placings| placings[0])),
+        );
+        remaining.sort();
+        remaining
+    }
+
+    /// Seat the players for the next round.
+    ///
+    /// Returns an `Err` if the current round isn't complete yet, or the tournament is finished.
+    pub fn start_round(&mut self) -> Result<&TournamentRound, TournamentError> {
+        if self.current_round().is_some() {
+            return Err(TournamentError::RoundInProgress);
+        }
+        if self.is_finished() {
+            return Err(TournamentError::Finished);
+        }
+
+        let table_size = self.config.table_size;
+        let (mut tables, byes) = match self.config.format {
+            TournamentFormat::Swiss { .. } => {
+                let ranked: Vec<_> = self.standings().iter().map(|s| s.entrant_id).collect();
+                let had_bye: Vec<_> = self.rounds.iter().flat_map(|r| r.byes.clone()).collect();
+                pairing::swiss(&ranked, table_size, &self.previous_opponents(), &had_bye)
+            }
+            TournamentFormat::RoundRobin => {
+                pairing::round_robin(self.entrants.len(), self.rounds.len())
+            }
+            TournamentFormat::Knockout => {
+                let remaining = self.remaining();
+                let seeded: Vec<_> = self
+                    .standings()
+                    .iter()
+                    .map(|s| s.entrant_id)
+                    .filter(|id| remaining.contains(id))
+                    .collect();
+                pairing::knockout(&seeded, table_size)
+            }
+        };
+
+        let mut first_seats = vec![0; self.entrants.len()];
+        for table in self.rounds.iter().flat_map(|r| &r.tables) {
+            first_seats[table.players[0]] += 1;
+        }
+        for table in &mut tables {
+            pairing::rotate_seats(table, &first_seats);
+            first_seats[table[0]] += 1;
+        }
+
+        self.rounds.push(TournamentRound {
+            tables: tables
+                .into_iter()
+                .map(|players| Table {
+                    players,
+                    result: None,
+                })
+                .collect(),
+            byes,
+        });
+        // UNWRAP: we just pushed a round
+        Ok(self.rounds.last().unwrap())
+    }
+
+    /// Record the result of a table in the current round.
+    ///
+    /// Returns an `Err` if there's no such table, it already has a result, the result has no rounds,
+    /// or the result has players who weren't at the table.
+    pub fn record_result(
+        &mut self,
+        table_index: usize,
+        result: TableResult,
+    ) -> Result<(), TournamentError> {
+        let round = self
+            .rounds
+            .last_mut()
+            .filter(|round| !round.is_complete())
+            .ok_or(TournamentError::NoRoundInProgress)?;
+        let table = round
+            .tables
+            .get_mut(table_index)
+            .ok_or(TournamentError::TableDoesntExist(table_index))?;
+        if table.result.is_some() {
+            return Err(TournamentError::AlreadyRecorded(table_index));
+        }
+        if result.rounds.is_empty() {
+            return Err(TournamentError::EmptyResult);
+        }
+        let players_match = result.rounds.iter().all(|round| {
+            round.points.contains_key(&round.winner_id)
+                && round.points.keys().all(|id| table.players.contains(id))
+        });
+        if !players_match {
+            return Err(TournamentError::WrongPlayers(table_index));
+        }
+        table.result = Some(result);
+        Ok(())
+    }
+
+    /// Get the standings from the results so far, from first to last place.
+    ///
+    /// Entrants are ordered by match points, then by the config's tie-breaks. In a knockout,
+    /// entrants who went further always rank ahead of those knocked out before them.
+    pub fn standings(&self) -> Vec<Standing> {
+        let mut standings = standings::tally(self.entrants.len(), &self.rounds);
+        standings::sort(&mut standings, &self.config.tie_breaks);
+        if self.config.format == TournamentFormat::Knockout {
+            let remaining = self.remaining();
+            standings.sort_by_key(|s| {
+                Reverse(s.rounds_played + usize::from(remaining.contains(&s.entrant_id)))
+            });
+        }
+        standings
+    }
+
+    /// Get the ID of the tournament's winner, once it's finished.
+    pub fn winner(&self) -> Option<usize> {
+        match self.is_finished() {
+            true => self.standings().first().map(|s| s.entrant_id),
+            false => None,
+        }
+    }
+
+    /// Get every pair of entrants who've been at a table together, in both orders.
+    fn previous_opponents(&self) -> HashSet<(usize, usize)> {
+        self.rounds
+            .iter()
+            .flat_map(|round| &round.tables)
+            .flat_map(|table| {
+                table.players.iter().flat_map(|&a| {
+                    table
+                        .players
+                        .iter()
+                        .filter(move |&&b| a != b)
+                        .map(move |&b| (a, b))
+                })
+            })
+            .collect()
+    }
+}
+
+/// Errors from setting up or running a tournament.
+#[derive(Debug, Clone, Error)]
+pub enum TournamentError {
+    #[error("A tournament needs at least 2 entrants, but there are {0}")]
+    NotEnoughEntrants(usize),
+    #[error("A table must seat at least 2 players, but the table size is {0}")]
+    InvalidTableSize(usize),
+    #[error("Round-robins are played head-to-head, so the table size must be 2, not {0}")]
+    RoundRobinTableSize(usize),
+    #[error("The current round still has tables without a result")]
+    RoundInProgress,
+    #[error("There's no round in progress")]
+    NoRoundInProgress,
+    #[error("The tournament is already finished")]
+    Finished,
+    #[error("Table {0} doesn't exist in the current round")]
+    TableDoesntExist(usize),
+    #[error("Table {0} already has a result")]
+    AlreadyRecorded(usize),
+    #[error("The result has no rounds")]
+    EmptyResult,
+    #[error("The result has players who weren't at table {0}")]
+    WrongPlayers(usize),
+}
@@ -0,0 +1,182 @@
+//! Contains the pairing algorithms for each `TournamentFormat`.
+//!
+//! Each returns the tables (as the entrant IDs at each) and the byes for the next round.
+
+use std::collections::HashSet;
+
+/// Split `player_count` players into as few tables of at most `table_size` as possible, keeping them as even as possible.
+fn table_sizes(player_count: usize, table_size: usize) -> Vec<usize> {
+    let table_count = player_count.div_ceil(table_size);
+    (0..table_count)
+        .map(|i| player_count / table_count + usize::from(i < player_count % table_count))
+        .collect()
+}
+
+/// The most seats to try filling when avoiding rematches, before falling back to seating greedily.
+const MAX_SEATS_TRIED: usize = 100_000;
+
+/// Seat players with similar standings together (Monrad-style), avoiding rematches where possible.
+///
+/// `ranked` is the players from first to last in the standings, `met` has every pair of players who've already
+/// played each other, and `had_bye` has everyone who's already sat a round out.
+pub(super) fn swiss(
+    ranked: &[usize],
+    table_size: usize,
+    met: &HashSet<(usize, usize)>,
+    had_bye: &[usize],
+) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut remaining = ranked.to_vec();
+    let mut byes = Vec::new();
+    if table_size == 2 && remaining.len() % 2 == 1 {
+        // the lowest ranked player who hasn't had a bye yet sits out
+        let index = remaining
+            .iter()
+            .rposition(|id| !had_bye.contains(id))
+            .unwrap_or(remaining.len() - 1);
+        byes.push(remaining.remove(index));
+    }
+
+    let sizes = table_sizes(remaining.len(), table_size);
+    let mut seating = Seating {
+        met,
+        seats_left: MAX_SEATS_TRIED,
+    };
+    let tables = seating
+        .seat(&remaining, &sizes)
+        .unwrap_or_else(|| seat_greedily(remaining, &sizes, met));
+    (tables, byes)
+}
+
+/// A search for a Swiss seating without any rematches.
+struct Seating<'a> {
+    met: &'a HashSet<(usize, usize)>,
+    seats_left: usize,
+}
+
+impl Seating<'_> {
+    /// Seat `players` (from highest to lowest ranked) at tables of `sizes`, with nobody meeting anyone again.
+    ///
+    /// Players are seated with those ranked closest to them where possible. Returns `None` if it's not possible,
+    /// or it took too long to find out.
+    fn seat(&mut self, players: &[usize], sizes: &[usize]) -> Option<Vec<Vec<usize>>> {
+        let Some((&size, other_sizes)) = sizes.split_first() else {
+            return Some(Vec::new());
+        };
+        let mut table = vec![players[0]];
+        self.fill(&mut table, &players[1..], 0, size, other_sizes)
+    }
+
+    /// Fill `table` up to `size` with players from `others[from..]`, then seat everyone else at the other tables.
+    fn fill(
+        &mut self,
+        table: &mut Vec<usize>,
+        others: &[usize],
+        from: usize,
+        size: usize,
+        other_sizes: &[usize],
+    ) -> Option<Vec<Vec<usize>>> {
+        if table.len() == size {
+            let unseated: Vec<_> = others
+                .iter()
+                .filter(|id| !table.contains(id))
+                .copied()
+                .collect();
+            let mut tables = self.seat(&unseated, other_sizes)?;
+            tables.insert(0, table.clone());
+            return Some(tables);
+        }
+        for (i, &id) in others.iter().enumerate().skip(from) {
+            self.seats_left = self.seats_left.checked_sub(1)?;
+            if table.iter().any(|&seated| self.met.contains(&(id, seated))) {
+                continue;
+            }
+            table.push(id);
+            if let Some(tables) = self.fill(table, others, i + 1, size, other_sizes) {
+                return Some(tables);
+            }
+            table.pop();
+        }
+        None
+    }
+}
+
+/// Seat `players` (from highest to lowest ranked) at tables of `sizes`, taking the highest ranked player
+/// who hasn't met anyone at the table yet for each seat, or the highest ranked player if everyone has.
+fn seat_greedily(
+    mut players: Vec<usize>,
+    sizes: &[usize],
+    met: &HashSet<(usize, usize)>,
+) -> Vec<Vec<usize>> {
+    sizes
+        .iter()
+        .map(|&size| {
+            let mut table = vec![players.remove(0)];
+            while table.len() < size {
+                let index = players
+                    .iter()
+                    .position(|id| table.iter().all(|seated| !met.contains(&(*id, *seated))))
+                    .unwrap_or(0);
+                table.push(players.remove(index));
+            }
+            table
+        })
+        .collect()
+}
+
+/// The number of rounds for everyone to have played everyone else once.
+pub(super) fn round_robin_rounds(entrant_count: usize) -> usize {
+    entrant_count + entrant_count % 2 - 1
+}
+
+/// Pair the players head-to-head for `round` of a round-robin, using the circle method.
+pub(super) fn round_robin(entrant_count: usize, round: usize) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut circle: Vec<_> = (0..entrant_count).map(Some).collect();
+    if entrant_count % 2 == 1 {
+        circle.push(None);
+    }
+    let len = circle.len();
+    circle[1..].rotate_right(round % (len - 1));
+
+    let mut tables = Vec::new();
+    let mut byes = Vec::new();
+    for i in 0..len / 2 {
+        match (circle[i], circle[len - 1 - i]) {
+            (Some(a), Some(b)) => tables.push(vec![a, b]),
+            (Some(id), None) | (None, Some(id)) => byes.push(id),
+            (None, None) => {}
+        }
+    }
+    (tables, byes)
+}
+
+/// Seat the players still in a knockout, spreading the seeds out so the best players meet as late as possible.
+///
+/// `seeded` is the players from best to worst seed; if a player has to sit out, it's the best seed.
+pub(super) fn knockout(seeded: &[usize], table_size: usize) -> (Vec<Vec<usize>>, Vec<usize>) {
+    let mut players = seeded.to_vec();
+    let mut byes = Vec::new();
+    if table_size == 2 && players.len() % 2 == 1 {
+        byes.push(players.remove(0));
+    }
+
+    let table_count = players.len().div_ceil(table_size);
+    let mut tables = vec![Vec::new(); table_count];
+    for (i, id) in players.into_iter().enumerate() {
+        // deal the players out to the tables in a snake, ie 0, 1, 2, 2, 1, 0, 0, 1...
+        let position = i % table_count;
+        match (i / table_count) % 2 {
+            0 => tables[position].push(id),
+            _ => tables[table_count - 1 - position].push(id),
+        }
+    }
+    (tables, byes)
+}
+
+/// Rotate a table's seats so that the player who's had the first seat the fewest times gets it this time.
+///
+/// `first_seats` has the number of times each entrant has had the first seat.
+pub(super) fn rotate_seats(table: &mut [usize], first_seats: &[usize]) {
+    if let Some(first) = (0..table.len()).min_by_key(|&i| first_seats[table[i]]) {
+        table.rotate_left(first);
+    }
+}
@@ -0,0 +1,129 @@
+//! Contains the `TableResult`, which is the outcome of a game played at a tournament table.
+
+use crate::game::{
+    r#trait::Game,
+    score::{Points, RoundScore, ScoreOf, VariantPlayerScore},
+};
+use std::{cmp::Reverse, collections::HashMap};
+
+/// The outcome of a game played at a table, as the points of each of its rounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TableResult {
+    /// The game's rounds, in the order they were played.
+    pub rounds: Vec<ScoredRound>,
+}
+
+/// A single round of a game, scored in points.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoredRound {
+    /// Map of player IDs to their points (fewer is better).
+    pub points: HashMap<usize, u32>,
+    /// The ID of the player who won.
+    pub winner_id: usize,
+}
+
+impl<P: VariantPlayerScore + Points> From<&RoundScore<P>> for ScoredRound {
+    fn from(score: &RoundScore<P>) -> Self {
+        Self {
+            points: score
+                .player_scores
+                .iter()
+                .map(|(&id, score)| (id, score.points()))
+                .collect(),
+            winner_id: score.winner_id,
+        }
+    }
+}
+
+impl TableResult {
+    /// Get the result of a game from its completed rounds.
+    pub fn from_game<G: Game>(game: &G) -> Self
+    where
+        ScoreOf<G>: Points,
+    {
+        let round_scores = game.get_state().round_scores();
+        let mut rounds: Vec<_> = round_scores.keys().copied().collect();
+        rounds.sort();
+        Self {
+            rounds: rounds
+                .iter()
+                .map(|round| ScoredRound::from(&round_scores[round]))
+                .collect(),
+        }
+    }
+
+    /// Get the number of rounds won by a player.
+    pub fn rounds_won(&self, player_id: usize) -> usize {
+        self.rounds
+            .iter()
+            .filter(|round| round.winner_id == player_id)
+            .count()
+    }
+
+    /// Get a player's total points over all rounds.
+    pub fn points(&self, player_id: usize) -> u32 {
+        self.rounds
+            .iter()
+            .filter_map(|round| round.points.get(&player_id))
+            .sum()
+    }
+
+    /// Get the number of rounds a player has no score for (eg, because they quit).
+    pub fn rounds_missed(&self, player_id: usize) -> usize {
+        self.rounds
+            .iter()
+            .filter(|round| !round.points.contains_key(&player_id))
+            .count()
+    }
+
+    /// Order `players` from first to last place.
+    ///
+    /// Players are ranked by the fewest rounds missed, then the fewest points, then the most rounds won;
+    /// any remaining ties keep the order of `players`.
+    pub fn placings(&self, players: &[usize]) -> Vec<usize> {
+        let mut placings = players.to_vec();
+        placings.sort_by_key(|&id| self.rank_key(id));
+        placings
+    }
+
+    /// Group everyone in the result by their place, from first to last; players in the same group tied.
+    ///
+    /// Players are ranked as in `placings`.
+    pub fn finish(&self) -> Vec<Vec<usize>> {
+        let mut players: Vec<_> = self
+            .rounds
+            .iter()
+            .flat_map(|round| round.points.keys().copied())
+            .collect();
+        players.sort();
+        players.dedup();
+
+        let mut finish: Vec<Vec<usize>> = Vec::new();
+        for id in self.placings(&players) {
+            match finish.last_mut() {
+                Some(place) if self.rank_key(place[0]) == self.rank_key(id) => place.push(id),
+                _ => finish.push(vec![id]),
+            }
+        }
+        finish
+    }
+
+    /// Get the number of `players` that a player finished strictly ahead of.
+    pub fn players_beaten(&self, player_id: usize, players: &[usize]) -> usize {
+        let key = self.rank_key(player_id);
+        players
+            .iter()
+            .filter(|&&other| self.rank_key(other) > key)
+            .count()
+    }
+
+    fn rank_key(&self, player_id: usize) -> (usize, u32, Reverse<usize>) {
+        (
+            self.rounds_missed(player_id),
+            self.points(player_id),
+            Reverse(self.rounds_won(player_id)),
+        )
+    }
+}
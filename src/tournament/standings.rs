@@ -0,0 +1,106 @@
+//! Contains each entrant's `Standing` in a tournament, and the `TieBreak`s used to order them.
+
+use super::TournamentRound;
+use std::cmp::Ordering;
+
+/// An entrant's results so far in a tournament.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Standing {
+    /// The entrant's ID.
+    pub entrant_id: usize,
+    /// A point for each player finished ahead of at a table, and a point for each bye.
+    pub match_points: u32,
+    /// The number of game rounds won, over all tables.
+    pub rounds_won: usize,
+    /// The total points, over all tables (fewer is better).
+    pub points: u32,
+    /// The total match points of every opponent faced (ie, the Buchholz score).
+    pub opponent_match_points: u32,
+    /// The number of tournament rounds with a result, including byes.
+    pub rounds_played: usize,
+    /// The number of byes.
+    pub byes: usize,
+}
+
+/// A way to order entrants with the same match points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TieBreak {
+    /// The most game rounds won.
+    RoundsWon,
+    /// The fewest points.
+    FewestPoints,
+    /// The most match points among opponents.
+    OpponentMatchPoints,
+}
+
+impl TieBreak {
+    /// Compare two standings, where `Less` means `a` ranks ahead of `b`.
+    fn compare(&self, a: &Standing, b: &Standing) -> Ordering {
+        match self {
+            TieBreak::RoundsWon => b.rounds_won.cmp(&a.rounds_won),
+            TieBreak::FewestPoints => a.points.cmp(&b.points),
+            TieBreak::OpponentMatchPoints => b.opponent_match_points.cmp(&a.opponent_match_points),
+        }
+    }
+}
+
+/// Tally every entrant's standing from the recorded results, in order of entrant ID.
+pub(super) fn tally(entrant_count: usize, rounds: &[TournamentRound]) -> Vec<Standing> {
+    let mut standings: Vec<_> = (0..entrant_count)
+        .map(|entrant_id| Standing {
+            entrant_id,
+            match_points: 0,
+            rounds_won: 0,
+            points: 0,
+            opponent_match_points: 0,
+            rounds_played: 0,
+            byes: 0,
+        })
+        .collect();
+    let mut opponents: Vec<Vec<usize>> = vec![Vec::new(); entrant_count];
+
+    for round in rounds {
+        for &id in &round.byes {
+            standings[id].match_points += 1;
+            standings[id].rounds_played += 1;
+            standings[id].byes += 1;
+        }
+        for table in &round.tables {
+            let Some(result) = &table.result else {
+                continue;
+            };
+            for &id in &table.players {
+                let standing = &mut standings[id];
+                standing.match_points += result.players_beaten(id, &table.players) as u32;
+                standing.rounds_won += result.rounds_won(id);
+                standing.points += result.points(id);
+                standing.rounds_played += 1;
+                opponents[id].extend(table.players.iter().filter(|&&other| other != id));
+            }
+        }
+    }
+
+    let match_points: Vec<_> = standings.iter().map(|s| s.match_points).collect();
+    for (standing, opponents) in standings.iter_mut().zip(opponents) {
+        standing.opponent_match_points = opponents.iter().map(|&id| match_points[id]).sum();
+    }
+    standings
+}
+
+/// Sort standings by match points, then by each tie-break in turn, then by entrant ID.
+pub(super) fn sort(standings: &mut [Standing], tie_breaks: &[TieBreak]) {
+    standings.sort_by(|a, b| {
+        b.match_points
+            .cmp(&a.match_points)
+            .then_with(|| {
+                tie_breaks
+                    .iter()
+                    .map(|tie_break| tie_break.compare(a, b))
+                    .find(|ordering| ordering.is_ne())
+                    .unwrap_or(Ordering::Equal)
+            })
+            .then(a.entrant_id.cmp(&b.entrant_id))
+    });
+}
@@ -5,12 +5,13 @@ use crate::{
         error::{ActionError, GameError, GameSetupError},
         r#trait::Game,
         rules::GameRules,
-        state::GameState,
+        state::{GamePhase, GameState},
         variants::basic::{config::BasicConfig, game::BasicRummyGame},
     },
 };
 use chrono::{DateTime, Utc};
 use std::collections::HashMap;
+use thiserror::Error;
 
 /// An entry in the game's history.
 #[derive(Clone, Debug)]
@@ -19,24 +20,80 @@ pub struct HistoryEntry {
     pub entry: GameInteractions,
     pub time: DateTime<Utc>,
     pub successful: bool,
+    /// The ID of the player who made the interaction, if known.
+    ///
+    /// For actions, this is the acting player (who may not be the current player, for out-of-turn actions).
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub actor: Option<usize>,
+    /// Whether the interaction was taken back with `History::undo`.
+    ///
+    /// Undone interactions are kept in the history, but no longer have any effect on the game.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub undone: bool,
+}
+
+impl HistoryEntry {
+    /// Whether the interaction has an effect on the game, ie it was successful and hasn't been undone.
+    pub fn took_effect(&self) -> bool {
+        self.successful && !self.undone
+    }
 }
 
 /// This wrapper tracks every interaction with the game,
 /// as well as the initial game state at the start of each round.
 ///
 /// This means one can construct the state of the game at each step.
+///
+/// It can also let the current player take back moves made during their turn (see `History::undo`),
+/// which is opt-in (see `History::set_undo_enabled`).
 #[derive(Clone, Debug)]
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct History<G: Game + Clone> {
     /// The current game.
-    current_game: G,
+    pub(crate) current_game: G,
     /// The map of round numbers to initial round states.
     initial_round_states: HashMap<usize, G>,
     /// The map of round numbers to its histories.
     round_histories: HashMap<usize, Vec<HistoryEntry>>,
+    /// Whether `undo()` and `redo()` are allowed.
+    #[cfg_attr(feature = "serde", serde(default))]
+    undo_enabled: bool,
+    /// The undone entries which can be redone, the most recently undone last.
+    #[cfg_attr(feature = "serde", serde(default))]
+    redo_stack: Vec<HistoryEntry>,
 }
 
 impl<G: Game + Clone> History<G> {
+    /// Start recording the history of `game`.
+    ///
+    /// `game` should be freshly created (ie, still in round 0), so that the history is complete.
+    pub fn from_new_game(game: G) -> Self {
+        let mut initial_round_states = HashMap::new();
+        initial_round_states.insert(0, game.clone());
+
+        let mut round_histories = HashMap::new();
+        round_histories.insert(0, vec![]);
+
+        Self::from_parts(game, initial_round_states, round_histories)
+    }
+
+    /// Create a history directly from its parts.
+    ///
+    /// `initial_round_states` and `round_histories` must both contain round 0, and be consistent with `current_game`.
+    pub(crate) fn from_parts(
+        current_game: G,
+        initial_round_states: HashMap<usize, G>,
+        round_histories: HashMap<usize, Vec<HistoryEntry>>,
+    ) -> Self {
+        Self {
+            current_game,
+            initial_round_states,
+            round_histories,
+            undo_enabled: false,
+            redo_stack: Vec::new(),
+        }
+    }
+
     /// Get a reference to the game.
     pub fn get_game(&self) -> &G {
         &self.current_game
@@ -52,6 +109,131 @@ impl<G: Game + Clone> History<G> {
         &self.round_histories
     }
 
+    /// Whether every round was shuffled with the seed derived from the deck config's `shuffle_seed`
+    /// (ie, the deck is seeded and no round used a committed shuffle),
+    /// so that the game can be rebuilt from just its initial state and interactions.
+    pub(crate) fn is_reproducible_from_seed(&self) -> bool {
+        self.initial_round_states.iter().all(|(&round, game)| {
+            let deck = game.get_state().deck();
+            deck.config().shuffle_seed.is_some() && deck.seed() == deck.config().round_seed(round)
+        })
+    }
+
+    /// Whether `undo()` and `redo()` are allowed. This is off by default.
+    pub fn undo_enabled(&self) -> bool {
+        self.undo_enabled
+    }
+
+    /// Allow or disallow `undo()` and `redo()`, eg for casual or tutorial games.
+    pub fn set_undo_enabled(&mut self, enabled: bool) {
+        self.undo_enabled = enabled;
+        if !enabled {
+            self.redo_stack.clear();
+        }
+    }
+
+    /// Take back the last interaction of the current player's turn.
+    ///
+    /// Forming melds, laying off, taking from the discard pile and rearranging one's hand can be undone,
+    /// one at a time (most recent first). Anything else locks in everything before it;
+    /// notably discarding (which ends the turn) and drawing from the deck (which reveals a hidden card),
+    /// as well as anything done by other players.
+    ///
+    /// The undone entry stays in the history, marked as `undone`.
+    /// Returns an `Err` if undo isn't enabled, the game is paused, or the last interaction can't be undone.
+    pub fn undo(&mut self) -> Result<(), UndoError> {
+        if !self.undo_enabled {
+            return Err(UndoError::Disabled);
+        }
+        let state = self.current_game.get_state();
+        if state.is_paused() {
+            return Err(UndoError::GamePaused);
+        }
+        if !matches!(state.phase(), GamePhase::Draw | GamePhase::Play) {
+            return Err(UndoError::NothingToUndo);
+        }
+        let current_player_id = state.get_current_player().ok().map(|p| p.id());
+
+        let history = self.get_current_round_history();
+        let entry = history
+            .iter_mut()
+            .rev()
+            .find(|entry| entry.took_effect())
+            .ok_or(UndoError::NothingToUndo)?;
+        if !can_undo(entry, current_player_id) {
+            return Err(UndoError::NothingToUndo);
+        }
+        entry.undone = true;
+        let entry = entry.clone();
+        self.redo_stack.push(entry);
+        self.rebuild_current_round();
+        Ok(())
+    }
+
+    /// Redo the most recently undone interaction.
+    ///
+    /// It's recorded again as a new entry, leaving the undone one as it was.
+    /// Only possible until anything else is done with the game, and not while it's paused.
+    pub fn redo(&mut self) -> Result<(), UndoError> {
+        if !self.undo_enabled {
+            return Err(UndoError::Disabled);
+        }
+        if self.current_game.get_state().is_paused() {
+            return Err(UndoError::GamePaused);
+        }
+        let undone = self.redo_stack.pop().ok_or(UndoError::NothingToRedo)?;
+        let successful =
+            apply_interaction(&mut self.current_game, undone.entry.clone(), undone.actor);
+        self.get_current_round_history().push(HistoryEntry {
+            entry: undone.entry,
+            time: Utc::now(),
+            successful,
+            actor: undone.actor,
+            undone: false,
+        });
+        Ok(())
+    }
+
+    /// Whether there's an undone interaction which `redo()` would redo.
+    pub fn can_redo(&self) -> bool {
+        self.undo_enabled && !self.redo_stack.is_empty()
+    }
+
+    /// Record an interaction which was just made, which also means nothing can be redone anymore.
+    fn record(&mut self, entry: GameInteractions, successful: bool, actor: Option<usize>) {
+        self.redo_stack.clear();
+        self.get_current_round_history().push(HistoryEntry {
+            entry,
+            time: Utc::now(),
+            successful,
+            actor,
+            undone: false,
+        });
+    }
+
+    /// Record an interaction which has already been undone, without applying it.
+    pub(crate) fn record_undone(&mut self, entry: GameInteractions, actor: Option<usize>) {
+        self.get_current_round_history().push(HistoryEntry {
+            entry,
+            time: Utc::now(),
+            successful: true,
+            actor,
+            undone: true,
+        });
+    }
+
+    /// Rebuild the current game from the start of the round, applying only the interactions which took effect.
+    fn rebuild_current_round(&mut self) {
+        let round = self.current_game.get_state().current_round;
+        let mut game = self.initial_round_states[&round].clone();
+        for entry in self.round_histories[&round].iter() {
+            if entry.took_effect() {
+                apply_interaction(&mut game, entry.entry.clone(), entry.actor);
+            }
+        }
+        self.current_game = game;
+    }
+
     /// Get a mutable ref to the current round's history.
     ///
     /// ### Panics
@@ -77,18 +259,7 @@ impl History<BasicRummyGame> {
         deck_config: DeckConfig,
     ) -> Result<Self, GameSetupError> {
         let game = BasicRummyGame::new(player_ids, config, deck_config)?;
-
-        let mut initial_round_states = HashMap::new();
-        initial_round_states.insert(0, game.clone());
-
-        let mut round_histories = HashMap::new();
-        round_histories.insert(0, vec![]);
-
-        Ok(Self {
-            current_game: game,
-            initial_round_states,
-            round_histories,
-        })
+        Ok(Self::from_new_game(game))
     }
 }
 
@@ -96,13 +267,30 @@ impl<G: Game + Clone> Game for History<G> {
     type Rules = G::Rules;
 
     fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError> {
+        let actor = self
+            .current_game
+            .get_state()
+            .get_current_player()
+            .ok()
+            .map(|p| p.id());
         let result = self.current_game.execute_action(action.clone());
-        let entry = HistoryEntry {
-            entry: GameInteractions::Action(action),
-            time: Utc::now(),
-            successful: result.is_ok(),
-        };
-        self.get_current_round_history().push(entry);
+        self.record(GameInteractions::Action(action), result.is_ok(), actor);
+        result
+    }
+
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        let result = self
+            .current_game
+            .execute_out_of_turn_action(player_id, action.clone());
+        self.record(
+            GameInteractions::Action(action),
+            result.is_ok(),
+            Some(player_id),
+        );
         result
     }
 
@@ -114,23 +302,34 @@ impl<G: Game + Clone> Game for History<G> {
 
     fn quit_player(&mut self, player_id: usize) -> Result<(), GameError> {
         let result = self.current_game.quit_player(player_id);
-        let entry = HistoryEntry {
-            entry: GameInteractions::PlayerQuit { player_id },
-            time: Utc::now(),
-            successful: result.is_ok(),
-        };
-        self.get_current_round_history().push(entry);
+        self.record(
+            GameInteractions::PlayerQuit { player_id },
+            result.is_ok(),
+            Some(player_id),
+        );
+        result
+    }
+
+    fn replace_player(&mut self, player_id: usize, new_player_id: usize) -> Result<(), GameError> {
+        let result = self.current_game.replace_player(player_id, new_player_id);
+        self.record(
+            GameInteractions::PlayerReplaced {
+                player_id,
+                new_player_id,
+            },
+            result.is_ok(),
+            Some(new_player_id),
+        );
         result
     }
 
     fn add_player(&mut self, player_id: usize) -> Result<(), GameError> {
         let result = self.current_game.add_player(player_id);
-        let entry = HistoryEntry {
-            entry: GameInteractions::PlayerJoin { player_id },
-            time: Utc::now(),
-            successful: result.is_ok(),
-        };
-        self.get_current_round_history().push(entry);
+        self.record(
+            GameInteractions::PlayerJoin { player_id },
+            result.is_ok(),
+            Some(player_id),
+        );
         result
     }
 
@@ -142,15 +341,26 @@ impl<G: Game + Clone> Game for History<G> {
         let result = self
             .current_game
             .rearrange_player_hand(player_id, new_arrangement.clone());
-        let entry = HistoryEntry {
-            entry: GameInteractions::HandRearrangement {
+        self.record(
+            GameInteractions::HandRearrangement {
                 player_id,
                 new_arrangement,
             },
-            time: Utc::now(),
-            successful: result.is_ok(),
-        };
-        self.get_current_round_history().push(entry);
+            result.is_ok(),
+            Some(player_id),
+        );
+        result
+    }
+
+    fn pause(&mut self) -> Result<(), GameError> {
+        let result = self.current_game.pause();
+        self.record(GameInteractions::Pause, result.is_ok(), None);
+        result
+    }
+
+    fn resume(&mut self) -> Result<(), GameError> {
+        let result = self.current_game.resume();
+        self.record(GameInteractions::Resume, result.is_ok(), None);
         result
     }
 
@@ -159,6 +369,7 @@ impl<G: Game + Clone> Game for History<G> {
 
         // note: we don't store failed `next_round()` calls because it doesn't really matter to anyone
         if result.is_ok() {
+            self.redo_stack.clear();
             let new_history = Vec::new();
             let round = self.current_game.get_state().current_round;
             self.round_histories.insert(round, new_history);
@@ -169,3 +380,65 @@ impl<G: Game + Clone> Game for History<G> {
         result
     }
 }
+
+/// Whether `entry` is a move made during the turn of the current player (with ID `current_player_id`)
+/// which can be undone.
+fn can_undo(entry: &HistoryEntry, current_player_id: Option<usize>) -> bool {
+    let undoable = matches!(
+        entry.entry,
+        GameInteractions::Action(
+            GameAction::FormMeld(_)
+                | GameAction::FormMelds(_)
+                | GameAction::LayOff(_)
+                | GameAction::DrawDiscardPile(_)
+        ) | GameInteractions::HandRearrangement { .. }
+    );
+    undoable && entry.actor.is_some() && entry.actor == current_player_id
+}
+
+/// Errors from undoing or redoing an interaction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Error)]
+pub enum UndoError {
+    #[error("Undo isn't enabled for this game")]
+    Disabled,
+    #[error("There's nothing in the current turn that can be undone")]
+    NothingToUndo,
+    #[error("There's nothing to redo")]
+    NothingToRedo,
+    #[error("The game is paused")]
+    GamePaused,
+}
+
+/// Apply an interaction made by `actor` to `game`, returning whether it executed successfully.
+pub(crate) fn apply_interaction<G: Game>(
+    game: &mut G,
+    interaction: GameInteractions,
+    actor: Option<usize>,
+) -> bool {
+    match (interaction, actor) {
+        (GameInteractions::Action(action), Some(actor)) => {
+            game.execute_action_as(actor, action).is_ok()
+        }
+        (GameInteractions::Action(action), None) => game.execute_action(action).is_ok(),
+        (GameInteractions::PlayerJoin { player_id }, _) => game.add_player(player_id).is_ok(),
+        (GameInteractions::PlayerQuit { player_id }, _) => game.quit_player(player_id).is_ok(),
+        (
+            GameInteractions::PlayerReplaced {
+                player_id,
+                new_player_id,
+            },
+            _,
+        ) => game.replace_player(player_id, new_player_id).is_ok(),
+        (
+            GameInteractions::HandRearrangement {
+                player_id,
+                new_arrangement,
+            },
+            _,
+        ) => game
+            .rearrange_player_hand(player_id, new_arrangement)
+            .is_ok(),
+        (GameInteractions::Pause, _) => game.pause().is_ok(),
+        (GameInteractions::Resume, _) => game.resume().is_ok(),
+    }
+}
@@ -1,4 +1,7 @@
 //! Contains "wrappers", which wrap a game and provide some additional functionality.
 
 pub mod history;
+pub mod observed;
+pub mod record;
 pub mod replay;
+pub mod timed;
@@ -0,0 +1,419 @@
+//! Contains `Observed`, a wrapper which emits `GameEvent`s to subscribed observers
+//! as the game is interacted with.
+//!
+//! This saves callers (like UIs) from having to diff the game's state after every interaction
+//! to work out what happened.
+//!
+//! ```rust
+//! let mut game = Observed::new(game);
+//! game.subscribe(|event| {
+//!     if event.is_visible_to(0) {
+//!         println!("{event:?}");
+//!     }
+//! });
+//! game.next_round().unwrap(); // prints `RoundStarted`, player 0's `HandDealt`, `PhaseChanged` etc.
+//! ```
+
+use crate::{
+    cards::{card::CardData, meld::Meldable},
+    game::{
+        action::{GameAction, LayOffAction},
+        error::{ActionError, GameError},
+        event::{DrawSource, GameEvent},
+        r#trait::Game,
+        rules::GameRules,
+        score::{ScoreOf, VariantPlayerScore},
+        state::{GamePhase, GameState},
+    },
+};
+use std::fmt::Debug;
+
+/// The state type of a `Game`.
+type StateOf<G> = GameState<ScoreOf<G>, <G as Game>::Rules>;
+
+/// An observer of a game's events.
+type Observer<P> = Box<dyn FnMut(&GameEvent<P>) + Send>;
+
+/// Identifies a subscribed observer, so that it can be unsubscribed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ObserverId(usize);
+
+/// A wrapper around a game, which emits `GameEvent`s to its observers
+/// after each successful interaction.
+///
+/// Failed interactions don't change the game, so they don't emit any events.
+pub struct Observed<G: Game> {
+    /// The wrapped game.
+    game: G,
+    /// The subscribed observers.
+    observers: Vec<(ObserverId, Observer<ScoreOf<G>>)>,
+    /// The ID to give the next observer.
+    next_id: usize,
+}
+
+impl<G: Game> Observed<G> {
+    /// Start observing `game`.
+    pub fn new(game: G) -> Self {
+        Self {
+            game,
+            observers: Vec::new(),
+            next_id: 0,
+        }
+    }
+
+    /// Get a reference to the wrapped game.
+    pub fn get_game(&self) -> &G {
+        &self.game
+    }
+
+    /// Stop observing, returning the wrapped game.
+    pub fn into_inner(self) -> G {
+        self.game
+    }
+
+    /// Subscribe `observer` to the game's events.
+    ///
+    /// Every event is passed to every observer; use `GameEvent::visibility` to route them to the right players.
+    pub fn subscribe(
+        &mut self,
+        observer: impl FnMut(&GameEvent<ScoreOf<G>>) + Send + 'static,
+    ) -> ObserverId {
+        let id = ObserverId(self.next_id);
+        self.next_id += 1;
+        self.observers.push((id, Box::new(observer)));
+        id
+    }
+
+    /// Unsubscribe the observer with `id`, returning whether it was subscribed.
+    pub fn unsubscribe(&mut self, id: ObserverId) -> bool {
+        let count = self.observers.len();
+        self.observers.retain(|(observer_id, _)| *observer_id != id);
+        self.observers.len() != count
+    }
+
+    /// Pass `events` to every observer.
+    fn emit(&mut self, events: Vec<GameEvent<ScoreOf<G>>>) {
+        for event in &events {
+            for (_, observer) in &mut self.observers {
+                observer(event);
+            }
+        }
+    }
+
+    /// Run `interaction` on the game, emitting the events from `interaction_events` and the resulting
+    /// turn/phase changes if it succeeded.
+    fn observe<T, E>(
+        &mut self,
+        interaction: impl FnOnce(&mut G) -> Result<T, E>,
+        interaction_events: impl FnOnce(&Snapshot, &StateOf<G>) -> Vec<GameEvent<ScoreOf<G>>>,
+    ) -> Result<T, E> {
+        let before = Snapshot::take(self.game.get_state());
+        let result = interaction(&mut self.game)?;
+
+        let after = self.game.get_state();
+        let mut events = interaction_events(&before, after);
+        if after.phase() != before.phase {
+            events.push(GameEvent::PhaseChanged {
+                phase: after.phase(),
+            });
+        }
+        // a round's score is recorded once it ends (starting the next round emits this itself)
+        if after.current_round() == before.current_round {
+            events.extend(round_end_event(&before, after));
+        }
+        let current_player_id = after.get_current_player().ok().map(|p| p.id());
+        let new_turn = current_player_id != before.current_player_id
+            || after.current_round() != before.current_round;
+        if let (true, Some(player_id)) = (new_turn, current_player_id) {
+            if matches!(after.phase(), GamePhase::Draw | GamePhase::Play) {
+                events.push(GameEvent::TurnChanged { player_id });
+            }
+        }
+
+        self.emit(events);
+        Ok(result)
+    }
+}
+
+impl<G: Game + Debug> Debug for Observed<G> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Observed")
+            .field("game", &self.game)
+            .field("observers", &self.observers.len())
+            .finish()
+    }
+}
+
+impl<G: Game> Game for Observed<G> {
+    type Rules = G::Rules;
+
+    fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError> {
+        let action_for_events = action.clone();
+        self.observe(
+            |game| game.execute_action(action),
+            |before, after| {
+                let actor = before.current_player_id;
+                action_events(before, after, actor, action_for_events)
+            },
+        )
+    }
+
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        let action_for_events = action.clone();
+        self.observe(
+            |game| game.execute_out_of_turn_action(player_id, action),
+            |before, after| action_events(before, after, Some(player_id), action_for_events),
+        )
+    }
+
+    fn get_state(
+        &self,
+    ) -> &GameState<<<Self as Game>::Rules as GameRules>::VariantScore, Self::Rules> {
+        self.game.get_state()
+    }
+
+    fn quit_player(&mut self, player_id: usize) -> Result<(), GameError> {
+        self.observe(
+            |game| game.quit_player(player_id),
+            |before, after| quit_events(before, after, player_id),
+        )
+    }
+
+    fn replace_player(&mut self, player_id: usize, new_player_id: usize) -> Result<(), GameError> {
+        self.observe(
+            |game| game.replace_player(player_id, new_player_id),
+            |_, _| {
+                vec![GameEvent::PlayerReplaced {
+                    player_id,
+                    new_player_id,
+                }]
+            },
+        )
+    }
+
+    fn add_player(&mut self, player_id: usize) -> Result<(), GameError> {
+        self.observe(
+            |game| game.add_player(player_id),
+            |_, _| vec![GameEvent::PlayerJoined { player_id }],
+        )
+    }
+
+    fn rearrange_player_hand(
+        &mut self,
+        player_id: usize,
+        new_arrangement: Vec<CardData>,
+    ) -> Result<(), GameError> {
+        self.observe(
+            |game| game.rearrange_player_hand(player_id, new_arrangement),
+            |_, _| vec![],
+        )
+    }
+
+    fn pause(&mut self) -> Result<(), GameError> {
+        self.observe(|game| game.pause(), |_, _| vec![GameEvent::Paused])
+    }
+
+    fn resume(&mut self) -> Result<(), GameError> {
+        self.observe(|game| game.resume(), |_, _| vec![GameEvent::Resumed])
+    }
+
+    fn next_round(&mut self) -> Result<(), GameError> {
+        self.observe(|game| game.next_round(), round_start_events)
+    }
+}
+
+/// The parts of a game's state needed to work out the effects of an interaction.
+struct Snapshot {
+    phase: GamePhase,
+    current_player_id: Option<usize>,
+    current_round: usize,
+    /// Whether the current round's score was recorded.
+    round_scored: bool,
+    discard_pile_size: usize,
+    players: Vec<PlayerSnapshot>,
+}
+
+/// The parts of a player needed to work out the effects of an interaction.
+struct PlayerSnapshot {
+    id: usize,
+    cards: Vec<CardData>,
+    meld_count: usize,
+}
+
+impl Snapshot {
+    /// Take a snapshot of `state`.
+    fn take<P: VariantPlayerScore, R: GameRules<VariantScore = P>>(
+        state: &GameState<P, R>,
+    ) -> Self {
+        Self {
+            phase: state.phase(),
+            current_player_id: state.get_current_player().ok().map(|p| p.id()),
+            current_round: state.current_round(),
+            round_scored: state.round_scores().contains_key(&state.current_round()),
+            discard_pile_size: state.deck().discard_pile().len(),
+            players: state
+                .players()
+                .iter()
+                .map(|p| PlayerSnapshot {
+                    id: p.id(),
+                    cards: p.cards().iter().map(|c| c.data()).collect(),
+                    meld_count: p.melds().len(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Work out the events of a successful `action` by the player with ID `actor`.
+fn action_events<P: VariantPlayerScore, R: GameRules<VariantScore = P>>(
+    before: &Snapshot,
+    after: &GameState<P, R>,
+    actor: Option<usize>,
+    action: GameAction,
+) -> Vec<GameEvent<P>> {
+    let Some(player_before) = actor.and_then(|id| before.players.iter().find(|p| p.id == id))
+    else {
+        return vec![];
+    };
+    let Some(player_after) = after.players().iter().find(|p| p.id() == player_before.id) else {
+        return vec![];
+    };
+    let player_id = player_before.id;
+    let hand: Vec<_> = player_after.cards().iter().map(|c| c.data()).collect();
+
+    match action {
+        GameAction::DrawDeck(_) => {
+            let cards = hand[player_before.cards.len().min(hand.len())..].to_vec();
+            vec![
+                GameEvent::HiddenCardsDrawn {
+                    player_id,
+                    amount: cards.len(),
+                },
+                GameEvent::CardsDrawn {
+                    player_id,
+                    source: DrawSource::Deck,
+                    cards,
+                },
+            ]
+        }
+        GameAction::DrawDiscardPile(_) => vec![GameEvent::CardsDrawn {
+            player_id,
+            source: DrawSource::DiscardPile,
+            cards: hand[player_before.cards.len().min(hand.len())..].to_vec(),
+        }],
+        GameAction::FormMeld(_) | GameAction::FormMelds(_) => player_after
+            .melds()
+            .iter()
+            .enumerate()
+            .skip(player_before.meld_count)
+            .map(|(meld_index, meld)| GameEvent::MeldFormed {
+                player_id,
+                meld_index,
+                cards: meld.cards().iter().map(|c| c.data()).collect(),
+            })
+            .collect(),
+        GameAction::LayOff(LayOffAction {
+            card_index,
+            target_player_index,
+            target_meld_index,
+        }) => {
+            let (Some(&card), Some(target_player)) = (
+                player_before.cards.get(card_index),
+                after.players().get(target_player_index),
+            ) else {
+                return vec![];
+            };
+            let target_player_id = target_player.id();
+            // a layoff that swaps out a wildcard leaves the hand's size unchanged
+            match hand.get(card_index) {
+                Some(&wildcard) if hand.len() == player_before.cards.len() => {
+                    vec![GameEvent::WildcardSwapped {
+                        player_id,
+                        target_player_id,
+                        meld_index: target_meld_index,
+                        card,
+                        wildcard,
+                    }]
+                }
+                _ => vec![GameEvent::CardLaidOff {
+                    player_id,
+                    target_player_id,
+                    meld_index: target_meld_index,
+                    card,
+                }],
+            }
+        }
+        GameAction::Discard(action) => player_before
+            .cards
+            .get(action.card_index)
+            .map(|&card| GameEvent::CardDiscarded { player_id, card })
+            .into_iter()
+            .collect(),
+    }
+}
+
+/// Work out the events of successfully starting a new round.
+fn round_start_events<P: VariantPlayerScore, R: GameRules<VariantScore = P>>(
+    before: &Snapshot,
+    after: &GameState<P, R>,
+) -> Vec<GameEvent<P>> {
+    let mut events: Vec<_> = round_end_event(before, after).into_iter().collect();
+    events.push(GameEvent::RoundStarted {
+        round: after.current_round(),
+    });
+    for player in after.players() {
+        events.push(GameEvent::HandDealt {
+            player_id: player.id(),
+            cards: player.cards().iter().map(|c| c.data()).collect(),
+        });
+    }
+    events
+}
+
+/// Work out the `RoundEnded` event, if the round in `before` has had its score recorded since.
+fn round_end_event<P: VariantPlayerScore, R: GameRules<VariantScore = P>>(
+    before: &Snapshot,
+    after: &GameState<P, R>,
+) -> Option<GameEvent<P>> {
+    if before.round_scored {
+        return None;
+    }
+    let score = after.round_scores().get(&before.current_round)?;
+    Some(GameEvent::RoundEnded {
+        round: before.current_round,
+        score: score.clone(),
+    })
+}
+
+/// Work out the events of the player with ID `player_id` successfully quitting,
+/// including where their hand went if it was taken from them.
+fn quit_events<P: VariantPlayerScore, R: GameRules<VariantScore = P>>(
+    before: &Snapshot,
+    after: &GameState<P, R>,
+    player_id: usize,
+) -> Vec<GameEvent<P>> {
+    let mut events = vec![GameEvent::PlayerQuit { player_id }];
+    let hand_before = before.players.iter().find(|p| p.id == player_id);
+    let hand_after = after.players().iter().find(|p| p.id() == player_id);
+    let (Some(hand_before), Some(hand_after)) = (hand_before, hand_after) else {
+        return events;
+    };
+    if hand_before.cards.is_empty() || !hand_after.cards().is_empty() {
+        return events;
+    }
+    match after.deck().discard_pile().len() > before.discard_pile_size {
+        true => events.push(GameEvent::HandDiscarded {
+            player_id,
+            cards: hand_before.cards.clone(),
+        }),
+        false => events.push(GameEvent::HandReturnedToStock {
+            player_id,
+            amount: hand_before.cards.len(),
+        }),
+    }
+    events
+}
@@ -0,0 +1,572 @@
+//! Contains a plain-text record format for complete games, similar in spirit to chess's PGN.
+//!
+//! A record consists of a header of `[Key "Value"]` tags describing the game setup,
+//! followed by one line per interaction in the game's `History`, with a separator at the start of each round.
+//!
+//! ## Example
+//! ```text
+//! [Variant "basic"]
+//! [Players "0 1"]
+//! [Seed "1"]
+//! [PackCount "1"]
+//! [HighRank "-"]
+//! [WildcardRank "-"]
+//! [DealAmount "-"]
+//! [DrawDeckAmount "-"]
+//! [DrawDiscardPileAmount "-"]
+//!
+//! == round 0 ==
+//! join 2
+//! == round 1 ==
+//! draw deck
+//! arrange 0 2♣ 3♣ 4♣ 5♣ 9♦ J♦ 2♥ 6♥ 7♥ 10♠ K♠
+//! meld 1 2 3
+//! ! discard 100
+//! discard 0
+//! ```
+//!
+//! ## Entries
+//! - `draw deck`
+//! - `draw discard` or `draw discard <count>`
+//! - `layoff <card index> <target player index> <target meld index>`
+//! - `meld <card indices...>`
+//! - `melds <card indices...> / <card indices...> / ...`
+//! - `discard <card index>`, optionally followed by `out` or `no-out` for the going out declaration
+//! - `join <player ID>`
+//! - `quit <player ID>`
+//! - `replace <player ID> <new player ID>`
+//! - `arrange <player ID> <cards...>`, using the [card notation](crate::cards::notation)
+//! - `pause` and `resume`
+//!
+//! Actions made out of turn are prefixed with `@<player ID> `, and entries that failed to execute are prefixed with `! `
+//! (before any `@`). Entries that were undone (see `History::undo`) are prefixed with `~ ` instead of `! `.
+//! Lines starting with `#` are comments.
+//!
+//! Timestamps are intentionally left out, so that records of the same game are identical and can be diffed.
+//! A parsed record is replayed to rebuild the `History`, so its entries are timestamped at parse time.
+
+use crate::{
+    cards::{
+        deck::DeckConfig,
+        notation::{format_cards, parse_cards},
+        suit_rank::Rank,
+    },
+    game::{
+        action::*,
+        error::{GameError, GameSetupError},
+        r#trait::Game,
+        state::QuitHandPolicy,
+        variants::basic::{
+            config::{BasicConfig, DrawDiscardPileOverride},
+            game::BasicRummyGame,
+        },
+    },
+    wrappers::history::{apply_interaction, History},
+};
+use std::{fmt::Write, str::FromStr};
+use thiserror::Error;
+
+/// A `Game` which can be written to and read from a game record.
+///
+/// The variant is responsible for describing its own setup (configuration, deck, seed) in the record header.
+pub trait RecordableGame: Game + Clone {
+    /// The name of the variant, written as the `Variant` tag.
+    const VARIANT: &'static str;
+
+    /// Write the game's setup into `header`.
+    ///
+    /// This is called on the game's initial (round 0) state.
+    ///
+    /// Returns an `Err` if the game can't be faithfully recorded (for eg, if its deck isn't seeded).
+    fn write_header(&self, header: &mut RecordHeader) -> Result<(), RecordError>;
+
+    /// Create a new game for `player_ids` from the setup in `header`.
+    fn from_header(player_ids: Vec<usize>, header: &RecordHeader) -> Result<Self, RecordError>;
+}
+
+/// The header tags of a game record, kept in the order they were written.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct RecordHeader {
+    tags: Vec<(String, String)>,
+}
+
+impl RecordHeader {
+    /// Set the tag `key` to `value`, replacing any existing value.
+    pub fn set(&mut self, key: &str, value: impl ToString) {
+        let value = value.to_string();
+        match self.tags.iter_mut().find(|(k, _)| k == key) {
+            Some((_, v)) => *v = value,
+            None => self.tags.push((key.to_string(), value)),
+        }
+    }
+
+    /// Get the value of the tag `key`, if it exists.
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.tags
+            .iter()
+            .find(|(k, _)| k == key)
+            .map(|(_, v)| v.as_str())
+    }
+
+    /// Get and parse the value of the tag `key`.
+    ///
+    /// Returns an `Err` if the tag doesn't exist or its value can't be parsed.
+    pub fn parse<T: FromStr>(&self, key: &str) -> Result<T, RecordError> {
+        let value = self
+            .get(key)
+            .ok_or_else(|| RecordError::MissingTag(key.to_string()))?;
+        value.parse().map_err(|_| RecordError::InvalidTag {
+            key: key.to_string(),
+            value: value.to_string(),
+        })
+    }
+
+    /// Get and parse the value of the tag `key`, where `-` denotes `None`.
+    ///
+    /// Returns an `Err` if the tag doesn't exist or its value can't be parsed.
+    pub fn parse_optional<T: FromStr>(&self, key: &str) -> Result<Option<T>, RecordError> {
+        match self.get(key) {
+            Some("-") => Ok(None),
+            _ => self.parse(key).map(Some),
+        }
+    }
+
+    /// Get an iterator over the tags.
+    pub fn tags(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.tags.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+}
+
+impl<G: RecordableGame> History<G> {
+    /// Write the history as a game record.
+    ///
+    /// Returns an `Err` if the game can't be faithfully recorded.
+    pub fn to_record(&self) -> Result<String, RecordError> {
+        let initial_game = self
+            .get_initial_round_states()
+            .get(&0)
+            .expect("History should always contain an initial round state for round 0");
+        if !self.is_reproducible_from_seed() {
+            return Err(RecordError::UnseededDeck);
+        }
+
+        let mut header = RecordHeader::default();
+        header.set("Variant", G::VARIANT);
+        let player_ids: Vec<_> = initial_game
+            .get_state()
+            .players()
+            .iter()
+            .map(|p| p.id().to_string())
+            .collect();
+        header.set("Players", player_ids.join(" "));
+        initial_game.write_header(&mut header)?;
+
+        let mut record = String::new();
+        for (key, value) in header.tags() {
+            // UNWRAP: writing to a `String` can't fail
+            writeln!(record, "[{key} \"{value}\"]").unwrap();
+        }
+        record.push('\n');
+
+        let mut rounds: Vec<_> = self.get_histories().keys().copied().collect();
+        rounds.sort();
+        for round in rounds {
+            writeln!(record, "== round {round} ==").unwrap();
+            // replayed alongside, to tell which actions were made out of turn
+            let mut game = self.get_initial_round_states()[&round].clone();
+            for entry in &self.get_histories()[&round] {
+                if entry.undone {
+                    record.push_str("~ ");
+                } else if !entry.successful {
+                    record.push_str("! ");
+                }
+                let current_player_id = game.get_state().get_current_player().ok().map(|p| p.id());
+                if let (GameInteractions::Action(_), Some(actor)) = (&entry.entry, entry.actor) {
+                    if Some(actor) != current_player_id {
+                        write!(record, "@{actor} ").unwrap();
+                    }
+                }
+                record.push_str(&format_interaction(&entry.entry));
+                record.push('\n');
+                if !entry.undone {
+                    apply_interaction(&mut game, entry.entry.clone(), entry.actor);
+                }
+            }
+        }
+
+        Ok(record)
+    }
+
+    /// Parse a game record and replay it into a `History`.
+    ///
+    /// Returns an `Err` if the record is malformed, is for a different variant,
+    /// or doesn't replay to the same outcome (ie, an entry succeeds/fails differently than recorded).
+    pub fn from_record(record: &str) -> Result<Self, RecordError> {
+        let mut lines = record
+            .lines()
+            .enumerate()
+            .map(|(i, line)| (i + 1, line.trim()))
+            .filter(|(_, line)| !line.is_empty() && !line.starts_with('#'))
+            .peekable();
+
+        let mut header = RecordHeader::default();
+        while let Some(&(line_number, line)) = lines.peek() {
+            if !line.starts_with('[') {
+                break;
+            }
+            let (key, value) = parse_tag(line).ok_or(RecordError::InvalidLine {
+                line_number,
+                line: line.to_string(),
+            })?;
+            header.set(key, value);
+            lines.next();
+        }
+
+        let variant: String = header.parse("Variant")?;
+        if variant != G::VARIANT {
+            return Err(RecordError::WrongVariant {
+                expected: G::VARIANT.to_string(),
+                found: variant,
+            });
+        }
+        let player_ids = header
+            .get("Players")
+            .ok_or_else(|| RecordError::MissingTag("Players".to_string()))?
+            .split_whitespace()
+            .map(|id| id.parse())
+            .collect::<Result<Vec<usize>, _>>()
+            .map_err(|_| RecordError::InvalidTag {
+                key: "Players".to_string(),
+                value: header.get("Players").unwrap_or_default().to_string(),
+            })?;
+
+        let mut history = History::from_new_game(G::from_header(player_ids, &header)?);
+        let mut started = false;
+
+        for (line_number, line) in lines {
+            if let Some(round) = parse_round_separator(line) {
+                // the first separator is for round 0, which `history` is already in
+                if started {
+                    history.next_round()?;
+                }
+                started = true;
+                let current_round = history.get_state().current_round();
+                if round != current_round {
+                    return Err(RecordError::UnexpectedRound {
+                        line_number,
+                        expected: current_round,
+                        found: round,
+                    });
+                }
+                continue;
+            }
+
+            let invalid_line = || RecordError::InvalidLine {
+                line_number,
+                line: line.to_string(),
+            };
+            let (undone, entry) = match line.strip_prefix('~') {
+                Some(entry) => (true, entry.trim_start()),
+                None => (false, line),
+            };
+            let (successful, entry) = match entry.strip_prefix('!') {
+                Some(entry) if !undone => (false, entry.trim_start()),
+                Some(_) => return Err(invalid_line()),
+                None => (true, entry),
+            };
+            let (actor, entry) = match entry.strip_prefix('@') {
+                Some(entry) => {
+                    let (actor, entry) = entry.split_once(' ').ok_or_else(invalid_line)?;
+                    (Some(actor.parse().map_err(|_| invalid_line())?), entry)
+                }
+                None => (None, entry),
+            };
+            let interaction = parse_interaction(entry).ok_or_else(invalid_line)?;
+            if !started {
+                return Err(RecordError::MissingRoundSeparator { line_number });
+            }
+
+            // undone entries had no lasting effect, so they're only recorded
+            if undone {
+                let current_player_id = history
+                    .get_state()
+                    .get_current_player()
+                    .ok()
+                    .map(|p| p.id());
+                history.record_undone(interaction, actor.or(current_player_id));
+                continue;
+            }
+            let replayed_successfully = apply_interaction(&mut history, interaction, actor);
+            if replayed_successfully != successful {
+                return Err(RecordError::ReplayMismatch { line_number });
+            }
+        }
+
+        Ok(history)
+    }
+}
+
+impl RecordableGame for BasicRummyGame {
+    const VARIANT: &'static str = "basic";
+
+    fn write_header(&self, header: &mut RecordHeader) -> Result<(), RecordError> {
+        let deck_config = self.state.deck.config();
+        let seed = deck_config.shuffle_seed.ok_or(RecordError::UnseededDeck)?;
+        header.set("Seed", seed);
+        header.set("PackCount", deck_config.pack_count);
+        header.set(
+            "HighRank",
+            format_optional(deck_config.high_rank.map(|r| r.as_str())),
+        );
+        header.set(
+            "WildcardRank",
+            format_optional(deck_config.wildcard_rank.map(|r| r.as_str())),
+        );
+
+        let config = self.rules.config();
+        header.set("DealAmount", format_optional(config.deal_amount));
+        header.set("DrawDeckAmount", format_optional(config.draw_deck_amount));
+        let draw_discard_pile_amount = config.draw_discard_pile_amount.as_ref().map(|a| match a {
+            DrawDiscardPileOverride::PlayerChooses => "player-chooses".to_string(),
+            DrawDiscardPileOverride::WholePile => "whole-pile".to_string(),
+            DrawDiscardPileOverride::Constant(amount) => amount.to_string(),
+        });
+        header.set(
+            "DrawDiscardPileAmount",
+            format_optional(draw_discard_pile_amount),
+        );
+        let quit_hand_policy = config.quit_hand_policy.map(|p| match p {
+            QuitHandPolicy::Keep => "keep",
+            QuitHandPolicy::ReturnToStock => "return-to-stock",
+            QuitHandPolicy::Discard => "discard",
+        });
+        header.set("QuitHandPolicy", format_optional(quit_hand_policy));
+
+        Ok(())
+    }
+
+    fn from_header(player_ids: Vec<usize>, header: &RecordHeader) -> Result<Self, RecordError> {
+        let deck_config = DeckConfig {
+            shuffle_seed: Some(header.parse("Seed")?),
+            pack_count: header.parse("PackCount")?,
+            high_rank: header.parse_optional::<Rank>("HighRank")?,
+            wildcard_rank: header.parse_optional::<Rank>("WildcardRank")?,
+        };
+
+        let draw_discard_pile_amount = match header.get("DrawDiscardPileAmount") {
+            Some("player-chooses") => Some(DrawDiscardPileOverride::PlayerChooses),
+            Some("whole-pile") => Some(DrawDiscardPileOverride::WholePile),
+            _ => header
+                .parse_optional("DrawDiscardPileAmount")?
+                .map(DrawDiscardPileOverride::Constant),
+        };
+        // records from before quit hand policies existed don't have this tag
+        let quit_hand_policy = match header.get("QuitHandPolicy") {
+            None | Some("-") => None,
+            Some("keep") => Some(QuitHandPolicy::Keep),
+            Some("return-to-stock") => Some(QuitHandPolicy::ReturnToStock),
+            Some("discard") => Some(QuitHandPolicy::Discard),
+            Some(value) => {
+                return Err(RecordError::InvalidTag {
+                    key: "QuitHandPolicy".to_string(),
+                    value: value.to_string(),
+                })
+            }
+        };
+        let config = BasicConfig {
+            deal_amount: header.parse_optional("DealAmount")?,
+            draw_deck_amount: header.parse_optional("DrawDeckAmount")?,
+            draw_discard_pile_amount,
+            quit_hand_policy,
+        };
+
+        Ok(BasicRummyGame::new(player_ids, config, deck_config)?)
+    }
+}
+
+/// Errors from writing or parsing a game record.
+#[derive(Debug, Clone, Error)]
+pub enum RecordError {
+    #[error("The deck isn't shuffled from its shuffle seed, so the game can't be reproduced from a record")]
+    UnseededDeck,
+    #[error("The record is missing the {0:?} tag")]
+    MissingTag(String),
+    #[error("The {key:?} tag has an invalid value: {value:?}")]
+    InvalidTag { key: String, value: String },
+    #[error("The record is for variant {found:?}, but expected {expected:?}")]
+    WrongVariant { expected: String, found: String },
+    #[error("Line {line_number} couldn't be parsed: {line:?}")]
+    InvalidLine { line_number: usize, line: String },
+    #[error("Line {line_number} is an entry before the first round separator")]
+    MissingRoundSeparator { line_number: usize },
+    #[error("Line {line_number} starts round {found}, but the game is in round {expected}")]
+    UnexpectedRound {
+        line_number: usize,
+        expected: usize,
+        found: usize,
+    },
+    #[error("Line {line_number} didn't replay with the recorded outcome")]
+    ReplayMismatch { line_number: usize },
+    #[error("Failed to set up the recorded game: {0}")]
+    Setup(#[from] GameSetupError),
+    #[error("Failed to replay the recorded game: {0}")]
+    Game(#[from] GameError),
+}
+
+/// Format a single interaction as a record entry.
+fn format_interaction(interaction: &GameInteractions) -> String {
+    match interaction {
+        GameInteractions::Action(action) => match action {
+            GameAction::DrawDeck(_) => "draw deck".to_string(),
+            GameAction::DrawDiscardPile(DrawDiscardPileAction { count: None }) => {
+                "draw discard".to_string()
+            }
+            GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(count) }) => {
+                format!("draw discard {count}")
+            }
+            GameAction::LayOff(action) => format!(
+                "layoff {} {} {}",
+                action.card_index, action.target_player_index, action.target_meld_index
+            ),
+            GameAction::FormMeld(action) => {
+                format!("meld {}", format_indices(&action.card_indices))
+            }
+            GameAction::FormMelds(action) => {
+                let melds: Vec<_> = action.melds.iter().map(|m| format_indices(m)).collect();
+                format!("melds {}", melds.join(" / "))
+            }
+            GameAction::Discard(action) => match action.declare_going_out {
+                None => format!("discard {}", action.card_index),
+                Some(true) => format!("discard {} out", action.card_index),
+                Some(false) => format!("discard {} no-out", action.card_index),
+            },
+        },
+        GameInteractions::PlayerJoin { player_id } => format!("join {player_id}"),
+        GameInteractions::PlayerQuit { player_id } => format!("quit {player_id}"),
+        GameInteractions::PlayerReplaced {
+            player_id,
+            new_player_id,
+        } => format!("replace {player_id} {new_player_id}"),
+        GameInteractions::HandRearrangement {
+            player_id,
+            new_arrangement,
+        } => format!("arrange {player_id} {}", format_cards(new_arrangement)),
+        GameInteractions::Pause => "pause".to_string(),
+        GameInteractions::Resume => "resume".to_string(),
+    }
+}
+
+/// Parse a single record entry into an interaction, returning `None` if it's invalid.
+fn parse_interaction(line: &str) -> Option<GameInteractions> {
+    let (keyword, rest) = line.split_once(' ').unwrap_or((line, ""));
+    let rest = rest.trim();
+    let action = match keyword {
+        "draw" => match rest.split_once(' ').unwrap_or((rest, "")) {
+            ("deck", "") => GameAction::DrawDeck(DrawDeckAction {}),
+            ("discard", "") => GameAction::DrawDiscardPile(DrawDiscardPileAction { count: None }),
+            ("discard", count) => GameAction::DrawDiscardPile(DrawDiscardPileAction {
+                count: Some(count.parse().ok()?),
+            }),
+            _ => return None,
+        },
+        "layoff" => match parse_indices(rest)?.as_slice() {
+            &[card_index, target_player_index, target_meld_index] => {
+                GameAction::LayOff(LayOffAction {
+                    card_index,
+                    target_player_index,
+                    target_meld_index,
+                })
+            }
+            _ => return None,
+        },
+        "meld" => GameAction::FormMeld(FormMeldAction {
+            card_indices: parse_indices(rest)?,
+        }),
+        "melds" => GameAction::FormMelds(FormMeldsAction {
+            melds: rest
+                .split('/')
+                .map(parse_indices)
+                .collect::<Option<Vec<_>>>()?,
+        }),
+        "discard" => {
+            let (index, declaration) = rest.split_once(' ').unwrap_or((rest, ""));
+            let declare_going_out = match declaration {
+                "" => None,
+                "out" => Some(true),
+                "no-out" => Some(false),
+                _ => return None,
+            };
+            GameAction::Discard(DiscardAction {
+                card_index: index.parse().ok()?,
+                declare_going_out,
+            })
+        }
+        "join" => {
+            return Some(GameInteractions::PlayerJoin {
+                player_id: rest.parse().ok()?,
+            })
+        }
+        "quit" => {
+            return Some(GameInteractions::PlayerQuit {
+                player_id: rest.parse().ok()?,
+            })
+        }
+        "pause" if rest.is_empty() => return Some(GameInteractions::Pause),
+        "resume" if rest.is_empty() => return Some(GameInteractions::Resume),
+        "replace" => {
+            let (player_id, new_player_id) = rest.split_once(' ')?;
+            return Some(GameInteractions::PlayerReplaced {
+                player_id: player_id.parse().ok()?,
+                new_player_id: new_player_id.parse().ok()?,
+            });
+        }
+        "arrange" => {
+            let (player_id, cards) = rest.split_once(' ').unwrap_or((rest, ""));
+            return Some(GameInteractions::HandRearrangement {
+                player_id: player_id.parse().ok()?,
+                new_arrangement: parse_cards(cards).ok()?,
+            });
+        }
+        _ => return None,
+    };
+    Some(GameInteractions::Action(action))
+}
+
+/// Parse a header tag like `[Key "Value"]`.
+fn parse_tag(line: &str) -> Option<(&str, &str)> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    let (key, value) = inner.split_once(' ')?;
+    let value = value.trim().strip_prefix('"')?.strip_suffix('"')?;
+    Some((key, value))
+}
+
+/// Parse a round separator like `== round 1 ==`.
+fn parse_round_separator(line: &str) -> Option<usize> {
+    line.strip_prefix("== round ")?
+        .strip_suffix(" ==")?
+        .parse()
+        .ok()
+}
+
+/// Format indices as space-separated numbers.
+fn format_indices(indices: &[usize]) -> String {
+    indices
+        .iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse space-separated indices, returning `None` if any are invalid or there are none.
+fn parse_indices(s: &str) -> Option<Vec<usize>> {
+    let indices = s
+        .split_whitespace()
+        .map(|i| i.parse().ok())
+        .collect::<Option<Vec<_>>>()?;
+    (!indices.is_empty()).then_some(indices)
+}
+
+/// Format an optional value, using `-` for `None`.
+fn format_optional<T: ToString>(value: Option<T>) -> String {
+    value.map_or("-".to_string(), |v| v.to_string())
+}
@@ -21,7 +21,7 @@ pub struct ReplayState<G: Game + Clone> {
     round: usize,
     /// The index of the current action of the replay.
     action: usize,
-    /// Whether to skip actions that didn't successfully execute.
+    /// Whether to skip actions that didn't successfully execute (or were undone).
     skip_failed_actions: bool,
 }
 
@@ -53,7 +53,7 @@ impl<G: Game + Clone> ReplayState<G> {
             match history.get(self.action) {
                 Some(action) => {
                     self.action += 1;
-                    if !action.successful && self.skip_failed_actions {
+                    if !action.took_effect() && self.skip_failed_actions {
                         continue;
                     } else {
                         Self::apply_action(&mut self.replaying_game, action);
@@ -117,17 +117,24 @@ impl<G: Game + Clone> ReplayState<G> {
 
     // Convenience function for applying an action to a game.
     fn apply_action(game: &mut G, action: &HistoryEntry) {
-        if action.successful {
+        if action.took_effect() {
             match action.entry.clone() {
-                GameInteractions::Action(game_action) => {
-                    game.execute_action(game_action).unwrap();
-                }
+                GameInteractions::Action(game_action) => match action.actor {
+                    Some(actor) => game.execute_action_as(actor, game_action).unwrap(),
+                    None => game.execute_action(game_action).unwrap(),
+                },
                 GameInteractions::PlayerJoin { player_id } => {
                     game.add_player(player_id).unwrap();
                 }
                 GameInteractions::PlayerQuit { player_id } => {
                     game.quit_player(player_id).unwrap();
                 }
+                GameInteractions::PlayerReplaced {
+                    player_id,
+                    new_player_id,
+                } => {
+                    game.replace_player(player_id, new_player_id).unwrap();
+                }
                 GameInteractions::HandRearrangement {
                     player_id,
                     new_arrangement,
@@ -135,6 +142,8 @@ impl<G: Game + Clone> ReplayState<G> {
                     game.rearrange_player_hand(player_id, new_arrangement)
                         .unwrap();
                 }
+                GameInteractions::Pause => game.pause().unwrap(),
+                GameInteractions::Resume => game.resume().unwrap(),
             };
         }
     }
@@ -163,7 +172,7 @@ pub struct Replay<G: Game + Clone> {
 impl<G: Game + Clone> Replay<G> {
     /// Create a replay from a game with history.
     ///
-    /// If you want to skip unsuccessful/failed actions during replay, set `skip_failed_actions` to true.
+    /// If you want to skip unsuccessful/failed (or undone) actions during replay, set `skip_failed_actions` to true.
     pub fn new(game: History<G>, skip_failed_actions: bool) -> Self {
         Self {
             replay_state: ReplayState::new(game, skip_failed_actions),
@@ -207,6 +216,16 @@ impl<G: Game + Clone> Game for Replay<G> {
         self.replay_state.game.execute_action(action)
     }
 
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        self.replay_state
+            .game
+            .execute_out_of_turn_action(player_id, action)
+    }
+
     fn get_state(
         &self,
     ) -> &crate::game::state::GameState<
@@ -220,6 +239,12 @@ impl<G: Game + Clone> Game for Replay<G> {
         self.replay_state.game.quit_player(player_id)
     }
 
+    fn replace_player(&mut self, player_id: usize, new_player_id: usize) -> Result<(), GameError> {
+        self.replay_state
+            .game
+            .replace_player(player_id, new_player_id)
+    }
+
     fn add_player(&mut self, player_id: usize) -> Result<(), GameError> {
         self.replay_state.game.add_player(player_id)
     }
@@ -234,6 +259,14 @@ impl<G: Game + Clone> Game for Replay<G> {
             .rearrange_player_hand(player_id, new_arrangement)
     }
 
+    fn pause(&mut self) -> Result<(), GameError> {
+        self.replay_state.game.pause()
+    }
+
+    fn resume(&mut self) -> Result<(), GameError> {
+        self.replay_state.game.resume()
+    }
+
     fn next_round(&mut self) -> Result<(), GameError> {
         self.replay_state.game.next_round()
     }
@@ -0,0 +1,437 @@
+//! Contains `Timed`, a wrapper which enforces turn time limits and a per-player time bank.
+//!
+//! Time is read from a `Clock`, which can be swapped for a `ManualClock` to control time in tests.
+//!
+//! Nothing runs in the background, so the owner of the game should call `Timed::check_timeout`
+//! regularly (for eg, on a timer). Interacting with the game also checks for a timeout first.
+//!
+//! While the game is paused, the clocks are frozen.
+//!
+//! ```rust
+//! let control = TimeControl {
+//!     turn_limit: Some(Duration::seconds(30)),
+//!     time_bank: Some(Duration::minutes(2)),
+//! };
+//! let mut game = Timed::new(game, control, TimeoutPolicy::DrawAndDiscard, SystemClock);
+//!
+//! // in a loop somewhere...
+//! if let Some(timeout) = game.check_timeout().unwrap() {
+//!     println!("player {} ran out of time", timeout.player_id);
+//! }
+//! ```
+
+use crate::{
+    cards::card::CardData,
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        error::{ActionError, FailedActionError, GameError},
+        r#trait::Game,
+        rules::GameRules,
+        state::{GamePhase, GameState},
+    },
+};
+use chrono::{DateTime, Duration, Utc};
+use std::{
+    collections::{BTreeSet, HashMap},
+    sync::{Arc, Mutex, MutexGuard},
+};
+
+/// The maximum number of actions a `TimeoutBot` can take for a turn, before falling back to drawing and discarding.
+const MAX_BOT_ACTIONS: usize = 64;
+
+/// A source of the current time.
+pub trait Clock {
+    /// Get the current time.
+    fn now(&self) -> DateTime<Utc>;
+}
+
+/// A `Clock` using the system's time.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A `Clock` which only moves when told to, for deterministic tests.
+///
+/// Clones share the same time, so one can be kept to control the clock after passing another to a game.
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    time: Arc<Mutex<DateTime<Utc>>>,
+}
+
+impl ManualClock {
+    /// Create a clock starting at `time`.
+    pub fn new(time: DateTime<Utc>) -> Self {
+        Self {
+            time: Arc::new(Mutex::new(time)),
+        }
+    }
+
+    /// Move the clock forward by `duration`.
+    pub fn advance(&self, duration: Duration) {
+        *self.time() += duration;
+    }
+
+    /// Set the clock to `time`.
+    pub fn set(&self, time: DateTime<Utc>) {
+        *self.time() = time;
+    }
+
+    fn time(&self) -> MutexGuard<'_, DateTime<Utc>> {
+        // UNWRAP: we never panic while holding the lock
+        self.time.lock().unwrap()
+    }
+}
+
+impl Clock for ManualClock {
+    fn now(&self) -> DateTime<Utc> {
+        *self.time()
+    }
+}
+
+/// The time controls of a game.
+///
+/// ### `turn_limit`
+/// The time each player has for a turn before their time bank is used.
+/// If `None`, every turn uses the time bank (like a chess clock).
+///
+/// ### `time_bank`
+/// The extra time each player has for the whole game, used up by turns going over `turn_limit`.
+/// If `None`, a turn times out as soon as it goes over `turn_limit`.
+///
+/// If both are `None`, turns never time out.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TimeControl {
+    pub turn_limit: Option<Duration>,
+    pub time_bank: Option<Duration>,
+}
+
+/// What to do when a player's turn times out.
+pub enum TimeoutPolicy<G: Game> {
+    /// Draw from the deck (if the player hasn't drawn yet) and discard the last card in their hand.
+    DrawAndDiscard,
+    /// Let a bot finish the player's turn, falling back to `DrawAndDiscard` if it doesn't.
+    AutoPlay(Box<dyn TimeoutBot<G> + Send>),
+    /// The player sits out the rest of the round: they're quit (so their hand is handled by the game's quit hand policy),
+    /// and take their seat again when the next round starts. The round is still scored as usual.
+    ///
+    /// This relies on the game supporting `Game::replace_player`. If sitting out would leave fewer than 2 players
+    /// in the round (which ends the game for most variants), their turn is played with `DrawAndDiscard` instead.
+    ForfeitRound,
+}
+
+/// A bot which plays a turn for a player who timed out.
+pub trait TimeoutBot<G: Game> {
+    /// Choose the next action for the player with ID `player_id`, whose turn it is in `game`.
+    fn next_action(&mut self, game: &G, player_id: usize) -> GameAction;
+}
+
+/// A turn that timed out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Timeout {
+    pub player_id: usize,
+    pub round: usize,
+}
+
+/// The current turn being timed.
+#[derive(Clone, Copy, Debug)]
+struct TurnClock {
+    player_id: usize,
+    round: usize,
+    started_at: DateTime<Utc>,
+}
+
+/// A wrapper around a game, which enforces its `TimeControl` by applying a `TimeoutPolicy`
+/// when a player's turn takes too long.
+pub struct Timed<G: Game, C: Clock = SystemClock> {
+    /// The wrapped game.
+    game: G,
+    clock: C,
+    control: TimeControl,
+    policy: TimeoutPolicy<G>,
+    /// The turn currently being timed, if any.
+    turn: Option<TurnClock>,
+    /// The remaining time bank of each player.
+    banks: HashMap<usize, Duration>,
+    /// The players who forfeited the current round, who take their seats again in the next round.
+    forfeited: BTreeSet<usize>,
+    /// When the game was paused, if it's paused.
+    paused_at: Option<DateTime<Utc>>,
+}
+
+impl<G: Game, C: Clock> Timed<G, C> {
+    /// Start timing `game` according to `control`, reading the time from `clock`.
+    pub fn new(game: G, control: TimeControl, policy: TimeoutPolicy<G>, clock: C) -> Self {
+        let mut timed = Self {
+            game,
+            clock,
+            control,
+            policy,
+            turn: None,
+            banks: HashMap::new(),
+            forfeited: BTreeSet::new(),
+            paused_at: None,
+        };
+        if timed.game.get_state().is_paused() {
+            timed.paused_at = Some(timed.clock.now());
+        }
+        timed.sync_turn();
+        timed
+    }
+
+    /// Get a reference to the wrapped game.
+    pub fn get_game(&self) -> &G {
+        &self.game
+    }
+
+    /// Stop timing, returning the wrapped game.
+    pub fn into_inner(self) -> G {
+        self.game
+    }
+
+    /// Get the time controls.
+    pub fn time_control(&self) -> TimeControl {
+        self.control
+    }
+
+    /// Get the remaining time bank of the player with ID `player_id`, or `None` if there's no time bank.
+    ///
+    /// This doesn't include the time used by the current turn.
+    pub fn time_bank(&self, player_id: usize) -> Option<Duration> {
+        self.control
+            .time_bank
+            .map(|bank| self.banks.get(&player_id).copied().unwrap_or(bank))
+    }
+
+    /// Get the time left for the current turn (including the player's time bank),
+    /// or `None` if no turn is being timed or the turn has no limit.
+    pub fn turn_time_remaining(&self) -> Option<Duration> {
+        let turn = self.turn?;
+        let allowed = self.allowed_time(turn.player_id)?;
+        Some((allowed - (self.now() - turn.started_at)).max(Duration::zero()))
+    }
+
+    /// Check whether the current turn has timed out, and if so, apply the `TimeoutPolicy`.
+    ///
+    /// Returns the turn that timed out, if any, or an `Err` if applying the policy failed.
+    pub fn check_timeout(&mut self) -> Result<Option<Timeout>, ActionError> {
+        let Some(turn) = self.turn else {
+            return Ok(None);
+        };
+        if self.paused_at.is_some() || self.turn_time_remaining() != Some(Duration::zero()) {
+            return Ok(None);
+        }
+
+        let timeout = Timeout {
+            player_id: turn.player_id,
+            round: turn.round,
+        };
+        match &mut self.policy {
+            TimeoutPolicy::DrawAndDiscard => draw_and_discard(&mut self.game)?,
+            TimeoutPolicy::AutoPlay(bot) => {
+                for _ in 0..MAX_BOT_ACTIONS {
+                    if !is_turn(&self.game, turn) {
+                        break;
+                    }
+                    let action = bot.next_action(&self.game, turn.player_id);
+                    if self.game.execute_action(action).is_err() {
+                        break;
+                    }
+                }
+                if is_turn(&self.game, turn) {
+                    draw_and_discard(&mut self.game)?;
+                }
+            }
+            TimeoutPolicy::ForfeitRound => {
+                let active_players = self
+                    .game
+                    .get_state()
+                    .players()
+                    .iter()
+                    .filter(|p| p.active())
+                    .count();
+                // quitting the second-to-last player would end the game, rather than just their round
+                if active_players > 2 {
+                    self.game.quit_player(turn.player_id).map_err(|error| {
+                        FailedActionError::ForfeitFailed {
+                            player_id: turn.player_id,
+                            error: Box::new(error),
+                        }
+                    })?;
+                    self.forfeited.insert(turn.player_id);
+                } else {
+                    draw_and_discard(&mut self.game)?;
+                }
+            }
+        }
+        self.sync_turn();
+        Ok(Some(timeout))
+    }
+
+    /// The current time on the game's clock, which stands still while the game is paused.
+    fn now(&self) -> DateTime<Utc> {
+        self.paused_at.unwrap_or_else(|| self.clock.now())
+    }
+
+    /// The total time the player with ID `player_id` has for a turn, or `None` if it's unlimited.
+    fn allowed_time(&self, player_id: usize) -> Option<Duration> {
+        let bank = self.time_bank(player_id);
+        match (self.control.turn_limit, bank) {
+            (None, None) => None,
+            (limit, bank) => Some(limit.unwrap_or_default() + bank.unwrap_or_default()),
+        }
+    }
+
+    /// Update the timed turn after the game changed, charging any overtime of the last turn to the time bank.
+    fn sync_turn(&mut self) {
+        let state = self.game.get_state();
+        let current = match state.phase() {
+            GamePhase::Draw | GamePhase::Play => state
+                .get_current_player()
+                .ok()
+                .map(|p| (p.id(), state.current_round())),
+            _ => None,
+        };
+        if current == self.turn.map(|t| (t.player_id, t.round)) {
+            return;
+        }
+
+        let now = self.now();
+        if let (Some(turn), Some(bank)) = (self.turn, self.control.time_bank) {
+            let overtime = (now - turn.started_at) - self.control.turn_limit.unwrap_or_default();
+            let bank = self.banks.entry(turn.player_id).or_insert(bank);
+            *bank = (*bank - overtime.max(Duration::zero())).max(Duration::zero());
+        }
+        self.turn = current.map(|(player_id, round)| TurnClock {
+            player_id,
+            round,
+            started_at: now,
+        });
+    }
+
+    /// Check for a timeout before an interaction, returning an `Err` if the current player timed out.
+    fn before_interaction(&mut self) -> Result<(), ActionError> {
+        match self.check_timeout()? {
+            Some(timeout) => Err(ActionError::FailedAction(FailedActionError::TimedOut {
+                player_id: timeout.player_id,
+            })),
+            None => Ok(()),
+        }
+    }
+}
+
+impl<G: Game, C: Clock> Game for Timed<G, C> {
+    type Rules = G::Rules;
+
+    fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError> {
+        self.before_interaction()?;
+        let result = self.game.execute_action(action);
+        self.sync_turn();
+        result
+    }
+
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        self.before_interaction()?;
+        let result = self.game.execute_out_of_turn_action(player_id, action);
+        self.sync_turn();
+        result
+    }
+
+    fn get_state(
+        &self,
+    ) -> &GameState<<<Self as Game>::Rules as GameRules>::VariantScore, Self::Rules> {
+        self.game.get_state()
+    }
+
+    fn quit_player(&mut self, player_id: usize) -> Result<(), GameError> {
+        let result = self.game.quit_player(player_id);
+        // a player who quits for good doesn't come back for the next round
+        if result.is_ok() {
+            self.forfeited.remove(&player_id);
+        }
+        self.sync_turn();
+        result
+    }
+
+    fn replace_player(&mut self, player_id: usize, new_player_id: usize) -> Result<(), GameError> {
+        let result = self.game.replace_player(player_id, new_player_id);
+        if result.is_ok() {
+            self.forfeited.remove(&player_id);
+        }
+        self.sync_turn();
+        result
+    }
+
+    fn add_player(&mut self, player_id: usize) -> Result<(), GameError> {
+        self.game.add_player(player_id)
+    }
+
+    fn rearrange_player_hand(
+        &mut self,
+        player_id: usize,
+        new_arrangement: Vec<CardData>,
+    ) -> Result<(), GameError> {
+        self.game.rearrange_player_hand(player_id, new_arrangement)
+    }
+
+    fn pause(&mut self) -> Result<(), GameError> {
+        // a turn which already ran out shouldn't be saved by pausing
+        self.check_timeout()
+            .map_err(GameError::TimeoutPolicyFailed)?;
+        self.game.pause()?;
+        self.paused_at = Some(self.clock.now());
+        Ok(())
+    }
+
+    fn resume(&mut self) -> Result<(), GameError> {
+        self.game.resume()?;
+        if let (Some(paused_at), Some(turn)) = (self.paused_at.take(), &mut self.turn) {
+            turn.started_at += self.clock.now() - paused_at;
+        }
+        Ok(())
+    }
+
+    fn next_round(&mut self) -> Result<(), GameError> {
+        // players who forfeited the round take their seats again for the next one
+        if self.game.get_state().phase() == GamePhase::RoundEnd {
+            while let Some(&player_id) = self.forfeited.first() {
+                self.game.replace_player(player_id, player_id)?;
+                self.forfeited.remove(&player_id);
+            }
+        }
+        let result = self.game.next_round();
+        self.sync_turn();
+        result
+    }
+}
+
+/// Whether it's still `turn` in `game`.
+fn is_turn<G: Game>(game: &G, turn: TurnClock) -> bool {
+    let state = game.get_state();
+    matches!(state.phase(), GamePhase::Draw | GamePhase::Play)
+        && state.current_round() == turn.round
+        && state
+            .get_current_player()
+            .is_ok_and(|p| p.id() == turn.player_id)
+}
+
+/// Finish the current player's turn by drawing from the deck (if they haven't drawn yet)
+/// and discarding the last card in their hand.
+fn draw_and_discard<G: Game>(game: &mut G) -> Result<(), ActionError> {
+    if game.get_state().phase() == GamePhase::Draw {
+        game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))?;
+    }
+    let hand_size = game.get_state().get_current_player()?.cards().len();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: hand_size.saturating_sub(1),
+        declare_going_out: None,
+    }))
+}
@@ -0,0 +1,72 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_history};
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        error::{ActionError, FailedActionError},
+        r#trait::Game,
+        variants::basic::game::BasicRummyGame,
+    },
+    wrappers::history::History,
+};
+
+#[test]
+fn only_current_player_can_act() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+
+    assert!(matches!(
+        game.execute_action_as(1, GameAction::DrawDeck(DrawDeckAction {})),
+        Err(ActionError::FailedAction(FailedActionError::NotYourTurn {
+            player_id: 1,
+            current_player_id: 0
+        }))
+    ));
+    game.execute_action_as(0, GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    assert_eq!(game.get_state().players()[0].cards().len(), 11);
+}
+
+#[test]
+fn history_records_actor() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    assert!(game
+        .execute_action_as(
+            1,
+            GameAction::Discard(DiscardAction {
+                card_index: 0,
+                declare_going_out: None,
+            })
+        )
+        .is_err());
+    game.execute_action_as(
+        0,
+        GameAction::Discard(DiscardAction {
+            card_index: 0,
+            declare_going_out: None,
+        }),
+    )
+    .unwrap();
+
+    let actors: Vec<_> = game.get_histories()[&1]
+        .iter()
+        .map(|e| (e.actor, e.successful))
+        .collect();
+    assert_eq!(
+        actors,
+        vec![(Some(0), true), (Some(1), false), (Some(0), true)]
+    );
+
+    // out of turn attempts are marked in records, and replay to the same outcome
+    let record = game.to_record().unwrap();
+    assert!(record.contains("\n! @1 discard 0\n"));
+    assert!(record.contains("\ndraw deck\n"));
+    let loaded = History::<BasicRummyGame>::from_record(&record).unwrap();
+    let loaded_actors: Vec<_> = loaded.get_histories()[&1]
+        .iter()
+        .map(|e| (e.actor, e.successful))
+        .collect();
+    assert_eq!(loaded_actors, actors);
+}
@@ -1,6 +1,7 @@
 use crate::common::fixtures::create_basic_game;
 use rummy::game::{
-    action::{DrawDeckAction, FormMeldAction, GameAction},
+    action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+    error::{ActionError, FailedActionError},
     r#trait::Game,
 };
 
@@ -29,3 +30,25 @@ fn basic_flow() {
     }))
     .unwrap();
 }
+
+#[test]
+fn discard_past_hand_fails() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let hand_size = game.get_state().get_current_player().unwrap().cards().len();
+    assert!(matches!(
+        game.execute_action(GameAction::Discard(DiscardAction {
+            card_index: hand_size,
+            declare_going_out: None,
+        })),
+        Err(ActionError::FailedAction(
+            FailedActionError::InvalidCardIndex
+        ))
+    ));
+    assert_eq!(
+        game.get_state().get_current_player().unwrap().cards().len(),
+        hand_size
+    );
+}
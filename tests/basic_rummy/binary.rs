@@ -0,0 +1,264 @@
+#![cfg(feature = "binary")]
+
+use crate::common::fixtures::{
+    create_basic_game, create_basic_game_with_history, play_basic_game_with_bots,
+};
+use rummy::{
+    binary::{BinaryError, BinaryGame, HistoryEncoding, FORMAT_VERSION},
+    cards::deck::DeckConfig,
+    game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+        r#trait::Game,
+        variants::basic::{config::BasicConfig, game::BasicRummyGame},
+    },
+    wrappers::history::History,
+};
+
+/// Plays a turn; the current player draws, sorts their hand, forms a meld and discards.
+fn play_turn(game: &mut impl Game) {
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.sort();
+    game.rearrange_player_hand(cur_player.id(), hand).unwrap();
+    let _ = game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }));
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+}
+
+/// Creates a history spanning multiple rounds.
+///
+/// Players are dealt no cards, so each round ends as soon as the first player draws and discards.
+fn multi_round_history() -> History<BasicRummyGame> {
+    let deck_config = DeckConfig {
+        shuffle_seed: Some(3),
+        ..DeckConfig::new()
+    };
+    let config = BasicConfig {
+        deal_amount: Some(0),
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let mut game = History::new(vec![0, 1, 2], config, deck_config).unwrap();
+    for _ in 0..3 {
+        game.next_round().unwrap();
+        game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+            .unwrap();
+        assert!(game
+            .execute_action(GameAction::Discard(DiscardAction {
+                card_index: 5,
+                declare_going_out: None,
+            }))
+            .is_err());
+        game.execute_action(GameAction::Discard(DiscardAction {
+            card_index: 0,
+            declare_going_out: None,
+        }))
+        .unwrap();
+    }
+    game.next_round().unwrap();
+    game.quit_player(2).unwrap();
+    game
+}
+
+/// Asserts that 2 histories have the same games and entries (with timestamps to the millisecond).
+fn assert_same_history(a: &History<BasicRummyGame>, b: &History<BasicRummyGame>) {
+    assert_eq!(a.get_game(), b.get_game());
+    assert_eq!(a.get_initial_round_states(), b.get_initial_round_states());
+    assert_eq!(a.get_histories().len(), b.get_histories().len());
+    for (round, entries) in a.get_histories() {
+        let other_entries = &b.get_histories()[round];
+        assert_eq!(entries.len(), other_entries.len());
+        for (entry, other) in entries.iter().zip(other_entries) {
+            assert_eq!(entry.entry, other.entry);
+            assert_eq!(entry.successful, other.successful);
+            assert_eq!(entry.actor, other.actor);
+            assert_eq!(entry.undone, other.undone);
+            assert_eq!(entry.time.timestamp_millis(), other.time.timestamp_millis());
+        }
+    }
+}
+
+/// The history stored in the `tests/fixtures/binary/v1_*.bin` fixtures.
+fn fixture_history() -> History<BasicRummyGame> {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    play_turn(&mut game);
+    assert!(game
+        .execute_action(GameAction::Discard(DiscardAction {
+            card_index: 100,
+            declare_going_out: None,
+        }))
+        .is_err());
+    play_turn(&mut game);
+    game.add_player(2).unwrap();
+    game
+}
+
+/// Asserts that a history decoded from a fixture matches the expected one (without comparing timestamps).
+fn assert_fixture_history(decoded: &History<BasicRummyGame>, expected: &History<BasicRummyGame>) {
+    assert_eq!(decoded.get_game(), expected.get_game());
+    assert_eq!(
+        decoded.get_initial_round_states(),
+        expected.get_initial_round_states()
+    );
+    let entries = &decoded.get_histories()[&1];
+    let expected_entries = &expected.get_histories()[&1];
+    assert_eq!(entries.len(), expected_entries.len());
+    for (entry, expected_entry) in entries.iter().zip(expected_entries) {
+        assert_eq!(entry.entry, expected_entry.entry);
+        assert_eq!(entry.successful, expected_entry.successful);
+        assert_eq!(entry.actor, expected_entry.actor);
+        assert!(!entry.undone);
+    }
+}
+
+#[test]
+fn decode_fixtures() {
+    let game = include_bytes!("../fixtures/binary/v1_game.bin");
+    let full = include_bytes!("../fixtures/binary/v1_history_full.bin");
+    let log = include_bytes!("../fixtures/binary/v1_history_log.bin");
+    assert_eq!(game[3], FORMAT_VERSION);
+
+    let expected = fixture_history();
+    let game = BasicRummyGame::from_binary(game).unwrap();
+    assert_eq!(&game, expected.get_game());
+    assert_eq!(game.get_state().check_invariants(), Ok(()));
+    assert_fixture_history(&History::from_binary(full).unwrap(), &expected);
+    assert_fixture_history(&History::from_binary(log).unwrap(), &expected);
+}
+
+#[test]
+fn decode_later_round_fixture() {
+    let game = include_bytes!("../fixtures/binary/v1_game_round_2.bin");
+    let game = BasicRummyGame::from_binary(game).unwrap();
+    let expected = play_basic_game_with_bots(2, 1);
+    assert_eq!(game.get_state().current_round(), 2);
+    assert_eq!(game, expected);
+}
+
+#[test]
+fn game_round_trip() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    play_turn(&mut game);
+
+    let bytes = game.to_binary();
+    assert_eq!(BasicRummyGame::from_binary(&bytes).unwrap(), game);
+
+    // 52 cards at a byte each, plus a small amount of overhead
+    assert!(bytes.len() < 100, "encoded game was {} bytes", bytes.len());
+}
+
+#[test]
+fn multi_round_game_round_trip() {
+    let history = multi_round_history();
+    let game = history.get_game();
+    assert_eq!(game.get_state().round_scores().len(), 3);
+    assert_eq!(
+        &BasicRummyGame::from_binary(&game.to_binary()).unwrap(),
+        game
+    );
+}
+
+#[test]
+fn full_history_round_trip() {
+    let history = multi_round_history();
+    let bytes = history.to_binary(HistoryEncoding::Full).unwrap();
+    assert_same_history(&History::from_binary(&bytes).unwrap(), &history);
+}
+
+#[test]
+fn seed_and_log_history_round_trip() {
+    let history = multi_round_history();
+    let bytes = history.to_binary(HistoryEncoding::SeedAndLog).unwrap();
+    assert_same_history(&History::from_binary(&bytes).unwrap(), &history);
+
+    let full_bytes = history.to_binary(HistoryEncoding::Full).unwrap();
+    assert!(bytes.len() < full_bytes.len() / 3);
+
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    play_turn(&mut game);
+    play_turn(&mut game);
+    // undone entries are kept, but not replayed
+    game.set_undo_enabled(true);
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.reverse();
+    game.rearrange_player_hand(cur_player.id(), hand).unwrap();
+    game.undo().unwrap();
+    let bytes = game.to_binary(HistoryEncoding::SeedAndLog).unwrap();
+    assert_same_history(&History::from_binary(&bytes).unwrap(), &game);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn binary_is_smaller_than_json() {
+    let history = multi_round_history();
+    let json = serde_json::to_vec(&history).unwrap();
+    let full = history.to_binary(HistoryEncoding::Full).unwrap();
+    let log = history.to_binary(HistoryEncoding::SeedAndLog).unwrap();
+    assert!(full.len() * 10 < json.len());
+    assert!(log.len() * 50 < json.len());
+}
+
+#[test]
+fn unseeded_history_is_encoded_with_its_random_seed() {
+    let config = BasicConfig {
+        deal_amount: None,
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let mut history = History::new(vec![0, 1], config, DeckConfig::new()).unwrap();
+    history.next_round().unwrap();
+
+    let bytes = history.to_binary(HistoryEncoding::SeedAndLog).unwrap();
+    let decoded = History::<BasicRummyGame>::from_binary(&bytes).unwrap();
+    assert_eq!(decoded.get_state(), history.get_state());
+}
+
+#[test]
+fn invalid_data_fails() {
+    let history = multi_round_history();
+    let bytes = history.to_binary(HistoryEncoding::SeedAndLog).unwrap();
+
+    assert!(matches!(
+        History::<BasicRummyGame>::from_binary(&bytes[..bytes.len() - 1]),
+        Err(BinaryError::UnexpectedEnd)
+    ));
+    assert!(matches!(
+        History::<BasicRummyGame>::from_binary(b"not a game"),
+        Err(BinaryError::InvalidMagic)
+    ));
+    assert!(matches!(
+        BasicRummyGame::from_binary(&bytes),
+        Err(BinaryError::InvalidValue(_))
+    ));
+
+    for version in [0, FORMAT_VERSION + 1] {
+        let mut unsupported = bytes.clone();
+        unsupported[3] = version;
+        assert!(matches!(
+            History::<BasicRummyGame>::from_binary(&unsupported),
+            Err(BinaryError::UnsupportedVersion(v)) if v == version
+        ));
+    }
+
+    let mut trailing = bytes.clone();
+    trailing.push(0);
+    assert!(matches!(
+        History::<BasicRummyGame>::from_binary(&trailing),
+        Err(BinaryError::TrailingBytes)
+    ));
+}
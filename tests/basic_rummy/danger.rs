@@ -0,0 +1,191 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use rummy::{
+    cards::{card::CardData, deck::DeckConfig, suit_rank::Rank},
+    game::{
+        action::{DiscardAction, DrawDeckAction, DrawDiscardPileAction, GameAction},
+        danger::{discard_dangers, DangerReason, DiscardDanger},
+        r#trait::Game,
+        state::GamePhase,
+        tracker::CardTracker,
+        variants::basic::game::BasicRummyGame,
+    },
+    simulation::bots::{Bot, GreedyBot},
+    wrappers::observed::Observed,
+};
+use std::sync::{Arc, Mutex};
+
+/// Get the dangers for `player_id`'s hand, from a tracker which has only seen the table.
+fn dangers<G: Game>(game: &G, player_id: usize) -> Vec<DiscardDanger> {
+    let mut tracker = CardTracker::new(player_id, game.get_state().deck().config());
+    tracker.sync(&game.get_state().view_for(Some(player_id)));
+    discard_dangers(game, &tracker)
+}
+
+fn draw_and_discard(game: &mut impl Game, card_index: usize) {
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index,
+        declare_going_out: None,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn every_card_in_hand_gets_a_danger() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let hand = game.get_state().players()[0].cards().clone();
+    let dangers = dangers(&game, 0);
+    assert_eq!(dangers.len(), hand.len());
+    for (i, danger) in dangers.iter().enumerate() {
+        assert_eq!(danger.card_index, i);
+        assert_eq!(danger.card, hand[i].data());
+        assert!((0.0..=1.0).contains(&danger.score));
+        // with one pack, every card has 2 neighbours of each side in its suit (except near the ends), and 3 of its rank
+        assert!(danger
+            .reasons
+            .iter()
+            .any(|reason| matches!(reason, DangerReason::LivePartners { total: 5..=7, .. })));
+    }
+
+    let mut tracker = CardTracker::new(5, game.get_state().deck().config());
+    tracker.sync(&game.get_state().view_for(Some(5)));
+    assert!(discard_dangers(&game, &tracker).is_empty());
+}
+
+#[test]
+fn picked_up_neighbours_are_risky() {
+    let mut game = Observed::new(create_basic_game(2).unwrap());
+    let deck_config = game.get_state().deck().config().clone();
+    let tracker = Arc::new(Mutex::new(CardTracker::new(0, &deck_config)));
+    let observer = tracker.clone();
+    game.subscribe(move |event| observer.lock().unwrap().observe(event));
+    game.next_round().unwrap();
+
+    // player 0 discards a card which player 1 picks up, then it's player 0's turn again
+    let picked_up = game.get_state().players()[0].cards()[0].data();
+    draw_and_discard(&mut game, 0);
+    game.execute_action(GameAction::DrawDiscardPile(DrawDiscardPileAction {
+        count: Some(1),
+    }))
+    .unwrap();
+    let card_index = game.get_state().players()[1]
+        .cards()
+        .iter()
+        .position(|c| c.data() != picked_up)
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let mut tracker = tracker.lock().unwrap();
+    tracker.sync(&game.get_state().view_for(Some(0)));
+    let is_partner = |card: &CardData| {
+        card.rank == picked_up.rank
+            || (card.suit == picked_up.suit
+                && (card.rank as u8).abs_diff(picked_up.rank as u8) <= 2)
+    };
+    let untracked = dangers(&game, 0);
+    for (danger, untracked) in discard_dangers(&game, &tracker).iter().zip(&untracked) {
+        let holds = danger
+            .reasons
+            .contains(&DangerReason::NextPlayerHolds { card: picked_up });
+        assert_eq!(holds, is_partner(&danger.card));
+        if holds {
+            assert!(danger.score > untracked.score);
+        }
+    }
+}
+
+#[test]
+fn cards_which_extend_melds_are_risky() {
+    let mut game = create_basic_game_with_config(3, Some(4), None, None).unwrap();
+    game.next_round().unwrap();
+    let mut layoffs = 0;
+    for _ in 0..300 {
+        let phase = game.get_state().phase();
+        if !matches!(phase, GamePhase::Draw | GamePhase::Play) {
+            break;
+        }
+        if phase == GamePhase::Play {
+            let player_id = game.get_state().get_current_player().unwrap().id();
+            let dangers = dangers(&game, player_id);
+            for danger in &dangers {
+                let extends = danger
+                    .reasons
+                    .iter()
+                    .any(|reason| matches!(reason, DangerReason::ExtendsMeld { .. }));
+                if extends {
+                    assert!(danger.score >= 0.8);
+                }
+            }
+            // the greedy bot only lays off cards which are known to extend a meld
+            if let GameAction::LayOff(action) = GreedyBot.next_action(&game, player_id) {
+                layoffs += 1;
+                assert!(dangers[action.card_index]
+                    .reasons
+                    .iter()
+                    .any(|reason| matches!(reason, DangerReason::ExtendsMeld { .. })));
+            }
+        }
+        let player_id = game.get_state().get_current_player().unwrap().id();
+        let action = GreedyBot.next_action(&game, player_id);
+        game.execute_action(action).unwrap();
+    }
+    assert!(layoffs > 0);
+}
+
+#[test]
+fn wildcards_and_extra_packs_count() {
+    let deck_config = DeckConfig {
+        shuffle_seed: Some(1),
+        pack_count: 2,
+        high_rank: None,
+        wildcard_rank: Some(Rank::Two),
+    };
+    let mut game: BasicRummyGame =
+        create_basic_game_with_config(2, None, None, Some(deck_config)).unwrap();
+    game.next_round().unwrap();
+
+    for danger in dangers(&game, 0) {
+        if danger.card.rank == Rank::Two {
+            assert!(danger.reasons.contains(&DangerReason::Wildcard));
+            assert!(danger.score >= 0.9);
+        } else {
+            // every partner has 2 copies, and the card's own second copy is a partner too
+            assert!(danger.reasons.iter().any(|reason| matches!(
+                reason,
+                DangerReason::LivePartners { total, .. } if total % 2 == 1 && *total >= 9
+            )));
+        }
+    }
+}
+
+#[test]
+fn out_of_date_trackers_give_valid_dangers() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    // the tracker never saw the hand being dealt, so it thinks the hand's cards are unseen
+    let tracker = CardTracker::new(0, game.get_state().deck().config());
+    let dangers = discard_dangers(&game, &tracker);
+    assert_eq!(dangers.len(), game.get_state().players()[0].cards().len());
+    for danger in dangers {
+        assert!((0.0..=1.0).contains(&danger.score));
+        for reason in danger.reasons {
+            if let DangerReason::LivePartners { unseen, total } = reason {
+                assert!(unseen <= total);
+            }
+        }
+    }
+}
@@ -64,3 +64,60 @@ fn deal_10_players_two_decks_fails() {
     let game = create_basic_game_with_config(10, None, None, Some(deck_config));
     assert!(matches!(game, Err(GameSetupError::NotEnoughCards)));
 }
+
+#[test]
+fn rounds_are_dealt_from_the_seed() {
+    use rummy::game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        variants::basic::{config::BasicConfig, game::BasicRummyGame},
+    };
+
+    /// Plays 3 rounds (ending each immediately), and returns the stock at the start of each.
+    fn round_stocks(shuffle_seed: u64) -> Vec<Vec<rummy::cards::card::CardData>> {
+        let deck_config = DeckConfig {
+            shuffle_seed: Some(shuffle_seed),
+            ..DeckConfig::new()
+        };
+        let config = BasicConfig {
+            deal_amount: Some(0),
+            draw_deck_amount: None,
+            draw_discard_pile_amount: None,
+            quit_hand_policy: None,
+        };
+        let mut game = BasicRummyGame::new(vec![0, 1], config, deck_config).unwrap();
+        let mut stocks = Vec::new();
+        for _ in 0..3 {
+            game.next_round().unwrap();
+            stocks.push(
+                game.get_state()
+                    .deck()
+                    .stock()
+                    .iter()
+                    .map(|c| c.data())
+                    .collect(),
+            );
+            game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+                .unwrap();
+            game.execute_action(GameAction::Discard(DiscardAction {
+                card_index: 0,
+                declare_going_out: None,
+            }))
+            .unwrap();
+        }
+        stocks
+    }
+
+    let stocks = round_stocks(3);
+    assert_ne!(stocks[0], stocks[1]);
+    assert_ne!(stocks[1], stocks[2]);
+    assert_eq!(stocks, round_stocks(3));
+    assert_ne!(stocks, round_stocks(4));
+}
+
+#[test]
+fn unseeded_game_gets_a_random_seed() {
+    let mut game = create_basic_game_with_config(2, None, None, None).unwrap();
+    game.next_round().unwrap();
+    let seed = game.get_state().deck().config().shuffle_seed;
+    assert!(seed.is_some_and(|seed| seed != 0));
+}
@@ -21,6 +21,7 @@ fn override_draw_multiple_cards_from_deck() {
         deal_amount: None,
         draw_deck_amount: Some(5),
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(2, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
@@ -57,6 +58,7 @@ fn override_draw_multiple_from_discard_pile() {
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: Some(DrawDiscardPileOverride::Constant(2)),
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(2, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
@@ -99,6 +101,7 @@ fn override_draw_chosen_amount_from_discard_pile() {
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: Some(DrawDiscardPileOverride::PlayerChooses),
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(2, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
@@ -148,6 +151,7 @@ fn override_draw_whole_discard_pile() {
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: Some(DrawDiscardPileOverride::WholePile),
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(2, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
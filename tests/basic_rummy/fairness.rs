@@ -0,0 +1,204 @@
+#![cfg(feature = "fair-shuffle")]
+
+use crate::common::fixtures::create_basic_game_with_history;
+use rummy::{
+    fairness::{
+        verify_player_entropy, verify_round, FairShuffleGame, FairnessError, ShuffleCommitment,
+        ShuffleReveal,
+    },
+    game::{error::GameError, r#trait::Game},
+    wrappers::record::RecordError,
+};
+
+/// The entropy sent by each player, in seat order.
+fn player_entropy() -> Vec<Vec<u8>> {
+    vec![b"alice".to_vec(), b"bob".to_vec()]
+}
+
+fn reveal(server_entropy: &[u8]) -> ShuffleReveal {
+    ShuffleReveal::new(server_entropy.to_vec(), player_entropy())
+}
+
+#[test]
+fn committed_round_verifies() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    let commitment = ShuffleCommitment::new(b"server secret");
+    let reveal = reveal(b"server secret");
+    game.commit_shuffle(&reveal).unwrap();
+    game.next_round().unwrap();
+
+    assert_eq!(game.get_state().deck().seed(), Some(reveal.seed()));
+    verify_round(&game, 1, &commitment, &reveal, &player_entropy()).unwrap();
+    verify_player_entropy(&game, 1, 0, b"alice", &reveal).unwrap();
+    verify_player_entropy(&game, 1, 1, b"bob", &reveal).unwrap();
+}
+
+#[test]
+fn commitment_is_fixed_before_player_entropy() {
+    // the commitment is published before any player entropy exists...
+    let commitment = ShuffleCommitment::new(b"server secret");
+
+    // ... so whatever the players send, the server can't change it
+    let player_entropies = [
+        vec![b"alice".to_vec(), b"bob".to_vec()],
+        vec![b"carol".to_vec(), b"dave".to_vec()],
+    ];
+    let mut seeds = Vec::new();
+    for player_entropy in player_entropies {
+        let reveal = ShuffleReveal::new(b"server secret".to_vec(), player_entropy.clone());
+        assert_eq!(reveal.commitment(), commitment);
+
+        let mut game = create_basic_game_with_history(2).unwrap();
+        game.commit_shuffle(&reveal).unwrap();
+        game.next_round().unwrap();
+        verify_round(&game, 1, &commitment, &reveal, &player_entropy).unwrap();
+        seeds.push(reveal.seed());
+    }
+
+    // while the players' entropy still changes the shuffle
+    assert_ne!(seeds[0], seeds[1]);
+}
+
+#[test]
+fn different_entropy_deals_differently() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    let mut other_game = game.clone();
+    game.commit_shuffle(&reveal(b"server secret")).unwrap();
+    other_game.commit_shuffle(&reveal(b"other secret")).unwrap();
+    game.next_round().unwrap();
+    other_game.next_round().unwrap();
+
+    assert_ne!(
+        game.get_state().deck().stock(),
+        other_game.get_state().deck().stock()
+    );
+}
+
+#[test]
+fn wrong_reveal_fails() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    let commitment = ShuffleCommitment::new(b"server secret");
+    game.commit_shuffle(&reveal(b"server secret")).unwrap();
+    game.next_round().unwrap();
+
+    // the revealed entropy must match the commitment...
+    assert!(matches!(
+        verify_round(
+            &game,
+            1,
+            &commitment,
+            &reveal(b"other secret"),
+            &player_entropy()
+        ),
+        Err(FairnessError::CommitmentMismatch)
+    ));
+
+    // ... and the round must have been dealt from it
+    let other_reveal = reveal(b"other secret");
+    assert!(matches!(
+        verify_round(
+            &game,
+            1,
+            &other_reveal.commitment(),
+            &other_reveal,
+            &player_entropy()
+        ),
+        Err(FairnessError::DealMismatch { player_id: 0 })
+    ));
+    assert!(matches!(
+        verify_round(
+            &game,
+            2,
+            &commitment,
+            &reveal(b"server secret"),
+            &player_entropy()
+        ),
+        Err(FairnessError::UnknownRound(2))
+    ));
+}
+
+#[test]
+fn dropped_or_reordered_player_entropy_fails() {
+    let commitment = ShuffleCommitment::new(b"server secret");
+    let tampered = [
+        // a dropped entry
+        vec![b"alice".to_vec()],
+        // reordered entries
+        vec![b"bob".to_vec(), b"alice".to_vec()],
+    ];
+    for player_entropy_reveal in tampered {
+        let reveal = ShuffleReveal::new(b"server secret".to_vec(), player_entropy_reveal);
+        let mut game = create_basic_game_with_history(2).unwrap();
+        game.commit_shuffle(&reveal).unwrap();
+        game.next_round().unwrap();
+
+        // the deal matches the reveal, but the reveal doesn't match what the players sent
+        assert!(matches!(
+            verify_round(&game, 1, &commitment, &reveal, &player_entropy()),
+            Err(FairnessError::EntropyCountMismatch { .. }
+                | FairnessError::PlayerEntropyMismatch { .. })
+        ));
+        assert!(
+            verify_player_entropy(&game, 1, 0, b"alice", &reveal).is_err()
+                || verify_player_entropy(&game, 1, 1, b"bob", &reveal).is_err()
+        );
+    }
+}
+
+#[test]
+fn extra_player_entropy_fails() {
+    let commitment = ShuffleCommitment::new(b"server secret");
+    let reveal = ShuffleReveal::new(
+        b"server secret".to_vec(),
+        vec![b"alice".to_vec(), b"bob".to_vec(), b"server".to_vec()],
+    );
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.commit_shuffle(&reveal).unwrap();
+    game.next_round().unwrap();
+
+    assert!(matches!(
+        verify_round(&game, 1, &commitment, &reveal, &player_entropy()),
+        Err(FairnessError::EntropyCountMismatch {
+            expected: 2,
+            found: 3
+        })
+    ));
+    assert!(matches!(
+        verify_player_entropy(&game, 1, 0, b"alice", &reveal),
+        Err(FairnessError::EntropyCountMismatch { .. })
+    ));
+    assert!(matches!(
+        verify_player_entropy(&game, 1, 2, b"server", &reveal),
+        Err(FairnessError::EntropyCountMismatch { .. })
+    ));
+}
+
+#[test]
+fn commit_after_dealing_fails() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    assert!(matches!(
+        game.commit_shuffle(&reveal(b"server secret")),
+        Err(GameError::WrongGamePhase)
+    ));
+}
+
+#[test]
+fn commitment_hex_round_trips() {
+    let commitment = ShuffleCommitment::new(b"server secret");
+    let hex = commitment.to_string();
+    assert_eq!(hex.len(), 64);
+    assert_eq!(hex.parse::<ShuffleCommitment>().unwrap(), commitment);
+    assert!(matches!(
+        "not a commitment".parse::<ShuffleCommitment>(),
+        Err(FairnessError::InvalidCommitment(_))
+    ));
+}
+
+#[test]
+fn committed_round_cannot_be_recorded() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.commit_shuffle(&reveal(b"server secret")).unwrap();
+    game.next_round().unwrap();
+    assert!(matches!(game.to_record(), Err(RecordError::UnseededDeck)));
+}
@@ -0,0 +1,143 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use rummy::{
+    cards::{meld::MeldKind, notation::parse_cards},
+    game::{
+        action::{DiscardAction, DrawDeckAction, DrawDiscardPileAction, GameAction, LayOffAction},
+        hints::{hints, Hint, HintReason},
+        r#trait::Game,
+        state::GamePhase,
+        tracker::CardTracker,
+    },
+    simulation::bots::{Bot, GreedyBot},
+};
+
+/// Get the hints for the current player, from a tracker which has only seen the table.
+fn current_hints<G: Game>(game: &G) -> Vec<Hint> {
+    let player_id = game.get_state().get_current_player().unwrap().id();
+    let mut tracker = CardTracker::new(player_id, game.get_state().deck().config());
+    tracker.sync(&game.get_state().view_for(Some(player_id)));
+    hints(game, &tracker)
+}
+
+#[test]
+fn every_hint_can_be_executed() {
+    let mut game = create_basic_game_with_config(3, Some(4), None, None).unwrap();
+    game.next_round().unwrap();
+    let mut reasons = Vec::new();
+    for _ in 0..300 {
+        if !matches!(game.get_state().phase(), GamePhase::Draw | GamePhase::Play) {
+            break;
+        }
+        let suggested = current_hints(&game);
+        assert!(!suggested.is_empty());
+        for hint in &suggested {
+            let mut game = game.clone();
+            assert!(
+                game.execute_action(hint.action.clone()).is_ok(),
+                "couldn't {hint}"
+            );
+            reasons.push(std::mem::discriminant(&hint.reason));
+        }
+
+        let player_id = game.get_state().get_current_player().unwrap().id();
+        let action = GreedyBot.next_action(&game, player_id);
+        game.execute_action(action).unwrap();
+    }
+    reasons.sort_by_key(|reason| format!("{reason:?}"));
+    reasons.dedup();
+    // drawing from the deck and every kind of play phase hint came up
+    // (taking from the discard pile depends on the deal)
+    assert!(reasons.len() >= 4);
+}
+
+#[test]
+fn draw_hints_end_with_the_deck() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    let suggested = current_hints(&game);
+    assert_eq!(
+        suggested.last().unwrap().action,
+        GameAction::DrawDeck(DrawDeckAction {})
+    );
+    if let [take, _] = suggested.as_slice() {
+        assert!(matches!(
+            take.reason,
+            HintReason::CompletesMeld { .. } | HintReason::ExtendsMeld { .. }
+        ));
+    }
+
+    // there are no hints for a player whose turn it isn't
+    let mut tracker = CardTracker::new(1, game.get_state().deck().config());
+    tracker.sync(&game.get_state().view_for(Some(1)));
+    assert!(hints(&game, &tracker).is_empty());
+}
+
+#[test]
+fn discard_hints_cover_the_hand() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let hand_size = game.get_state().players()[0].cards().len();
+    let mut discarded: Vec<_> = current_hints(&game)
+        .into_iter()
+        .filter_map(|hint| match hint.action {
+            GameAction::Discard(DiscardAction { card_index, .. }) => Some(card_index),
+            _ => None,
+        })
+        .collect();
+    discarded.sort();
+    assert_eq!(discarded, (0..hand_size).collect::<Vec<_>>());
+}
+
+#[test]
+fn hints_explain_themselves() {
+    let cards = parse_cards("7♥ 5♥ 6♥ Q♠ K♦").unwrap();
+    let take = Hint {
+        action: GameAction::DrawDiscardPile(DrawDiscardPileAction { count: Some(1) }),
+        reason: HintReason::CompletesMeld {
+            card: cards[0],
+            kind: MeldKind::Run,
+            cards: cards[1..3].iter().chain(&cards[..1]).copied().collect(),
+        },
+    };
+    assert_eq!(
+        take.to_string(),
+        "take the 7♥ from the discard pile: completes a run [5♥ 6♥ 7♥]"
+    );
+
+    let lay_off = Hint {
+        action: GameAction::LayOff(LayOffAction {
+            card_index: 0,
+            target_player_index: 1,
+            target_meld_index: 0,
+        }),
+        reason: HintReason::LaysOff {
+            card: cards[3],
+            player_id: 2,
+            kind: MeldKind::Set,
+        },
+    };
+    assert_eq!(lay_off.to_string(), "lay off Q♠ onto player 2's set");
+
+    let discard = |danger| Hint {
+        action: GameAction::Discard(DiscardAction {
+            card_index: 4,
+            declare_going_out: None,
+        }),
+        reason: HintReason::Deadwood {
+            card: cards[4],
+            points: 10,
+            danger,
+        },
+    };
+    assert_eq!(
+        discard(0.1).to_string(),
+        "discard K♦: 10 points of deadwood"
+    );
+    assert_eq!(
+        discard(0.8).to_string(),
+        "discard K♦: 10 points of deadwood, though it may help the next player"
+    );
+}
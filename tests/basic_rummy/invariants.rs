@@ -0,0 +1,175 @@
+#[cfg(feature = "serde")]
+use crate::common::fixtures::create_basic_game;
+use crate::common::fixtures::{create_basic_game_with_config, play_basic_history_with_bots};
+#[cfg(feature = "serde")]
+use rummy::{
+    cards::{card::CardData, meld::MeldError},
+    game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+        error::InvariantError,
+        variants::basic::game::BasicRummyGame,
+    },
+};
+use rummy::{
+    cards::{deck::DeckConfig, suit_rank::Rank},
+    game::{r#trait::Game, state::GamePhase},
+    simulation::bots::{Bot, GreedyBot},
+};
+#[cfg(feature = "serde")]
+use serde_json::{json, Value};
+
+#[test]
+fn played_games_keep_invariants() {
+    let history = play_basic_history_with_bots(3, 2);
+    for game in history.get_initial_round_states().values() {
+        assert_eq!(game.get_state().check_invariants(), Ok(()));
+    }
+    assert_eq!(history.get_state().check_invariants(), Ok(()));
+
+    // wildcards can be laid off and swapped out of melds, which must stay valid
+    let deck_config = DeckConfig {
+        shuffle_seed: Some(2),
+        pack_count: 2,
+        high_rank: None,
+        wildcard_rank: Some(Rank::Joker),
+    };
+    let mut game = create_basic_game_with_config(4, None, None, Some(deck_config)).unwrap();
+    game.next_round().unwrap();
+    for _ in 0..300 {
+        if !matches!(game.get_state().phase(), GamePhase::Draw | GamePhase::Play) {
+            break;
+        }
+        let player_id = game.get_state().get_current_player().unwrap().id();
+        let action = GreedyBot.next_action(&game, player_id);
+        game.execute_action(action).unwrap();
+        assert_eq!(game.get_state().check_invariants(), Ok(()));
+    }
+
+    game.quit_player(1).unwrap();
+    assert_eq!(game.get_state().check_invariants(), Ok(()));
+}
+
+/// A serialized game where player 0 has formed a set of 3s, and it's player 1's turn to draw.
+#[cfg(feature = "serde")]
+fn serialized_game() -> Value {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.sort();
+    game.rearrange_player_hand(cur_player.id(), hand).unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }))
+    .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    serde_json::to_value(game).unwrap()
+}
+
+#[cfg(feature = "serde")]
+fn check(game: Value) -> Result<(), InvariantError> {
+    let game: BasicRummyGame = serde_json::from_value(game).unwrap();
+    game.get_state().check_invariants()
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn unedited_game_is_consistent() {
+    assert_eq!(check(serialized_game()), Ok(()));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn missing_and_duplicate_cards_are_caught() {
+    let mut game = serialized_game();
+    let removed = game["state"]["deck"]["stock"]
+        .as_array_mut()
+        .unwrap()
+        .remove(0);
+    let card: CardData = serde_json::from_value(removed.clone()).unwrap();
+    assert_eq!(
+        check(game.clone()),
+        Err(InvariantError::CardsNotConserved {
+            card,
+            expected: 1,
+            found: 0,
+        })
+    );
+
+    game["state"]["players"][1]["cards"]
+        .as_array_mut()
+        .unwrap()
+        .extend([removed.clone(), removed]);
+    assert_eq!(
+        check(game),
+        Err(InvariantError::CardsNotConserved {
+            card,
+            expected: 1,
+            found: 2,
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn invalid_melds_are_caught() {
+    // swap a card of the set of 3s with one from player 0's hand, so every card is still accounted for
+    let mut game = serialized_game();
+    let player = &mut game["state"]["players"][0];
+    let hand_card = player["cards"][0].clone();
+    let meld_card = std::mem::replace(&mut player["melds"][0]["Set"]["cards"][1], hand_card);
+    player["cards"][0] = meld_card;
+    assert_eq!(
+        check(game),
+        Err(InvariantError::InvalidMeld {
+            player_id: 0,
+            meld_index: 0,
+            error: MeldError::InvalidSet,
+        })
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn inconsistent_turns_are_caught() {
+    let mut game = serialized_game();
+    game["state"]["current_player"] = json!(2);
+    assert_eq!(
+        check(game),
+        Err(InvariantError::CurrentPlayerOutOfRange {
+            current: 2,
+            player_count: 2,
+        })
+    );
+
+    let mut game = serialized_game();
+    game["state"]["players"][1]["active"] = json!(false);
+    assert_eq!(
+        check(game),
+        Err(InvariantError::InactiveCurrentPlayer { player_id: 1 })
+    );
+
+    let mut game = serialized_game();
+    game["state"]["phase"] = json!("RoundEnd");
+    assert_eq!(check(game), Err(InvariantError::RoundEndWithoutWinner));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn edited_games_can_still_be_played() {
+    let mut game = serialized_game();
+    game["state"]["deck"]["stock"]
+        .as_array_mut()
+        .unwrap()
+        .remove(0);
+    let mut game: BasicRummyGame = serde_json::from_value(game).unwrap();
+    assert!(game.get_state().check_invariants().is_err());
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+}
@@ -1,9 +1,24 @@
+pub mod acting_player;
 pub mod basic_flow;
+pub mod binary;
+pub mod danger;
 pub mod default_dealing;
 pub mod drawing;
+pub mod fairness;
 pub mod form_meld;
 pub mod hand_rearrangement;
+pub mod hints;
+pub mod invariants;
 pub mod override_dealing;
+pub mod pausing;
 pub mod player_ops;
+pub mod player_view;
+pub mod ratings;
 pub mod serialization;
+pub mod server;
+pub mod session;
+pub mod simulation;
+pub mod stats;
+pub mod tournament;
+pub mod tracker;
 pub mod wrappers;
@@ -7,6 +7,7 @@ fn override_deal_amount_is_correct() {
         deal_amount: Some(20),
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(2, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
@@ -21,6 +22,7 @@ fn override_is_correct_with_many_players() {
         deal_amount: Some(1),
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     let mut game = create_basic_game_with_config(20, None, Some(game_config), None).unwrap();
     game.next_round().unwrap();
@@ -35,6 +37,7 @@ fn override_fails_if_not_enough_cards() {
         deal_amount: Some(1),
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     let game = create_basic_game_with_config(27, None, Some(game_config), None);
     assert!(matches!(game, Err(GameSetupError::NotEnoughCards)));
@@ -46,6 +49,7 @@ fn override_fails_if_one_player() {
         deal_amount: Some(1),
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     let game = create_basic_game_with_config(1, None, Some(game_config), None);
     assert!(matches!(game, Err(GameSetupError::TooFewPlayers)));
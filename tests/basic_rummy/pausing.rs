@@ -0,0 +1,93 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_history};
+use rummy::{
+    game::{
+        action::{DrawDeckAction, GameAction, GameInteractions},
+        error::{ActionError, FailedActionError, GameError},
+        r#trait::Game,
+        state::GamePhase,
+        variants::basic::game::BasicRummyGame,
+    },
+    wrappers::history::History,
+};
+
+#[test]
+fn actions_fail_while_paused() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.pause().unwrap();
+    assert!(game.get_state().is_paused());
+
+    for result in [
+        game.execute_action(GameAction::DrawDeck(DrawDeckAction {})),
+        game.execute_action_as(1, GameAction::DrawDeck(DrawDeckAction {})),
+    ] {
+        assert!(matches!(
+            result,
+            Err(ActionError::FailedAction(FailedActionError::GamePaused))
+        ));
+    }
+
+    game.resume().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    assert_eq!(game.get_state().phase(), GamePhase::Play);
+}
+
+#[test]
+fn next_round_fails_while_paused() {
+    let mut game = create_basic_game(2).unwrap();
+    game.pause().unwrap();
+    assert!(matches!(game.next_round(), Err(GameError::GamePaused)));
+    game.resume().unwrap();
+    game.next_round().unwrap();
+}
+
+#[test]
+fn pause_and_resume_twice_fail() {
+    let mut game = create_basic_game(2).unwrap();
+    assert!(matches!(game.resume(), Err(GameError::NotPaused)));
+    game.pause().unwrap();
+    assert!(matches!(game.pause(), Err(GameError::GamePaused)));
+}
+
+#[test]
+fn players_can_quit_while_paused() {
+    let mut game = create_basic_game(3).unwrap();
+    game.next_round().unwrap();
+    game.pause().unwrap();
+    game.quit_player(0).unwrap();
+    assert_eq!(game.get_state().get_current_player().unwrap().id(), 1);
+}
+
+#[test]
+fn pause_and_resume_are_recorded() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    game.pause().unwrap();
+    assert!(game
+        .execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .is_err());
+    game.resume().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let entries: Vec<_> = game.get_histories()[&1]
+        .iter()
+        .map(|e| (e.entry.clone(), e.successful))
+        .collect();
+    let draw = GameInteractions::Action(GameAction::DrawDeck(DrawDeckAction {}));
+    assert_eq!(
+        entries,
+        [
+            (GameInteractions::Pause, true),
+            (draw.clone(), false),
+            (GameInteractions::Resume, true),
+            (draw, true),
+        ]
+    );
+
+    let record = game.to_record().unwrap();
+    assert!(record.lines().any(|l| l == "pause"));
+    let parsed = History::<BasicRummyGame>::from_record(&record).unwrap();
+    assert_eq!(parsed.get_game(), game.get_game());
+}
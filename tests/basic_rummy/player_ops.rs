@@ -1,5 +1,29 @@
-use crate::common::fixtures::create_basic_game;
-use rummy::game::{error::GameError, r#trait::Game};
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use rummy::game::{
+    action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+    error::GameError,
+    r#trait::Game,
+    state::{GamePhase, QuitHandPolicy},
+    variants::basic::{config::BasicConfig, game::BasicRummyGame},
+};
+
+/// Creates a game with `player_count` players and `quit_hand_policy`, which has started its first round.
+fn started_game(player_count: usize, quit_hand_policy: QuitHandPolicy) -> BasicRummyGame {
+    let config = BasicConfig {
+        deal_amount: None,
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: Some(quit_hand_policy),
+    };
+    let mut game =
+        create_basic_game_with_config(player_count, Some(1), Some(config), None).unwrap();
+    game.next_round().unwrap();
+    game
+}
+
+fn current_player_id(game: &BasicRummyGame) -> usize {
+    game.get_state().get_current_player().unwrap().id()
+}
 
 #[test]
 fn add_player_during_new_round() {
@@ -61,3 +85,140 @@ fn quit_nonexistent_player_fails() {
         Err(GameError::PlayerDoesntExist)
     ));
 }
+
+#[test]
+fn quitting_current_player_passes_turn() {
+    let mut game = create_basic_game(3).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.quit_player(0).unwrap();
+    assert_eq!(current_player_id(&game), 1);
+    assert_eq!(game.get_state().phase(), GamePhase::Draw);
+}
+
+#[test]
+fn turns_skip_quit_player() {
+    let mut game = create_basic_game(3).unwrap();
+    game.next_round().unwrap();
+    game.quit_player(1).unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    assert_eq!(current_player_id(&game), 2);
+}
+
+#[test]
+fn quit_hand_is_kept() {
+    let mut game = started_game(3, QuitHandPolicy::Keep);
+    let hand = game.get_state().players()[1].cards().clone();
+    let stock_size = game.get_state().deck().stock().len();
+    game.quit_player(1).unwrap();
+
+    let state = game.get_state();
+    assert_eq!(state.players()[1].cards(), &hand);
+    assert_eq!(state.deck().stock().len(), stock_size);
+}
+
+#[test]
+fn quit_hand_is_returned_to_stock() {
+    let mut game = started_game(3, QuitHandPolicy::ReturnToStock);
+    let hand = game.get_state().players()[1].cards().clone();
+    let stock_size = game.get_state().deck().stock().len();
+    game.quit_player(1).unwrap();
+
+    let state = game.get_state();
+    assert!(state.players()[1].cards().is_empty());
+    assert_eq!(state.deck().stock().len(), stock_size + hand.len());
+    assert_eq!(state.deck().stock()[..hand.len()], hand[..]);
+}
+
+#[test]
+fn quit_hand_is_discarded() {
+    let mut game = started_game(3, QuitHandPolicy::Discard);
+    let hand = game.get_state().players()[1].cards().clone();
+    let discard_size = game.get_state().deck().discard_pile().len();
+    game.quit_player(1).unwrap();
+
+    let state = game.get_state();
+    assert!(state.players()[1].cards().is_empty());
+    assert_eq!(state.deck().discard_pile().len(), discard_size + hand.len());
+    assert_eq!(
+        state.deck().peek_discard_pile(),
+        hand.last().map(|c| c.data())
+    );
+}
+
+#[test]
+fn quit_player_with_empty_hand_doesnt_win_round() {
+    let config = BasicConfig {
+        deal_amount: Some(2),
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: Some(QuitHandPolicy::Discard),
+    };
+    let mut game = create_basic_game_with_config(3, Some(193), Some(config), None).unwrap();
+    game.next_round().unwrap();
+    game.quit_player(0).unwrap();
+    assert!(game.get_state().players()[0].cards().is_empty());
+
+    // player 1 draws into a set, and goes out by melding their whole hand
+    assert_eq!(current_player_id(&game), 1);
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![0, 1, 2],
+    }))
+    .unwrap();
+    assert_eq!(game.get_state().phase(), GamePhase::RoundEnd);
+
+    game.next_round().unwrap();
+    assert_eq!(game.get_state().round_scores()[&1].winner_id, 1);
+}
+
+#[test]
+fn replace_player_takes_over_hand() {
+    let mut game = started_game(3, QuitHandPolicy::Keep);
+    let hand = game.get_state().players()[0].cards().clone();
+    game.quit_player(0).unwrap();
+    game.replace_player(0, 5).unwrap();
+
+    let player = &game.get_state().players()[0];
+    assert_eq!(player.id(), 5);
+    assert!(player.active());
+    assert_eq!(player.cards(), &hand);
+    // the turn already passed on when player 0 quit
+    assert_eq!(current_player_id(&game), 1);
+}
+
+#[test]
+fn replace_player_without_hand_joins_next_round() {
+    let mut game = started_game(3, QuitHandPolicy::Discard);
+    game.quit_player(2).unwrap();
+    game.replace_player(2, 2).unwrap();
+    assert!(!game.get_state().players()[2].active());
+    assert_eq!(game.get_state().players()[2].joined_in_round(), 1);
+}
+
+#[test]
+fn replace_active_player_fails() {
+    let mut game = started_game(3, QuitHandPolicy::Keep);
+    assert!(matches!(
+        game.replace_player(0, 5),
+        Err(GameError::SeatNotVacant)
+    ));
+}
+
+#[test]
+fn replace_player_with_existing_id_fails() {
+    let mut game = started_game(3, QuitHandPolicy::Keep);
+    game.quit_player(0).unwrap();
+    assert!(matches!(
+        game.replace_player(0, 1),
+        Err(GameError::AddedPlayerAlreadyExists)
+    ));
+}
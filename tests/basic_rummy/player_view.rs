@@ -0,0 +1,46 @@
+use crate::common::fixtures::create_basic_game;
+use rummy::game::{
+    action::{DrawDeckAction, GameAction},
+    r#trait::Game,
+    state::GamePhase,
+};
+
+#[test]
+fn player_sees_only_their_hand() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let state = game.get_state();
+
+    let view = state.view_for(Some(1));
+    assert_eq!(view.viewer, Some(1));
+    assert_eq!(view.phase, GamePhase::Play);
+    assert_eq!(view.current_round, 1);
+    assert_eq!(view.current_player_id, Some(0));
+    let hand: Vec<_> = state.players()[1]
+        .cards()
+        .iter()
+        .map(|c| c.data())
+        .collect();
+    assert_eq!(view.hand, hand);
+    assert_eq!(view.players[0].card_count, 11);
+    assert_eq!(view.players[1].card_count, 10);
+    assert_eq!(view.stock_size, state.deck().stock().len());
+}
+
+#[test]
+fn spectator_sees_no_hand() {
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    let state = game.get_state();
+
+    let view = state.view_for(None);
+    assert!(view.hand.is_empty());
+    assert_eq!(view.players.len(), 2);
+
+    // an unknown player sees the same as a spectator
+    let unknown_view = state.view_for(Some(5));
+    assert_eq!(unknown_view.hand, view.hand);
+    assert_eq!(unknown_view.players, view.players);
+}
@@ -0,0 +1,95 @@
+use crate::common::fixtures::play_basic_game_with_bots;
+use rummy::{
+    game::r#trait::Game,
+    ratings::{EloConfig, RatingError, RatingStore},
+};
+
+fn assert_close(a: f64, b: f64) {
+    assert!((a - b).abs() < 1e-9, "{a} != {b}");
+}
+
+#[test]
+fn two_player_finishes_use_plain_elo() {
+    let mut ratings = RatingStore::new(EloConfig::default());
+    let changes = ratings.record_finish(&[vec![0], vec![1]]).unwrap();
+    assert_close(changes[&0], 16.0);
+    assert_close(changes[&1], -16.0);
+    assert_close(ratings.rating(0).value, 1516.0);
+    assert_eq!(ratings.rating(0).finishes, 1);
+
+    // the favourite gains less for winning again
+    let changes = ratings.record_finish(&[vec![0], vec![1]]).unwrap();
+    let expected = 1.0 / (1.0 + 10f64.powf(-32.0 / 400.0));
+    assert_close(changes[&0], 32.0 * (1.0 - expected));
+}
+
+#[test]
+fn multiplayer_finishes_reward_every_place() {
+    let mut ratings = RatingStore::new(EloConfig::default());
+    let changes = ratings
+        .record_finish(&[vec![3], vec![1], vec![0], vec![2]])
+        .unwrap();
+    assert!(changes[&3] > changes[&1]);
+    assert!(changes[&1] > 0.0);
+    assert!(changes[&0] < 0.0);
+    assert!(changes[&0] > changes[&2]);
+    assert_close(changes.values().sum(), 0.0);
+    // winning against 3 equally rated players is worth the whole K-factor, shared between them
+    assert_close(changes[&3], 16.0);
+
+    let leaderboard: Vec<_> = ratings.leaderboard().iter().map(|(id, _)| *id).collect();
+    assert_eq!(leaderboard, vec![3, 1, 0, 2]);
+}
+
+#[test]
+fn tied_players_with_the_same_rating_dont_change() {
+    let mut ratings = RatingStore::new(EloConfig::default());
+    let changes = ratings.record_finish(&[vec![0], vec![1, 2]]).unwrap();
+    assert_close(changes[&1], changes[&2]);
+    assert_close(changes[&1], -8.0);
+    let changes = ratings.record_finish(&[vec![1, 2]]).unwrap();
+    assert_close(changes[&1], 0.0);
+}
+
+#[test]
+fn invalid_finishes_fail() {
+    let mut ratings = RatingStore::new(EloConfig::default());
+    assert!(matches!(
+        ratings.record_finish(&[vec![0]]),
+        Err(RatingError::NotEnoughPlayers(1))
+    ));
+    assert!(matches!(
+        ratings.record_finish(&[vec![0], vec![1, 0]]),
+        Err(RatingError::DuplicatePlayer(0))
+    ));
+    assert!(ratings.ratings().is_empty());
+}
+
+#[test]
+fn ratings_from_played_games() {
+    let game = play_basic_game_with_bots(3, 2);
+    let state = game.get_state();
+
+    let mut ratings = RatingStore::new(EloConfig::default());
+    let round_score = &state.round_scores()[&1];
+    let changes = ratings.record_round(round_score).unwrap();
+    let winner = round_score.winner_id;
+    assert!(changes
+        .iter()
+        .all(|(&id, &change)| id == winner || change < changes[&winner]));
+
+    let mut ratings = RatingStore::new(EloConfig::default());
+    let changes = ratings.record_game(&game).unwrap();
+    assert_eq!(changes.len(), 3);
+    assert!(ratings.ratings().values().all(|r| r.finishes == 1));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn rating_stores_serialize() {
+    let mut ratings = RatingStore::new(EloConfig::default());
+    ratings.record_finish(&[vec![2], vec![0, 1]]).unwrap();
+    let saved = serde_json::to_string(&ratings).unwrap();
+    let loaded: RatingStore = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded, ratings);
+}
@@ -1,3 +1,6 @@
+#[cfg(feature = "serde")]
+use rummy::game::variants::basic::game::BasicRummyGame;
+
 #[test]
 #[cfg(feature = "serde")]
 fn serialization_works() {
@@ -11,3 +14,93 @@ fn serialization_works() {
     let deserialized_game = serde_json::from_str(&serialized).unwrap();
     assert_eq!(game, deserialized_game);
 }
+
+/// The game stored in each of the `tests/fixtures/serialization/basic_game_v*.json` fixtures.
+#[cfg(feature = "serde")]
+fn fixture_game() -> BasicRummyGame {
+    use crate::common::fixtures::create_basic_game;
+    use rummy::game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+        r#trait::Game,
+    };
+
+    let mut game = create_basic_game(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.sort();
+    game.rearrange_player_hand(cur_player.id(), hand).unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }))
+    .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    game
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serialized_game_is_versioned() {
+    use rummy::serialization::SCHEMA_VERSION;
+
+    let serialized = serde_json::to_value(fixture_game()).unwrap();
+    assert_eq!(serialized["version"], SCHEMA_VERSION);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+/// Games serialized by every released schema version can still be loaded.
+fn load_all_fixture_versions() {
+    let fixtures = [
+        include_str!("../fixtures/serialization/basic_game_v0.json"),
+        include_str!("../fixtures/serialization/basic_game_v1.json"),
+        include_str!("../fixtures/serialization/basic_game_v2.json"),
+        include_str!("../fixtures/serialization/basic_game_v3.json"),
+        include_str!("../fixtures/serialization/basic_game_v4.json"),
+    ];
+    let expected_game = fixture_game();
+    for (version, fixture) in fixtures.iter().enumerate() {
+        let game: BasicRummyGame = serde_json::from_str(fixture)
+            .unwrap_or_else(|err| panic!("fixture v{version} failed to load: {err}"));
+        assert_eq!(game, expected_game, "fixture v{version} loaded incorrectly");
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+/// Decks from before the seed was stored were shuffled with the config's seed in every round,
+/// so a game saved after the first round keeps that seed.
+fn migrated_later_round_keeps_shuffle_seed() {
+    use crate::common::fixtures::play_basic_game_with_bots;
+    use rummy::game::r#trait::Game;
+
+    let fixture = include_str!("../fixtures/serialization/basic_game_v1_round_2.json");
+    let game: BasicRummyGame = serde_json::from_str(fixture).unwrap();
+    let expected_game = play_basic_game_with_bots(2, 1);
+    let (state, expected_state) = (game.get_state(), expected_game.get_state());
+    assert_eq!(state.current_round(), 2);
+    assert_eq!(state.players(), expected_state.players());
+    assert_eq!(state.deck().stock(), expected_state.deck().stock());
+    assert_eq!(
+        state.deck().seed(),
+        expected_state.deck().config().shuffle_seed
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn future_version_fails() {
+    let mut serialized = serde_json::to_value(fixture_game()).unwrap();
+    serialized["version"] = serde_json::json!(u32::MAX);
+    let result = serde_json::from_value::<BasicRummyGame>(serialized);
+    assert!(result
+        .unwrap_err()
+        .to_string()
+        .contains("unsupported schema version"));
+}
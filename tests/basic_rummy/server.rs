@@ -0,0 +1,547 @@
+#![cfg(feature = "server")]
+
+use futures_util::{SinkExt, StreamExt};
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction, GameInteractions},
+        event::{DrawSource, GameEvent},
+        state::GamePhase,
+        variants::basic::score::BasicScore,
+    },
+    server::{
+        protocol::{ClientMessage, GameId, ServerMessage},
+        Server,
+    },
+};
+use std::{collections::VecDeque, time::Duration};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines},
+    net::{tcp::OwnedReadHalf, tcp::OwnedWriteHalf, TcpListener, TcpStream},
+    time::{sleep, timeout},
+};
+use tokio_tungstenite::tungstenite::Message;
+
+/// A scripted client talking to a server on localhost.
+struct Client {
+    lines: Lines<BufReader<OwnedReadHalf>>,
+    writer: OwnedWriteHalf,
+    /// Events and views received while waiting for a reply.
+    pending: VecDeque<ServerMessage>,
+}
+
+impl Client {
+    async fn connect(address: &str) -> Self {
+        let (reader, writer) = TcpStream::connect(address).await.unwrap().into_split();
+        Self {
+            lines: BufReader::new(reader).lines(),
+            writer,
+            pending: VecDeque::new(),
+        }
+    }
+
+    async fn send(&mut self, message: ClientMessage) {
+        let mut line = serde_json::to_string(&message).unwrap();
+        line.push('\n');
+        self.writer.write_all(line.as_bytes()).await.unwrap();
+    }
+
+    /// Receive messages (starting with any pending ones) until one matches `predicate`, returning it.
+    async fn recv_until(&mut self, predicate: impl Fn(&ServerMessage) -> bool) -> ServerMessage {
+        while let Some(message) = self.pending.pop_front() {
+            if predicate(&message) {
+                return message;
+            }
+        }
+        loop {
+            let message = self.recv().await;
+            if predicate(&message) {
+                return message;
+            }
+        }
+    }
+
+    async fn recv(&mut self) -> ServerMessage {
+        let line = timeout(Duration::from_secs(5), self.lines.next_line())
+            .await
+            .expect("timed out waiting for the server")
+            .unwrap()
+            .expect("the server closed the connection");
+        serde_json::from_str(&line).unwrap()
+    }
+
+    /// Send a message and get its reply, keeping any events and views received meanwhile as pending.
+    async fn request(&mut self, message: ClientMessage) -> ServerMessage {
+        self.send(message).await;
+        loop {
+            match self.recv().await {
+                message @ (ServerMessage::Event { .. } | ServerMessage::View { .. }) => {
+                    self.pending.push_back(message)
+                }
+                reply => return reply,
+            }
+        }
+    }
+
+    async fn join(&mut self, game_id: GameId) -> (usize, String) {
+        match self.request(ClientMessage::JoinGame { game_id }).await {
+            ServerMessage::Joined {
+                player_id, token, ..
+            } => (player_id, token),
+            other => panic!("expected to join, got {other:?}"),
+        }
+    }
+
+    async fn wait_for_event(&mut self, event: GameEvent<BasicScore>) {
+        self.recv_until(|m| matches!(m, ServerMessage::Event { event: e } if *e == event))
+            .await;
+    }
+
+    async fn game_ids(&mut self) -> Vec<GameId> {
+        let ServerMessage::Games { games } = self.request(ClientMessage::ListGames).await else {
+            panic!("expected the games");
+        };
+        games.iter().map(|g| g.game_id).collect()
+    }
+}
+
+async fn start_server() -> String {
+    start_server_with(Server::new()).await
+}
+
+async fn start_server_with(server: Server) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let address = listener.local_addr().unwrap().to_string();
+    tokio::spawn(server.run(listener));
+    address
+}
+
+/// The timeout used by tests of the server's timeouts.
+const SHORT_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Starts a server with a 2 player game which has started its first round.
+async fn started_game() -> (String, GameId, [(Client, String); 2]) {
+    let address = start_server().await;
+    let (game_id, clients) = started_game_at(&address).await;
+    (address, game_id, clients)
+}
+
+/// Starts a 2 player game on the server at `address`, which has started its first round.
+async fn started_game_at(address: &str) -> (GameId, [(Client, String); 2]) {
+    let mut first = Client::connect(address).await;
+    let mut second = Client::connect(address).await;
+    let ServerMessage::GameCreated { game_id } = first.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    let (_, first_token) = first.join(game_id).await;
+    let (_, second_token) = second.join(game_id).await;
+    assert_eq!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Ok
+    );
+    (game_id, [(first, first_token), (second, second_token)])
+}
+
+fn draw_deck() -> ClientMessage {
+    ClientMessage::Interact {
+        interaction: GameInteractions::Action(GameAction::DrawDeck(DrawDeckAction {})),
+    }
+}
+
+#[tokio::test]
+async fn lobby_create_join_and_start() {
+    let address = start_server().await;
+    let mut first = Client::connect(&address).await;
+    let mut second = Client::connect(&address).await;
+
+    let ServerMessage::GameCreated { game_id } = first.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    let (first_id, first_token) = first.join(game_id).await;
+    let (second_id, second_token) = second.join(game_id).await;
+    assert_eq!((first_id, second_id), (0, 1));
+    assert_ne!(first_token, second_token);
+
+    let ServerMessage::Games { games } = second.request(ClientMessage::ListGames).await else {
+        panic!("expected the games");
+    };
+    assert_eq!(games.len(), 1);
+    assert_eq!(games[0].player_ids, vec![0, 1]);
+    assert!(!games[0].started);
+
+    assert_eq!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Ok
+    );
+    assert!(matches!(
+        second.request(ClientMessage::StartGame).await,
+        ServerMessage::Error { .. }
+    ));
+
+    // each player gets a view with only their own hand
+    for (client, id) in [(&mut first, 0), (&mut second, 1)] {
+        let ServerMessage::View { view } = client
+            .recv_until(|m| matches!(m, ServerMessage::View { view } if view.current_round == 1))
+            .await
+        else {
+            unreachable!();
+        };
+        assert_eq!(view.viewer, Some(id));
+        assert_eq!(view.hand.len(), view.players[id].card_count);
+        assert!(!view.hand.is_empty());
+    }
+}
+
+#[tokio::test]
+async fn players_can_only_act_as_their_seat() {
+    let (_, _, [(mut first, _), (mut second, _)]) = started_game().await;
+
+    assert!(matches!(
+        second.request(draw_deck()).await,
+        ServerMessage::Error { .. }
+    ));
+    assert!(matches!(
+        second
+            .request(ClientMessage::Interact {
+                interaction: GameInteractions::PlayerQuit { player_id: 0 }
+            })
+            .await,
+        ServerMessage::Error { .. }
+    ));
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+}
+
+#[tokio::test]
+async fn spectators_only_see_public_events() {
+    let (address, game_id, [(mut first, _), _second]) = started_game().await;
+    let mut spectator = Client::connect(&address).await;
+    assert_eq!(
+        spectator.request(ClientMessage::Spectate { game_id }).await,
+        ServerMessage::Spectating { game_id }
+    );
+    let ServerMessage::View { view } = spectator
+        .recv_until(|m| matches!(m, ServerMessage::View { .. }))
+        .await
+    else {
+        unreachable!();
+    };
+    assert_eq!(view.viewer, None);
+    assert!(view.hand.is_empty());
+
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+    let ServerMessage::Event { event } = first
+        .recv_until(|m| {
+            matches!(
+                m,
+                ServerMessage::Event {
+                    event: GameEvent::CardsDrawn { .. }
+                }
+            )
+        })
+        .await
+    else {
+        unreachable!();
+    };
+    assert!(matches!(
+        event,
+        GameEvent::CardsDrawn {
+            player_id: 0,
+            source: DrawSource::Deck,
+            ..
+        }
+    ));
+
+    let ServerMessage::Event { event } = spectator
+        .recv_until(|m| matches!(m, ServerMessage::Event { .. }))
+        .await
+    else {
+        unreachable!();
+    };
+    assert_eq!(
+        event,
+        GameEvent::HiddenCardsDrawn {
+            player_id: 0,
+            amount: 1
+        }
+    );
+}
+
+#[tokio::test]
+async fn reconnecting_needs_the_seat_token() {
+    let (address, game_id, [(mut first, _), (second, second_token)]) = started_game().await;
+    let mut other = Client::connect(&address).await;
+    assert!(matches!(
+        other
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: second_token.clone()
+            })
+            .await,
+        ServerMessage::Error { .. }
+    ));
+
+    drop(second);
+    first.wait_for_event(GameEvent::Paused).await;
+    assert!(matches!(
+        other
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: "not a token".to_string()
+            })
+            .await,
+        ServerMessage::Error { .. }
+    ));
+    assert!(matches!(
+        other
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: second_token
+            })
+            .await,
+        ServerMessage::Joined { player_id: 1, .. }
+    ));
+}
+
+#[tokio::test]
+async fn disconnecting_pauses_until_reconnected() {
+    let (address, game_id, [(mut first, _), (second, second_token)]) = started_game().await;
+    drop(second);
+    first.wait_for_event(GameEvent::Paused).await;
+    assert!(matches!(
+        first.request(draw_deck()).await,
+        ServerMessage::Error { .. }
+    ));
+
+    let mut second = Client::connect(&address).await;
+    assert!(matches!(
+        second
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: second_token
+            })
+            .await,
+        ServerMessage::Joined { player_id: 1, .. }
+    ));
+    first.wait_for_event(GameEvent::Resumed).await;
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+}
+
+#[tokio::test]
+async fn starting_needs_every_seat_connected() {
+    let address = start_server().await;
+    let mut first = Client::connect(&address).await;
+    let mut second = Client::connect(&address).await;
+    let ServerMessage::GameCreated { game_id } = first.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    first.join(game_id).await;
+    let (_, second_token) = second.join(game_id).await;
+
+    drop(second);
+    sleep(SHORT_TIMEOUT).await;
+    assert!(matches!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Error { .. }
+    ));
+
+    let mut second = Client::connect(&address).await;
+    assert!(matches!(
+        second
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: second_token
+            })
+            .await,
+        ServerMessage::Joined { player_id: 1, .. }
+    ));
+    assert_eq!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Ok
+    );
+}
+
+#[tokio::test]
+async fn out_of_range_indices_are_rejected() {
+    let (_, _, [(mut first, _), (mut second, _)]) = started_game().await;
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+    let ServerMessage::View { view } = first
+        .recv_until(|m| matches!(m, ServerMessage::View { view } if view.hand.len() == 11))
+        .await
+    else {
+        unreachable!();
+    };
+
+    for card_index in [view.hand.len(), usize::MAX] {
+        let discard = ClientMessage::Interact {
+            interaction: GameInteractions::Action(GameAction::Discard(DiscardAction {
+                card_index,
+                declare_going_out: None,
+            })),
+        };
+        assert!(matches!(
+            first.request(discard).await,
+            ServerMessage::Error { .. }
+        ));
+    }
+
+    // the game is still running for every seat
+    let discard = ClientMessage::Interact {
+        interaction: GameInteractions::Action(GameAction::Discard(DiscardAction {
+            card_index: view.hand.len() - 1,
+            declare_going_out: None,
+        })),
+    };
+    assert_eq!(first.request(discard).await, ServerMessage::Ok);
+    assert_eq!(second.request(draw_deck()).await, ServerMessage::Ok);
+}
+
+#[tokio::test]
+async fn disconnected_seats_forfeit_after_the_timeout() {
+    let address = start_server_with(Server::new().reconnect_timeout(SHORT_TIMEOUT)).await;
+    let mut first = Client::connect(&address).await;
+    let mut second = Client::connect(&address).await;
+    let mut third = Client::connect(&address).await;
+    let ServerMessage::GameCreated { game_id } = first.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    first.join(game_id).await;
+    second.join(game_id).await;
+    let (_, third_token) = third.join(game_id).await;
+    assert_eq!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Ok
+    );
+
+    drop(third);
+    first.wait_for_event(GameEvent::Paused).await;
+    first
+        .wait_for_event(GameEvent::PlayerQuit { player_id: 2 })
+        .await;
+    first.wait_for_event(GameEvent::Resumed).await;
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+
+    // the seat is gone, so it can't be taken back
+    let mut third = Client::connect(&address).await;
+    assert!(matches!(
+        third
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: third_token
+            })
+            .await,
+        ServerMessage::Error { .. }
+    ));
+}
+
+#[tokio::test]
+async fn reconnecting_in_time_keeps_the_seat() {
+    let address = start_server_with(Server::new().reconnect_timeout(SHORT_TIMEOUT * 5)).await;
+    let mut first = Client::connect(&address).await;
+    let mut second = Client::connect(&address).await;
+    let ServerMessage::GameCreated { game_id } = first.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    first.join(game_id).await;
+    let (_, second_token) = second.join(game_id).await;
+    assert_eq!(
+        first.request(ClientMessage::StartGame).await,
+        ServerMessage::Ok
+    );
+
+    drop(second);
+    first.wait_for_event(GameEvent::Paused).await;
+    let mut second = Client::connect(&address).await;
+    assert!(matches!(
+        second
+            .request(ClientMessage::Reconnect {
+                game_id,
+                token: second_token
+            })
+            .await,
+        ServerMessage::Joined { player_id: 1, .. }
+    ));
+
+    // the earlier disconnect's timer doesn't forfeit the seat
+    sleep(SHORT_TIMEOUT * 10).await;
+    assert_eq!(first.game_ids().await, [game_id]);
+    assert_eq!(first.request(draw_deck()).await, ServerMessage::Ok);
+}
+
+#[tokio::test]
+async fn finished_and_empty_games_are_removed() {
+    let server = Server::new()
+        .reconnect_timeout(SHORT_TIMEOUT)
+        .empty_game_timeout(SHORT_TIMEOUT);
+    let address = start_server_with(server).await;
+    let mut client = Client::connect(&address).await;
+
+    // a game nobody joins
+    client.request(ClientMessage::CreateGame).await;
+    assert_eq!(client.game_ids().await.len(), 1);
+    sleep(SHORT_TIMEOUT * 3).await;
+    assert!(client.game_ids().await.is_empty());
+
+    // a game everyone leaves
+    let ServerMessage::GameCreated { game_id } = client.request(ClientMessage::CreateGame).await
+    else {
+        panic!("expected the game to be created");
+    };
+    client.join(game_id).await;
+    assert_eq!(
+        client.request(ClientMessage::Leave).await,
+        ServerMessage::Left
+    );
+    assert!(client.game_ids().await.is_empty());
+
+    // a game which ends, once a player forfeits and leaves only one player
+    let (game_id, [(mut first, _), (second, _)]) = started_game_at(&address).await;
+    drop(second);
+    first
+        .wait_for_event(GameEvent::PlayerQuit { player_id: 1 })
+        .await;
+    first
+        .recv_until(|m| {
+            matches!(
+                m,
+                ServerMessage::View { view } if view.phase == GamePhase::GameEnd
+            )
+        })
+        .await;
+    sleep(SHORT_TIMEOUT).await;
+    assert!(!client.game_ids().await.contains(&game_id));
+}
+
+#[tokio::test]
+async fn websocket_clients_share_the_lobby() {
+    let server = Server::new();
+    let address = start_server_with(server.clone()).await;
+    let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let websocket_address = listener.local_addr().unwrap().to_string();
+    tokio::spawn(server.run_websocket(listener));
+
+    let stream = TcpStream::connect(&websocket_address).await.unwrap();
+    let (mut websocket, _) =
+        tokio_tungstenite::client_async(format!("ws://{websocket_address}"), stream)
+            .await
+            .unwrap();
+    let message = serde_json::to_string(&ClientMessage::CreateGame).unwrap();
+    websocket.send(Message::Text(message)).await.unwrap();
+    let reply = timeout(Duration::from_secs(5), websocket.next())
+        .await
+        .expect("timed out waiting for the server")
+        .expect("the server closed the connection")
+        .unwrap();
+    let Message::Text(reply) = reply else {
+        panic!("expected a text message, got {reply:?}");
+    };
+    let ServerMessage::GameCreated { game_id } = serde_json::from_str(&reply).unwrap() else {
+        panic!("expected the game to be created");
+    };
+
+    let mut client = Client::connect(&address).await;
+    assert_eq!(client.game_ids().await, [game_id]);
+}
@@ -0,0 +1,274 @@
+#![cfg(feature = "session")]
+
+use crate::common::fixtures::create_basic_game;
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        error::{ActionError, FailedActionError},
+        event::{DrawSource, GameEvent},
+        state::GamePhase,
+    },
+    session::{SessionError, SessionHost},
+};
+
+#[tokio::test]
+async fn only_current_player_can_act() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+    session.next_round(0).await.unwrap();
+
+    assert!(matches!(
+        session
+            .act(1, GameAction::DrawDeck(DrawDeckAction {}))
+            .await,
+        Err(SessionError::Action(ActionError::FailedAction(
+            FailedActionError::NotYourTurn {
+                player_id: 1,
+                current_player_id: 0
+            }
+        )))
+    ));
+    session
+        .act(0, GameAction::DrawDeck(DrawDeckAction {}))
+        .await
+        .unwrap();
+    assert!(matches!(
+        session
+            .act(
+                0,
+                GameAction::Discard(DiscardAction {
+                    card_index: 100,
+                    declare_going_out: None
+                })
+            )
+            .await,
+        Err(SessionError::Action(_))
+    ));
+    session
+        .act(
+            0,
+            GameAction::Discard(DiscardAction {
+                card_index: 0,
+                declare_going_out: None,
+            }),
+        )
+        .await
+        .unwrap();
+
+    let view = session.view(None).await.unwrap();
+    assert_eq!(view.current_player_id, Some(1));
+}
+
+#[tokio::test]
+async fn concurrent_players_are_serialized() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+    session.next_round(0).await.unwrap();
+
+    // both players race to draw; only the current player's draw succeeds
+    let tasks: Vec<_> = (0..2)
+        .map(|player_id| {
+            let session = session.clone();
+            tokio::spawn(async move {
+                session
+                    .act(player_id, GameAction::DrawDeck(DrawDeckAction {}))
+                    .await
+            })
+        })
+        .collect();
+    let mut results = Vec::new();
+    for task in tasks {
+        results.push(task.await.unwrap());
+    }
+    assert!(results[0].is_ok());
+    assert!(matches!(
+        results[1],
+        Err(SessionError::Action(ActionError::FailedAction(
+            FailedActionError::NotYourTurn {
+                player_id: 1,
+                current_player_id: 0
+            }
+        )))
+    ));
+}
+
+#[tokio::test]
+async fn subscribers_get_their_events_and_views() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+    let mut player_0 = session.subscribe(Some(0)).await.unwrap();
+    let mut player_1 = session.subscribe(Some(1)).await.unwrap();
+    let mut spectator = session.subscribe(None).await.unwrap();
+
+    session.next_round(0).await.unwrap();
+    session
+        .act(0, GameAction::DrawDeck(DrawDeckAction {}))
+        .await
+        .unwrap();
+
+    // each player only sees their own hand being dealt
+    assert_eq!(
+        player_0.next_event().await.unwrap(),
+        GameEvent::RoundStarted { round: 1 }
+    );
+    assert!(matches!(
+        player_0.next_event().await.unwrap(),
+        GameEvent::HandDealt { player_id: 0, .. }
+    ));
+    assert_eq!(
+        player_1.next_event().await.unwrap(),
+        GameEvent::RoundStarted { round: 1 }
+    );
+    assert!(matches!(
+        player_1.next_event().await.unwrap(),
+        GameEvent::HandDealt { player_id: 1, .. }
+    ));
+
+    // the spectator sees no hands or drawn cards
+    let mut spectator_events = Vec::new();
+    for _ in 0..5 {
+        spectator_events.push(spectator.next_event().await.unwrap());
+    }
+    assert_eq!(
+        spectator_events,
+        [
+            GameEvent::RoundStarted { round: 1 },
+            GameEvent::PhaseChanged {
+                phase: GamePhase::Draw
+            },
+            GameEvent::TurnChanged { player_id: 0 },
+            GameEvent::HiddenCardsDrawn {
+                player_id: 0,
+                amount: 1
+            },
+            GameEvent::PhaseChanged {
+                phase: GamePhase::Play
+            },
+        ]
+    );
+    for _ in 0..3 {
+        let event = player_0.next_event().await.unwrap();
+        assert!(!matches!(
+            event,
+            GameEvent::CardsDrawn {
+                source: DrawSource::Deck,
+                ..
+            }
+        ));
+    }
+    assert!(matches!(
+        player_0.next_event().await.unwrap(),
+        GameEvent::CardsDrawn {
+            player_id: 0,
+            source: DrawSource::Deck,
+            ..
+        }
+    ));
+
+    let view = player_0.view_changed().await.unwrap();
+    assert_eq!(view.hand.len(), 11);
+    assert_eq!(player_1.view().hand.len(), 10);
+    assert!(spectator.view().hand.is_empty());
+}
+
+#[tokio::test]
+async fn session_stops_when_handles_are_dropped() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+    let mut subscription = session.subscribe(None).await.unwrap();
+    drop(session);
+    assert!(matches!(
+        subscription.next_event().await,
+        Err(SessionError::Closed)
+    ));
+}
+
+#[tokio::test]
+async fn paused_session_rejects_actions() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+    session.next_round(0).await.unwrap();
+    let mut spectator = session.subscribe(None).await.unwrap();
+
+    session.pause(0).await.unwrap();
+    assert_eq!(spectator.next_event().await.unwrap(), GameEvent::Paused);
+    assert!(spectator.view().paused);
+    assert!(matches!(
+        session
+            .act(0, GameAction::DrawDeck(DrawDeckAction {}))
+            .await,
+        Err(SessionError::Action(ActionError::FailedAction(
+            FailedActionError::GamePaused
+        )))
+    ));
+
+    session.resume(1).await.unwrap();
+    assert_eq!(spectator.next_event().await.unwrap(), GameEvent::Resumed);
+    session
+        .act(0, GameAction::DrawDeck(DrawDeckAction {}))
+        .await
+        .unwrap();
+}
+
+#[tokio::test]
+async fn commands_check_the_acting_player() {
+    let session = SessionHost::spawn(create_basic_game(2).unwrap()).handle();
+
+    // only players in the game can start rounds, pause or resume it
+    assert!(matches!(
+        session.next_round(2).await,
+        Err(SessionError::NotAllowed { actor: 2 })
+    ));
+    session.next_round(1).await.unwrap();
+    assert!(matches!(
+        session.pause(2).await,
+        Err(SessionError::NotAllowed { actor: 2 })
+    ));
+
+    // and players can only quit or rearrange their own seat
+    let hand = session.view(Some(0)).await.unwrap().hand;
+    assert!(matches!(
+        session.rearrange_player_hand(1, 0, hand.clone()).await,
+        Err(SessionError::NotAllowed { actor: 1 })
+    ));
+    session.rearrange_player_hand(0, 0, hand).await.unwrap();
+    assert!(matches!(
+        session.quit_player(1, 0).await,
+        Err(SessionError::NotAllowed { actor: 1 })
+    ));
+    session.quit_player(0, 0).await.unwrap();
+    assert_eq!(session.view(None).await.unwrap().current_player_id, Some(1));
+}
+
+#[tokio::test]
+async fn only_the_host_seats_players() {
+    let host = SessionHost::spawn(create_basic_game(3).unwrap());
+    let session = host.handle();
+    session.next_round(0).await.unwrap();
+
+    host.add_player(3).await.unwrap();
+    session.quit_player(1, 1).await.unwrap();
+    host.replace_player(1, 4).await.unwrap();
+    let player_ids: Vec<_> = session
+        .view(None)
+        .await
+        .unwrap()
+        .players
+        .iter()
+        .map(|p| p.id)
+        .collect();
+    assert_eq!(player_ids, [0, 4, 2, 3]);
+}
+
+#[tokio::test]
+async fn players_who_quit_cant_act() {
+    let session = SessionHost::spawn(create_basic_game(3).unwrap()).handle();
+    session.next_round(0).await.unwrap();
+    session.quit_player(2, 2).await.unwrap();
+
+    assert!(matches!(
+        session.pause(2).await,
+        Err(SessionError::NotAllowed { actor: 2 })
+    ));
+    let hand = session.view(Some(2)).await.unwrap().hand;
+    assert!(matches!(
+        session.rearrange_player_hand(2, 2, hand).await,
+        Err(SessionError::NotAllowed { actor: 2 })
+    ));
+    session.pause(0).await.unwrap();
+}
@@ -0,0 +1,83 @@
+use crate::common::fixtures::create_basic_game_with_config;
+use rummy::{
+    game::variants::basic::game::BasicRummyGame,
+    simulation::{
+        bots::{GreedyBot, RandomBot},
+        BotFactory, Simulation, SimulationConfig, SimulationError,
+    },
+};
+use std::num::NonZeroUsize;
+
+fn new_game(player_count: usize) -> impl Fn(u64) -> BasicRummyGame + Sync {
+    move |seed| create_basic_game_with_config(player_count, Some(seed), None, None).unwrap()
+}
+
+fn greedy_and_random() -> Vec<BotFactory<BasicRummyGame>> {
+    vec![
+        Box::new(|_| Box::new(GreedyBot)),
+        Box::new(|seed| Box::new(RandomBot::new(seed))),
+    ]
+}
+
+fn config(games: usize, threads: usize) -> SimulationConfig {
+    SimulationConfig {
+        games,
+        rounds_per_game: 2,
+        threads: NonZeroUsize::new(threads),
+        ..SimulationConfig::default()
+    }
+}
+
+#[test]
+fn report_doesnt_depend_on_threads() {
+    let single = Simulation::new(config(12, 1), new_game(2), greedy_and_random())
+        .run()
+        .unwrap();
+    let multiple = Simulation::new(config(12, 3), new_game(2), greedy_and_random())
+        .run()
+        .unwrap();
+    assert_eq!(single, multiple);
+}
+
+#[test]
+fn report_totals_add_up() {
+    let report = Simulation::new(config(20, 2), new_game(2), greedy_and_random())
+        .run()
+        .unwrap();
+    assert_eq!(report.games, 20);
+    assert_eq!(report.round_wins.iter().sum::<usize>(), report.rounds);
+    assert_eq!(
+        report.game_wins.iter().sum::<usize>(),
+        report.completed_games
+    );
+    assert_eq!(report.round_lengths.len(), report.rounds);
+    for points in &report.points {
+        assert_eq!(points.len(), report.rounds);
+    }
+    assert!(report.starting_seat_wins <= report.rounds);
+    assert!(report.stock_exhaustions <= report.rounds + report.abandoned_rounds);
+    assert!((0.0..=1.0).contains(&report.stock_exhaustion_rate()));
+}
+
+#[test]
+fn round_winners_have_no_points() {
+    let bots: Vec<BotFactory<BasicRummyGame>> = (0..3)
+        .map(|_| -> BotFactory<BasicRummyGame> { Box::new(|_| Box::new(GreedyBot)) })
+        .collect();
+    let report = Simulation::new(config(10, 2), new_game(3), bots)
+        .run()
+        .unwrap();
+    assert_eq!(report.invalid_actions, 0);
+    let summaries: Vec<_> = (0..3).map(|seat| report.points_summary(seat)).collect();
+    assert!(report.rounds > 0);
+    assert!(summaries.iter().all(|s| s.min == 0));
+}
+
+#[test]
+fn missing_bots_fail() {
+    let result = Simulation::new(config(2, 1), new_game(3), greedy_and_random()).run();
+    assert!(matches!(
+        result,
+        Err(SimulationError::WrongBotCount { seats: 3, bots: 2 })
+    ));
+}
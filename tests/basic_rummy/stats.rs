@@ -0,0 +1,122 @@
+use crate::common::fixtures::{create_basic_game_with_history, play_basic_history_with_bots};
+use rummy::{
+    game::{
+        action::{DrawDeckAction, GameAction, GameInteractions},
+        r#trait::Game,
+        score::Points,
+        variants::basic::game::BasicRummyGame,
+    },
+    stats::{CareerStats, PlayerStats},
+    wrappers::history::History,
+};
+
+/// Count the successful actions in a history which match `filter`.
+fn count_actions(history: &History<BasicRummyGame>, filter: impl Fn(&GameAction) -> bool) -> usize {
+    history
+        .get_histories()
+        .values()
+        .flatten()
+        .filter(|entry| entry.successful)
+        .filter(|entry| matches!(&entry.entry, GameInteractions::Action(action) if filter(action)))
+        .count()
+}
+
+#[test]
+fn stats_match_the_history() {
+    let history = play_basic_history_with_bots(3, 3);
+    let mut stats = CareerStats::new();
+    stats.add_game(&history);
+    assert_eq!(stats.games(), 1);
+    let players = stats.players();
+    assert_eq!(players.len(), 3);
+
+    let round_scores = history.get_state().round_scores();
+    assert_eq!(round_scores.len(), 3);
+    for (id, player) in players {
+        assert_eq!(player.games_played, 1);
+        assert_eq!(player.rounds_played, 3);
+        let deadwood: u32 = round_scores
+            .values()
+            .map(|score| score.player_scores[id].points())
+            .sum();
+        assert_eq!(player.deadwood, u64::from(deadwood));
+        assert!(player.discard_pile_draws <= player.draws);
+    }
+    assert_eq!(players.values().map(|p| p.rounds_won).sum::<usize>(), 3);
+    assert!(players.values().map(|p| p.games_won).sum::<usize>() >= 1);
+
+    let total = |f: fn(&PlayerStats) -> usize| players.values().map(f).sum::<usize>();
+    assert_eq!(
+        total(|p| p.draws),
+        count_actions(&history, |a| matches!(
+            a,
+            GameAction::DrawDeck(_) | GameAction::DrawDiscardPile(_)
+        ))
+    );
+    assert_eq!(
+        total(|p| p.discards.values().sum()),
+        count_actions(&history, |a| matches!(a, GameAction::Discard(_)))
+    );
+    assert_eq!(
+        total(|p| p.own_layoffs + p.opponent_layoffs),
+        count_actions(&history, |a| matches!(a, GameAction::LayOff(_)))
+    );
+    assert!(total(|p| p.melds) > 0);
+
+    let player = &players[&0];
+    let ranks = player.most_discarded_ranks();
+    assert!(ranks.windows(2).all(|pair| pair[0].1 >= pair[1].1));
+    assert_eq!(
+        ranks.iter().map(|(_, n)| n).sum::<usize>(),
+        player.discards.values().sum::<usize>()
+    );
+}
+
+#[test]
+fn games_are_added_incrementally() {
+    let history = play_basic_history_with_bots(2, 1);
+    let mut once = CareerStats::new();
+    once.add_game(&history);
+    let mut twice = once.clone();
+    twice.add_game(&history);
+
+    assert_eq!(twice.games(), 2);
+    for (id, player) in once.players() {
+        let doubled = &twice.players()[id];
+        assert_eq!(doubled.games_played, 2 * player.games_played);
+        assert_eq!(doubled.games_won, 2 * player.games_won);
+        assert_eq!(doubled.draws, 2 * player.draws);
+        assert_eq!(doubled.deadwood, 2 * player.deadwood);
+        assert_eq!(doubled.win_rate(), player.win_rate());
+        assert_eq!(doubled.average_deadwood(), player.average_deadwood());
+    }
+}
+
+#[test]
+fn unfinished_rounds_dont_count() {
+    let mut history = create_basic_game_with_history(2).unwrap();
+    history.next_round().unwrap();
+    history
+        .execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let mut stats = CareerStats::new();
+    stats.add_game(&history);
+    for player in stats.players().values() {
+        assert_eq!(player.games_played, 1);
+        assert_eq!(player.games_won, 0);
+        assert_eq!(player.rounds_played, 0);
+        assert_eq!(player.draws, 0);
+        assert_eq!(player.go_out_rate(), 0.0);
+    }
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn stats_serialize() {
+    let mut stats = CareerStats::new();
+    stats.add_game(&play_basic_history_with_bots(2, 2));
+    let saved = serde_json::to_string(&stats).unwrap();
+    let loaded: CareerStats = serde_json::from_str(&saved).unwrap();
+    assert_eq!(loaded, stats);
+}
@@ -0,0 +1,325 @@
+use crate::common::fixtures::play_basic_game_with_bots;
+use rummy::tournament::{
+    ScoredRound, Table, TableResult, TieBreak, Tournament, TournamentConfig, TournamentError,
+    TournamentFormat,
+};
+use std::collections::{HashMap, HashSet};
+
+fn new_tournament(format: TournamentFormat, table_size: usize, entrants: usize) -> Tournament {
+    let config = TournamentConfig {
+        format,
+        table_size,
+        ..TournamentConfig::default()
+    };
+    let names = (0..entrants).map(|i| format!("Player {i}")).collect();
+    Tournament::new(config, names).unwrap()
+}
+
+/// A result where each round is `(winner_id, points of each player)`.
+fn result(rounds: &[(usize, &[(usize, u32)])]) -> TableResult {
+    TableResult {
+        rounds: rounds
+            .iter()
+            .map(|(winner_id, points)| ScoredRound {
+                points: points.iter().copied().collect(),
+                winner_id: *winner_id,
+            })
+            .collect(),
+    }
+}
+
+/// A single round, which the lowest entrant ID at the table wins, and everyone else gets 10 points.
+fn lowest_id_wins(table: &Table) -> TableResult {
+    let winner = *table.players.iter().min().unwrap();
+    let points: Vec<_> = table
+        .players
+        .iter()
+        .map(|&id| (id, if id == winner { 0 } else { 10 }))
+        .collect();
+    result(&[(winner, &points)])
+}
+
+/// Start the next round and record every table's result.
+fn play_round(tournament: &mut Tournament, decide: impl Fn(&Table) -> TableResult) {
+    let round = tournament.start_round().unwrap().clone();
+    for (table_index, table) in round.tables.iter().enumerate() {
+        tournament
+            .record_result(table_index, decide(table))
+            .unwrap();
+    }
+}
+
+/// Play 2 rounds of a basic game at the table, between greedy bots.
+fn play_basic_game(table: &Table) -> TableResult {
+    let game = play_basic_game_with_bots(table.players.len(), 2);
+    // the game's player IDs are 0.., so map them back to the entrants
+    let mut result = TableResult::from_game(&game);
+    for round in &mut result.rounds {
+        round.winner_id = table.players[round.winner_id];
+        round.points = round
+            .points
+            .iter()
+            .map(|(&seat, &points)| (table.players[seat], points))
+            .collect();
+    }
+    result
+}
+
+fn pair(table: &Table) -> (usize, usize) {
+    let (a, b) = (table.players[0], table.players[1]);
+    (a.min(b), a.max(b))
+}
+
+#[test]
+fn swiss_avoids_rematches_and_spreads_byes() {
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 3 }, 2, 5);
+    let mut pairs = HashSet::new();
+    let mut byes = HashSet::new();
+    for _ in 0..3 {
+        play_round(&mut tournament, lowest_id_wins);
+        let round = tournament.rounds().last().unwrap();
+        assert_eq!(round.tables.len(), 2);
+        assert_eq!(round.byes.len(), 1);
+        assert!(byes.insert(round.byes[0]));
+        for table in &round.tables {
+            assert!(pairs.insert(pair(table)));
+        }
+    }
+    assert!(tournament.is_finished());
+    assert_eq!(tournament.winner(), Some(0));
+    assert!(matches!(
+        tournament.start_round(),
+        Err(TournamentError::Finished)
+    ));
+}
+
+#[test]
+fn swiss_spreads_players_over_even_tables() {
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 2 }, 4, 10);
+    play_round(&mut tournament, lowest_id_wins);
+    let round = tournament.start_round().unwrap();
+    let sizes: Vec<_> = round.tables.iter().map(|t| t.players.len()).collect();
+    assert_eq!(sizes, vec![4, 3, 3]);
+    assert!(round.byes.is_empty());
+    // the round 1 winners lead the standings and haven't met yet, so they're seated together
+    assert_eq!(
+        round.tables[0]
+            .players
+            .iter()
+            .filter(|&&id| id == 0 || id == 4 || id == 7)
+            .count(),
+        3
+    );
+}
+
+#[test]
+fn round_robin_pairs_everyone_once() {
+    let mut tournament = new_tournament(TournamentFormat::RoundRobin, 2, 6);
+    let mut pairs = HashSet::new();
+    let mut first_seats: HashMap<usize, usize> = HashMap::new();
+    while !tournament.is_finished() {
+        play_round(&mut tournament, lowest_id_wins);
+        for table in &tournament.rounds().last().unwrap().tables {
+            assert!(pairs.insert(pair(table)));
+            *first_seats.entry(table.players[0]).or_default() += 1;
+        }
+    }
+    assert_eq!(tournament.rounds().len(), 5);
+    assert_eq!(pairs.len(), 15);
+    assert!(first_seats.values().all(|&count| count == 2 || count == 3));
+
+    let standings = tournament.standings();
+    let match_points: Vec<_> = standings.iter().map(|s| s.match_points).collect();
+    assert_eq!(match_points, vec![5, 4, 3, 2, 1, 0]);
+}
+
+#[test]
+fn round_robin_gives_odd_players_byes() {
+    let mut tournament = new_tournament(TournamentFormat::RoundRobin, 2, 5);
+    while !tournament.is_finished() {
+        play_round(&mut tournament, lowest_id_wins);
+    }
+    let byes: HashSet<_> = tournament
+        .rounds()
+        .iter()
+        .flat_map(|r| r.byes.clone())
+        .collect();
+    assert_eq!(tournament.rounds().len(), 5);
+    assert_eq!(byes.len(), 5);
+}
+
+#[test]
+fn knockout_plays_down_to_one() {
+    let mut tournament = new_tournament(TournamentFormat::Knockout, 2, 8);
+    play_round(&mut tournament, lowest_id_wins);
+    let first_round: HashSet<_> = tournament.rounds()[0].tables.iter().map(pair).collect();
+    assert_eq!(first_round, HashSet::from([(0, 7), (1, 6), (2, 5), (3, 4)]));
+    assert_eq!(tournament.remaining(), vec![0, 1, 2, 3]);
+
+    while !tournament.is_finished() {
+        play_round(&mut tournament, lowest_id_wins);
+    }
+    assert_eq!(tournament.rounds().len(), 3);
+    assert_eq!(tournament.remaining(), vec![0]);
+    assert_eq!(tournament.winner(), Some(0));
+    let standings: Vec<_> = tournament
+        .standings()
+        .iter()
+        .map(|s| s.entrant_id)
+        .collect();
+    assert_eq!(&standings[..2], &[0, 1]);
+}
+
+#[test]
+fn knockout_with_bigger_tables() {
+    let mut tournament = new_tournament(TournamentFormat::Knockout, 4, 7);
+    let round = tournament.start_round().unwrap();
+    let mut sizes: Vec<_> = round.tables.iter().map(|t| t.players.len()).collect();
+    sizes.sort();
+    assert_eq!(sizes, vec![3, 4]);
+    tournament
+        .record_result(0, lowest_id_wins(&tournament.rounds()[0].tables[0].clone()))
+        .unwrap();
+    tournament
+        .record_result(1, lowest_id_wins(&tournament.rounds()[0].tables[1].clone()))
+        .unwrap();
+    assert_eq!(tournament.remaining(), vec![0, 1]);
+
+    play_round(&mut tournament, lowest_id_wins);
+    assert!(tournament.is_finished());
+    assert_eq!(tournament.winner(), Some(0));
+}
+
+#[test]
+fn tie_breaks_use_round_scores() {
+    // 0 beats 1 winning 2 of 3 rounds, 2 beats 3 winning all 3 with fewer points
+    let decide = |table: &Table| match pair(table) {
+        (0, 1) => result(&[
+            (0, &[(0, 0), (1, 10)]),
+            (1, &[(0, 5), (1, 0)]),
+            (0, &[(0, 0), (1, 10)]),
+        ]),
+        _ => result(&[
+            (2, &[(2, 0), (3, 10)]),
+            (2, &[(2, 0), (3, 10)]),
+            (2, &[(2, 0), (3, 10)]),
+        ]),
+    };
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 1 }, 2, 4);
+    play_round(&mut tournament, decide);
+    let standings = tournament.standings();
+    assert_eq!(standings[0].entrant_id, 2);
+    assert_eq!(standings[0].rounds_won, 3);
+    assert_eq!(standings[1].entrant_id, 0);
+    assert_eq!(standings[1].points, 5);
+
+    let config = TournamentConfig {
+        format: TournamentFormat::Swiss { rounds: 1 },
+        table_size: 2,
+        tie_breaks: vec![],
+    };
+    let names = (0..4).map(|i| i.to_string()).collect();
+    let mut tournament = Tournament::new(config, names).unwrap();
+    play_round(&mut tournament, decide);
+    assert_eq!(tournament.standings()[0].entrant_id, 0);
+
+    let config = TournamentConfig {
+        format: TournamentFormat::Swiss { rounds: 1 },
+        table_size: 2,
+        tie_breaks: vec![TieBreak::FewestPoints],
+    };
+    let names = (0..4).map(|i| i.to_string()).collect();
+    let mut tournament = Tournament::new(config, names).unwrap();
+    play_round(&mut tournament, decide);
+    assert_eq!(tournament.standings()[0].entrant_id, 2);
+}
+
+#[test]
+fn invalid_setups_and_results_fail() {
+    let names = |n: usize| (0..n).map(|i| i.to_string()).collect::<Vec<_>>();
+    let config = |format, table_size| TournamentConfig {
+        format,
+        table_size,
+        ..TournamentConfig::default()
+    };
+    assert!(matches!(
+        Tournament::new(config(TournamentFormat::Knockout, 2), names(1)),
+        Err(TournamentError::NotEnoughEntrants(1))
+    ));
+    assert!(matches!(
+        Tournament::new(config(TournamentFormat::Knockout, 1), names(4)),
+        Err(TournamentError::InvalidTableSize(1))
+    ));
+    assert!(matches!(
+        Tournament::new(config(TournamentFormat::RoundRobin, 3), names(4)),
+        Err(TournamentError::RoundRobinTableSize(3))
+    ));
+
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 2 }, 2, 4);
+    assert!(matches!(
+        tournament.record_result(0, result(&[(0, &[(0, 0), (1, 10)])])),
+        Err(TournamentError::NoRoundInProgress)
+    ));
+    let table = tournament.start_round().unwrap().tables[0].clone();
+    assert!(matches!(
+        tournament.start_round(),
+        Err(TournamentError::RoundInProgress)
+    ));
+    assert!(matches!(
+        tournament.record_result(2, lowest_id_wins(&table)),
+        Err(TournamentError::TableDoesntExist(2))
+    ));
+    assert!(matches!(
+        tournament.record_result(0, TableResult { rounds: vec![] }),
+        Err(TournamentError::EmptyResult)
+    ));
+    assert!(matches!(
+        tournament.record_result(0, result(&[(3, &[(2, 0), (3, 10)])])),
+        Err(TournamentError::WrongPlayers(0))
+    ));
+    tournament.record_result(0, lowest_id_wins(&table)).unwrap();
+    assert!(matches!(
+        tournament.record_result(0, lowest_id_wins(&table)),
+        Err(TournamentError::AlreadyRecorded(0))
+    ));
+}
+
+#[test]
+fn players_missing_rounds_place_last() {
+    // player 1 quit after the first round, so isn't in the second
+    let table_result = result(&[(1, &[(0, 40), (1, 0)]), (0, &[(0, 0)])]);
+    assert_eq!(table_result.placings(&[1, 0]), vec![0, 1]);
+    assert_eq!(table_result.players_beaten(0, &[0, 1]), 1);
+}
+
+#[test]
+fn tables_play_basic_games() {
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 2 }, 2, 4);
+    while !tournament.is_finished() {
+        play_round(&mut tournament, play_basic_game);
+    }
+    let standings = tournament.standings();
+    assert!(tournament.winner().is_some());
+    assert!(standings.iter().all(|s| s.rounds_played == 2));
+    assert_eq!(
+        standings.iter().map(|s| s.rounds_won).sum::<usize>(),
+        2 * 2 * 2
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn tournaments_resume_after_serializing() {
+    let mut tournament = new_tournament(TournamentFormat::Swiss { rounds: 2 }, 2, 4);
+    play_round(&mut tournament, lowest_id_wins);
+    let table = tournament.start_round().unwrap().tables[1].clone();
+    tournament.record_result(1, lowest_id_wins(&table)).unwrap();
+
+    let saved = serde_json::to_string(&tournament).unwrap();
+    let mut resumed: Tournament = serde_json::from_str(&saved).unwrap();
+    assert_eq!(resumed, tournament);
+
+    let table = resumed.current_round().unwrap().tables[0].clone();
+    resumed.record_result(0, lowest_id_wins(&table)).unwrap();
+    assert!(resumed.is_finished());
+}
@@ -0,0 +1,231 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use rummy::{
+    cards::card::CardData,
+    game::{
+        action::{DiscardAction, DrawDeckAction, DrawDiscardPileAction, GameAction},
+        r#trait::Game,
+        state::{GamePhase, QuitHandPolicy},
+        tracker::CardTracker,
+        variants::basic::{config::BasicConfig, game::BasicRummyGame},
+    },
+    simulation::bots::{Bot, GreedyBot},
+    wrappers::observed::Observed,
+};
+use std::sync::{Arc, Mutex};
+
+/// Wrap a game so that every event goes to a tracker for each of `viewers`.
+fn track(
+    game: BasicRummyGame,
+    viewers: &[usize],
+) -> (Observed<BasicRummyGame>, Vec<Arc<Mutex<CardTracker>>>) {
+    let deck_config = game.get_state().deck().config().clone();
+    let mut game = Observed::new(game);
+    let trackers: Vec<_> = viewers
+        .iter()
+        .map(|&viewer| Arc::new(Mutex::new(CardTracker::new(viewer, &deck_config))))
+        .collect();
+    for tracker in &trackers {
+        let tracker = tracker.clone();
+        game.subscribe(move |event| tracker.lock().unwrap().observe(event));
+    }
+    (game, trackers)
+}
+
+fn hand(game: &Observed<BasicRummyGame>, player_id: usize) -> Vec<CardData> {
+    let state = game.get_state();
+    let player = state
+        .players()
+        .iter()
+        .find(|p| p.id() == player_id)
+        .unwrap();
+    player.cards().iter().map(|c| c.data()).collect()
+}
+
+fn sorted(mut cards: Vec<CardData>) -> Vec<CardData> {
+    cards.sort();
+    cards
+}
+
+/// Whether every card in `part` is in `whole`, counting copies.
+fn contains_all(whole: &[CardData], part: &[CardData]) -> bool {
+    let mut whole = whole.to_vec();
+    part.iter()
+        .all(|card| match whole.iter().position(|c| c == card) {
+            Some(index) => {
+                whole.remove(index);
+                true
+            }
+            None => false,
+        })
+}
+
+#[test]
+fn tracks_cards_picked_up_from_the_discard_pile() {
+    let (mut game, trackers) = track(create_basic_game(2).unwrap(), &[0]);
+    game.next_round().unwrap();
+    {
+        let tracker = trackers[0].lock().unwrap();
+        assert_eq!(tracker.hand(), hand(&game, 0));
+        assert_eq!(tracker.unseen().len(), 52 - tracker.hand().len());
+        assert!(contains_all(&tracker.unseen(), &hand(&game, 1)));
+    }
+
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let discarded = hand(&game, 0)[0];
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    game.execute_action(GameAction::DrawDiscardPile(DrawDiscardPileAction {
+        count: Some(1),
+    }))
+    .unwrap();
+    {
+        let tracker = trackers[0].lock().unwrap();
+        assert_eq!(tracker.hand(), hand(&game, 0));
+        assert_eq!(tracker.discarded(), &[discarded]);
+        assert!(tracker.discard_pile().is_empty());
+        assert_eq!(tracker.known_hand(1), &[discarded]);
+        assert_eq!(
+            tracker.unseen().len(),
+            52 - tracker.hand().len() - tracker.known_hand(1).len()
+        );
+    }
+
+    // player 1 discards something other than the card they picked up, so it's still known
+    let card_index = hand(&game, 1).iter().position(|c| *c != discarded).unwrap();
+    let other_discard = hand(&game, 1)[card_index];
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    let tracker = trackers[0].lock().unwrap();
+    assert_eq!(tracker.known_hand(1), &[discarded]);
+    assert_eq!(tracker.discard_pile(), &[other_discard]);
+    assert_eq!(tracker.discarded(), &[discarded, other_discard]);
+    assert_eq!(tracker.unseen_count(&other_discard), 0);
+}
+
+#[test]
+fn private_draws_stay_private() {
+    let (mut game, trackers) = track(create_basic_game(2).unwrap(), &[0, 1]);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let drawn = *hand(&game, 0).last().unwrap();
+
+    let own = trackers[0].lock().unwrap();
+    let other = trackers[1].lock().unwrap();
+    assert!(own.hand().contains(&drawn));
+    assert!(own.known_hand(0).is_empty());
+    assert!(other.known_hand(0).is_empty());
+    assert_eq!(
+        other.unseen_count(&drawn),
+        1 - usize::from(other.hand().contains(&drawn))
+    );
+}
+
+#[test]
+fn syncing_catches_up_with_the_view() {
+    let (mut game, _) = track(create_basic_game(2).unwrap(), &[]);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+
+    // a tracker which missed every event still knows what's visible after syncing
+    let mut tracker = CardTracker::new(1, game.get_state().deck().config());
+    tracker.sync(&game.get_state().view_for(Some(1)));
+    assert_eq!(tracker.hand(), hand(&game, 1));
+    assert_eq!(tracker.discard_pile().len(), 1);
+    assert_eq!(
+        tracker.unseen().len(),
+        52 - tracker.hand().len() - tracker.discard_pile().len()
+    );
+}
+
+#[test]
+fn trackers_stay_consistent_over_a_game() {
+    let game = create_basic_game_with_config(3, Some(4), None, None).unwrap();
+    let (mut game, trackers) = track(game, &[0, 1, 2]);
+    game.next_round().unwrap();
+    for _ in 0..300 {
+        if !matches!(game.get_state().phase(), GamePhase::Draw | GamePhase::Play) {
+            break;
+        }
+        let player_id = game.get_state().get_current_player().unwrap().id();
+        let action = GreedyBot.next_action(&game, player_id);
+        game.execute_action(action).unwrap();
+
+        for tracker in &trackers {
+            let mut tracker = tracker.lock().unwrap();
+            let viewer = tracker.viewer();
+            // the events alone are enough to follow the viewer's own hand
+            assert_eq!(sorted(tracker.hand().to_vec()), sorted(hand(&game, viewer)));
+            tracker.sync(&game.get_state().view_for(Some(viewer)));
+
+            // every unseen card is in the stock or an opponent's hand, and wasn't seen going there
+            let mut hidden: Vec<_> = game
+                .get_state()
+                .deck()
+                .stock()
+                .iter()
+                .map(|c| c.data())
+                .collect();
+            for player in game.get_state().players() {
+                if player.id() == viewer {
+                    continue;
+                }
+                let cards = hand(&game, player.id());
+                let known = tracker.known_hand(player.id());
+                assert!(contains_all(&cards, known));
+                let mut unknown = cards;
+                for card in known {
+                    let index = unknown.iter().position(|c| c == card).unwrap();
+                    unknown.remove(index);
+                }
+                hidden.extend(unknown);
+            }
+            assert_eq!(sorted(tracker.unseen()), sorted(hidden));
+        }
+    }
+}
+
+#[test]
+fn tracks_a_quitting_players_discarded_hand() {
+    let config = BasicConfig {
+        deal_amount: None,
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: Some(QuitHandPolicy::Discard),
+    };
+    let game = create_basic_game_with_config(3, Some(1), Some(config), None).unwrap();
+    let (mut game, trackers) = track(game, &[0]);
+    game.next_round().unwrap();
+    let quitter_hand = hand(&game, 2);
+
+    game.quit_player(2).unwrap();
+    let tracker = trackers[0].lock().unwrap();
+    assert_eq!(
+        tracker.discard_pile(),
+        game.get_state()
+            .deck()
+            .discard_pile()
+            .iter()
+            .map(|c| c.data())
+            .collect::<Vec<_>>()
+    );
+    assert!(contains_all(tracker.discarded(), &quitter_hand));
+    assert!(!quitter_hand.is_empty());
+    // without having to sync, the hand is known to be on the discard pile
+    assert!(quitter_hand
+        .iter()
+        .all(|card| tracker.unseen_count(card) == 0));
+}
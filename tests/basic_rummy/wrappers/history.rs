@@ -1,7 +1,10 @@
 use crate::common::fixtures::create_basic_game_with_history;
-use rummy::game::{
-    action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction, GameInteractions},
-    r#trait::Game,
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction, GameInteractions},
+        r#trait::Game,
+    },
+    wrappers::history::UndoError,
 };
 
 #[test]
@@ -87,3 +90,110 @@ fn history_is_correct() {
 
     assert_eq!(round_history.len(), 5);
 }
+
+#[test]
+fn undo_takes_back_moves_in_the_turn() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.set_undo_enabled(true);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let after_draw = game.get_state().clone();
+
+    let player_id = game.get_state().get_current_player().unwrap().id();
+    let mut hand: Vec<_> = game.get_state().players()[0]
+        .cards()
+        .iter()
+        .map(|c| c.data())
+        .collect();
+    hand.sort();
+    game.rearrange_player_hand(player_id, hand).unwrap();
+    let after_rearrangement = game.get_state().clone();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }))
+    .unwrap();
+    let after_meld = game.get_state().clone();
+
+    game.undo().unwrap();
+    assert_eq!(game.get_state(), &after_rearrangement);
+    game.undo().unwrap();
+    assert_eq!(game.get_state(), &after_draw);
+    // drawing from the deck revealed a card, so it can't be taken back
+    assert_eq!(game.undo(), Err(UndoError::NothingToUndo));
+
+    assert!(game.can_redo());
+    game.redo().unwrap();
+    game.redo().unwrap();
+    assert_eq!(game.get_state(), &after_meld);
+    assert_eq!(game.redo(), Err(UndoError::NothingToRedo));
+
+    // the undone entries are kept, and the redone ones recorded again
+    let round_history = &game.get_histories()[&1];
+    let undone: Vec<_> = round_history.iter().map(|entry| entry.undone).collect();
+    assert_eq!(undone, vec![false, true, true, false, false]);
+    assert_eq!(round_history[1].entry, round_history[3].entry);
+    assert_eq!(round_history[2].entry, round_history[4].entry);
+}
+
+#[test]
+fn undo_is_opt_in_and_locked_by_discarding() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![0, 1, 2],
+    }))
+    .unwrap_err();
+    assert_eq!(game.undo(), Err(UndoError::Disabled));
+
+    game.set_undo_enabled(true);
+    let player_id = game.get_state().get_current_player().unwrap().id();
+    let mut hand: Vec<_> = game.get_state().players()[0]
+        .cards()
+        .iter()
+        .map(|c| c.data())
+        .collect();
+    hand.reverse();
+    game.rearrange_player_hand(player_id, hand.clone()).unwrap();
+    game.undo().unwrap();
+
+    // doing anything else means the undone rearrangement can't be redone
+    game.rearrange_player_hand(player_id, hand).unwrap();
+    assert_eq!(game.redo(), Err(UndoError::NothingToRedo));
+
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    assert_eq!(game.undo(), Err(UndoError::NothingToUndo));
+}
+
+#[test]
+fn undo_and_redo_are_rejected_while_paused() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.set_undo_enabled(true);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let player_id = game.get_state().get_current_player().unwrap().id();
+    let mut hand: Vec<_> = game.get_state().players()[0]
+        .cards()
+        .iter()
+        .map(|c| c.data())
+        .collect();
+    hand.reverse();
+    game.rearrange_player_hand(player_id, hand.clone()).unwrap();
+    hand.reverse();
+    game.rearrange_player_hand(player_id, hand).unwrap();
+    game.undo().unwrap();
+    let before_pause = game.get_state().clone();
+
+    game.pause().unwrap();
+    assert_eq!(game.undo(), Err(UndoError::GamePaused));
+    assert_eq!(game.redo(), Err(UndoError::GamePaused));
+    game.resume().unwrap();
+    assert_eq!(game.get_state(), &before_pause);
+}
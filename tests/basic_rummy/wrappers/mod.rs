@@ -1,2 +1,5 @@
 pub mod history;
+pub mod observed;
+pub mod record;
 pub mod replay;
+pub mod timed;
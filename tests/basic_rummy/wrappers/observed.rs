@@ -0,0 +1,251 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+        event::{DrawSource, EventVisibility, GameEvent},
+        r#trait::Game,
+        state::{GamePhase, QuitHandPolicy},
+        variants::basic::{config::BasicConfig, game::BasicRummyGame, score::BasicScore},
+    },
+    wrappers::observed::Observed,
+};
+use std::sync::{Arc, Mutex};
+
+/// The events emitted by an observed game.
+type Events = Arc<Mutex<Vec<GameEvent<BasicScore>>>>;
+
+/// Creates an observed game, along with the list of events it emits.
+fn observed_game() -> (Observed<BasicRummyGame>, Events) {
+    observe(create_basic_game(2).unwrap())
+}
+
+/// Observes `game`, returning it along with the list of events it emits.
+fn observe(game: BasicRummyGame) -> (Observed<BasicRummyGame>, Events) {
+    let mut game = Observed::new(game);
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = events.clone();
+    game.subscribe(move |event| events_clone.lock().unwrap().push(event.clone()));
+    (game, events)
+}
+
+/// Take all events emitted so far.
+fn take(events: &Events) -> Vec<GameEvent<BasicScore>> {
+    std::mem::take(&mut *events.lock().unwrap())
+}
+
+#[test]
+fn round_start_events() {
+    let (mut game, events) = observed_game();
+    game.next_round().unwrap();
+
+    let events = take(&events);
+    assert_eq!(events[0], GameEvent::RoundStarted { round: 1 });
+    for (i, player) in game.get_state().players().iter().enumerate() {
+        let cards = player.cards().iter().map(|c| c.data()).collect();
+        assert_eq!(
+            events[i + 1],
+            GameEvent::HandDealt {
+                player_id: player.id(),
+                cards
+            }
+        );
+        assert_eq!(
+            events[i + 1].visibility(),
+            EventVisibility::Private {
+                player_id: player.id()
+            }
+        );
+    }
+    assert_eq!(
+        events[3..],
+        [
+            GameEvent::PhaseChanged {
+                phase: GamePhase::Draw
+            },
+            GameEvent::TurnChanged { player_id: 0 },
+        ]
+    );
+}
+
+#[test]
+fn turn_events() {
+    let (mut game, events) = observed_game();
+    game.next_round().unwrap();
+    take(&events);
+
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let drawn = game.get_state().players()[0].cards().last().unwrap().data();
+    let draw_events = take(&events);
+    assert_eq!(
+        draw_events,
+        [
+            GameEvent::HiddenCardsDrawn {
+                player_id: 0,
+                amount: 1
+            },
+            GameEvent::CardsDrawn {
+                player_id: 0,
+                source: DrawSource::Deck,
+                cards: vec![drawn]
+            },
+            GameEvent::PhaseChanged {
+                phase: GamePhase::Play
+            },
+        ]
+    );
+    assert!(draw_events[0].is_visible_to(1));
+    assert!(draw_events[1].is_visible_to(0));
+    assert!(!draw_events[1].is_visible_to(1));
+
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.sort();
+    game.rearrange_player_hand(0, hand.clone()).unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }))
+    .unwrap();
+    assert_eq!(
+        take(&events),
+        [GameEvent::MeldFormed {
+            player_id: 0,
+            meld_index: 0,
+            cards: hand[1..=4].to_vec()
+        }]
+    );
+
+    // failed actions don't emit anything
+    assert!(game
+        .execute_action(GameAction::Discard(DiscardAction {
+            card_index: 100,
+            declare_going_out: None,
+        }))
+        .is_err());
+    assert!(take(&events).is_empty());
+
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    assert_eq!(
+        take(&events),
+        [
+            GameEvent::CardDiscarded {
+                player_id: 0,
+                card: hand[0]
+            },
+            GameEvent::PhaseChanged {
+                phase: GamePhase::Draw
+            },
+            GameEvent::TurnChanged { player_id: 1 },
+        ]
+    );
+}
+
+#[test]
+fn player_events_and_unsubscribing() {
+    let (mut game, events) = observed_game();
+    game.next_round().unwrap();
+    game.add_player(2).unwrap();
+    assert_eq!(
+        take(&events).last(),
+        Some(&GameEvent::PlayerJoined { player_id: 2 })
+    );
+
+    let other_events = Arc::new(Mutex::new(Vec::new()));
+    let other_events_clone = other_events.clone();
+    let id = game.subscribe(move |event| other_events_clone.lock().unwrap().push(event.clone()));
+    assert!(game.unsubscribe(id));
+    assert!(!game.unsubscribe(id));
+
+    game.quit_player(2).unwrap();
+    assert_eq!(take(&events), [GameEvent::PlayerQuit { player_id: 2 }]);
+    assert!(take(&other_events).is_empty());
+}
+
+#[test]
+fn round_end_events() {
+    // players are dealt no cards, so the round ends as soon as the first player draws and discards
+    let config = BasicConfig {
+        deal_amount: Some(0),
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let game = create_basic_game_with_config(2, Some(1), Some(config), None).unwrap();
+    let (mut game, events) = observe(game);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    take(&events);
+
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    let events_at_end = take(&events);
+    let score = game.get_state().round_scores()[&1].clone();
+    assert_eq!(score.winner_id, 0);
+    assert_eq!(
+        events_at_end[1..],
+        [
+            GameEvent::PhaseChanged {
+                phase: GamePhase::RoundEnd
+            },
+            GameEvent::RoundEnded { round: 1, score },
+        ]
+    );
+
+    // the round isn't ended again when the next one starts
+    game.next_round().unwrap();
+    assert_eq!(take(&events)[0], GameEvent::RoundStarted { round: 2 });
+}
+
+#[test]
+fn quit_hand_policy_events() {
+    for policy in [
+        QuitHandPolicy::Keep,
+        QuitHandPolicy::ReturnToStock,
+        QuitHandPolicy::Discard,
+    ] {
+        let config = BasicConfig {
+            deal_amount: None,
+            draw_deck_amount: None,
+            draw_discard_pile_amount: None,
+            quit_hand_policy: Some(policy),
+        };
+        let game = create_basic_game_with_config(3, Some(1), Some(config), None).unwrap();
+        let (mut game, events) = observe(game);
+        game.next_round().unwrap();
+        let hand: Vec<_> = game.get_state().players()[2]
+            .cards()
+            .iter()
+            .map(|c| c.data())
+            .collect();
+        take(&events);
+
+        game.quit_player(2).unwrap();
+        let quit = GameEvent::PlayerQuit { player_id: 2 };
+        let expected = match policy {
+            QuitHandPolicy::Keep => vec![quit],
+            QuitHandPolicy::ReturnToStock => vec![
+                quit,
+                GameEvent::HandReturnedToStock {
+                    player_id: 2,
+                    amount: hand.len(),
+                },
+            ],
+            QuitHandPolicy::Discard => vec![
+                quit,
+                GameEvent::HandDiscarded {
+                    player_id: 2,
+                    cards: hand,
+                },
+            ],
+        };
+        assert_eq!(take(&events), expected);
+    }
+}
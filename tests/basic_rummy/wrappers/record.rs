@@ -0,0 +1,166 @@
+use crate::common::fixtures::create_basic_game_with_history;
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, FormMeldAction, GameAction},
+        r#trait::Game,
+        variants::basic::game::BasicRummyGame,
+    },
+    wrappers::{history::History, record::RecordError},
+};
+
+/// Plays a short game with history, including a failed action, a join and a hand rearrangement.
+fn recorded_game() -> History<BasicRummyGame> {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.next_round().unwrap();
+    game.add_player(2).unwrap();
+
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let cur_player = game.get_state().get_current_player().unwrap();
+    let mut hand: Vec<_> = cur_player.cards().iter().map(|c| c.data()).collect();
+    hand.sort();
+    game.rearrange_player_hand(cur_player.id(), hand).unwrap();
+    game.execute_action(GameAction::FormMeld(FormMeldAction {
+        card_indices: vec![1, 2, 3, 4],
+    }))
+    .unwrap();
+    assert!(game
+        .execute_action(GameAction::Discard(DiscardAction {
+            card_index: 10000,
+            declare_going_out: None,
+        }))
+        .is_err());
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    game
+}
+
+#[test]
+fn record_round_trip() {
+    let game = recorded_game();
+    let record = game.to_record().unwrap();
+
+    let parsed = History::<BasicRummyGame>::from_record(&record).unwrap();
+    assert_eq!(parsed.get_game(), game.get_game());
+    for (round, entries) in game.get_histories() {
+        let parsed_entries = &parsed.get_histories()[round];
+        assert_eq!(entries.len(), parsed_entries.len());
+        for (entry, parsed_entry) in entries.iter().zip(parsed_entries) {
+            assert_eq!(entry.entry, parsed_entry.entry);
+            assert_eq!(entry.successful, parsed_entry.successful);
+        }
+    }
+
+    // writing the parsed game gives the exact same record
+    assert_eq!(parsed.to_record().unwrap(), record);
+}
+
+#[test]
+fn record_is_readable() {
+    let record = recorded_game().to_record().unwrap();
+    let lines: Vec<_> = record.lines().collect();
+
+    assert_eq!(lines[0], "[Variant \"basic\"]");
+    assert_eq!(lines[1], "[Players \"0 1\"]");
+    assert!(lines.contains(&"== round 0 =="));
+    assert!(lines.contains(&"== round 1 =="));
+    assert!(lines.contains(&"join 2"));
+    assert!(lines.contains(&"meld 1 2 3 4"));
+    assert!(lines.contains(&"! discard 10000"));
+    assert!(lines.iter().any(|l| l.starts_with("arrange 0 ")));
+}
+
+#[test]
+fn replaced_player_round_trip() {
+    let mut game = create_basic_game_with_history(3).unwrap();
+    game.next_round().unwrap();
+    game.quit_player(1).unwrap();
+    game.replace_player(1, 7).unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    let record = game.to_record().unwrap();
+    assert!(record.lines().any(|l| l == "replace 1 7"));
+    let parsed = History::<BasicRummyGame>::from_record(&record).unwrap();
+    assert_eq!(parsed.get_game(), game.get_game());
+}
+
+#[test]
+fn undone_entries_round_trip() {
+    let mut game = create_basic_game_with_history(2).unwrap();
+    game.set_undo_enabled(true);
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    let player_id = game.get_state().get_current_player().unwrap().id();
+    let mut hand: Vec<_> = game.get_state().players()[0]
+        .cards()
+        .iter()
+        .map(|c| c.data())
+        .collect();
+    hand.sort();
+    game.rearrange_player_hand(player_id, hand).unwrap();
+    game.undo().unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+
+    let record = game.to_record().unwrap();
+    assert!(record.lines().any(|l| l.starts_with("~ arrange 0 ")));
+    let parsed = History::<BasicRummyGame>::from_record(&record).unwrap();
+    assert_eq!(parsed.get_game(), game.get_game());
+    assert!(parsed.get_histories()[&1][1].undone);
+    assert_eq!(parsed.to_record().unwrap(), record);
+}
+
+#[test]
+fn tampered_record_fails_to_replay() {
+    let record = recorded_game().to_record().unwrap();
+
+    // the failed discard is now recorded as successful
+    let tampered = record.replace("! discard 10000", "discard 10000");
+    assert!(matches!(
+        History::<BasicRummyGame>::from_record(&tampered),
+        Err(RecordError::ReplayMismatch { .. })
+    ));
+
+    let tampered = record.replace("[Variant \"basic\"]", "[Variant \"gin\"]");
+    assert!(matches!(
+        History::<BasicRummyGame>::from_record(&tampered),
+        Err(RecordError::WrongVariant { .. })
+    ));
+
+    let tampered = record.replace("meld 1 2 3 4", "meld one two");
+    assert!(matches!(
+        History::<BasicRummyGame>::from_record(&tampered),
+        Err(RecordError::InvalidLine { .. })
+    ));
+}
+
+#[test]
+fn unseeded_game_is_recorded_with_its_random_seed() {
+    use rummy::{cards::deck::DeckConfig, game::variants::basic::config::BasicConfig};
+
+    let config = BasicConfig {
+        deal_amount: None,
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let mut game = History::new(vec![0, 1], config, DeckConfig::new()).unwrap();
+    game.next_round().unwrap();
+
+    let record = game.to_record().unwrap();
+    let seed = game.get_state().deck().config().shuffle_seed.unwrap();
+    assert!(record.contains(&format!("[Seed \"{seed}\"]")));
+    let loaded = History::<BasicRummyGame>::from_record(&record).unwrap();
+    assert_eq!(loaded.get_state(), game.get_state());
+}
@@ -0,0 +1,262 @@
+use crate::common::fixtures::{create_basic_game, create_basic_game_with_config};
+use chrono::{DateTime, Duration};
+use rummy::{
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        error::{ActionError, FailedActionError},
+        r#trait::Game,
+        state::GamePhase,
+        variants::basic::{config::BasicConfig, game::BasicRummyGame},
+    },
+    wrappers::timed::{ManualClock, TimeControl, Timed, Timeout, TimeoutBot, TimeoutPolicy},
+};
+
+/// Creates a timed game with `player_count` players which has started its first round,
+/// along with a handle to its clock.
+fn timed_game(
+    player_count: usize,
+    control: TimeControl,
+    policy: TimeoutPolicy<BasicRummyGame>,
+) -> (Timed<BasicRummyGame, ManualClock>, ManualClock) {
+    let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+    let mut game = Timed::new(
+        create_basic_game(player_count).unwrap(),
+        control,
+        policy,
+        clock.clone(),
+    );
+    game.next_round().unwrap();
+    (game, clock)
+}
+
+/// Play the current player's turn by drawing from the deck and discarding their first card.
+fn play_turn(game: &mut impl Game) {
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+}
+
+fn current_player_id(game: &impl Game) -> usize {
+    game.get_state().get_current_player().unwrap().id()
+}
+
+/// A bot that draws from the deck and discards its first card.
+struct FirstCardBot;
+
+impl TimeoutBot<BasicRummyGame> for FirstCardBot {
+    fn next_action(&mut self, game: &BasicRummyGame, _player_id: usize) -> GameAction {
+        match game.get_state().phase() {
+            GamePhase::Draw => GameAction::DrawDeck(DrawDeckAction {}),
+            _ => GameAction::Discard(DiscardAction {
+                card_index: 0,
+                declare_going_out: None,
+            }),
+        }
+    }
+}
+
+#[test]
+fn timeout_draws_and_discards() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::DrawAndDiscard);
+    let hand = game.get_state().players()[0].cards().clone();
+
+    clock.advance(Duration::seconds(29));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    assert_eq!(game.turn_time_remaining(), Some(Duration::seconds(1)));
+
+    clock.advance(Duration::seconds(2));
+    assert_eq!(
+        game.check_timeout().unwrap(),
+        Some(Timeout {
+            player_id: 0,
+            round: 1
+        })
+    );
+    // the drawn card was discarded, leaving the hand unchanged
+    assert_eq!(game.get_state().players()[0].cards(), &hand);
+    assert_eq!(current_player_id(&game), 1);
+    assert_eq!(game.get_state().phase(), GamePhase::Draw);
+    assert_eq!(game.turn_time_remaining(), Some(Duration::seconds(30)));
+}
+
+#[test]
+fn time_bank_covers_overtime() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(10)),
+        time_bank: Some(Duration::seconds(15)),
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::DrawAndDiscard);
+
+    clock.advance(Duration::seconds(20));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    play_turn(&mut game);
+    assert_eq!(game.time_bank(0), Some(Duration::seconds(5)));
+    assert_eq!(game.time_bank(1), Some(Duration::seconds(15)));
+
+    clock.advance(Duration::seconds(5));
+    play_turn(&mut game);
+    assert_eq!(game.time_bank(1), Some(Duration::seconds(15)));
+
+    clock.advance(Duration::seconds(14));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    clock.advance(Duration::seconds(1));
+    assert_eq!(
+        game.check_timeout().unwrap(),
+        Some(Timeout {
+            player_id: 0,
+            round: 1
+        })
+    );
+    assert_eq!(game.time_bank(0), Some(Duration::zero()));
+}
+
+#[test]
+fn time_bank_without_turn_limit() {
+    let control = TimeControl {
+        turn_limit: None,
+        time_bank: Some(Duration::seconds(60)),
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::DrawAndDiscard);
+
+    clock.advance(Duration::seconds(45));
+    play_turn(&mut game);
+    assert_eq!(game.time_bank(0), Some(Duration::seconds(15)));
+    play_turn(&mut game);
+    assert_eq!(game.turn_time_remaining(), Some(Duration::seconds(15)));
+}
+
+#[test]
+fn no_time_control_never_times_out() {
+    let (mut game, clock) = timed_game(2, TimeControl::default(), TimeoutPolicy::ForfeitRound);
+
+    clock.advance(Duration::days(365));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    assert_eq!(game.turn_time_remaining(), None);
+    assert_eq!(game.time_bank(0), None);
+}
+
+#[test]
+fn action_after_timeout_fails() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::DrawAndDiscard);
+
+    clock.advance(Duration::seconds(31));
+    let result = game.execute_action(GameAction::DrawDeck(DrawDeckAction {}));
+    assert!(matches!(
+        result,
+        Err(ActionError::FailedAction(FailedActionError::TimedOut {
+            player_id: 0
+        }))
+    ));
+    assert_eq!(current_player_id(&game), 1);
+    assert_eq!(game.get_state().phase(), GamePhase::Draw);
+}
+
+#[test]
+fn timeout_auto_plays_with_bot() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::AutoPlay(Box::new(FirstCardBot)));
+    let first_card = game.get_state().players()[0].cards()[0].data();
+
+    clock.advance(Duration::seconds(31));
+    assert!(game.check_timeout().unwrap().is_some());
+    assert_eq!(
+        game.get_state().deck().peek_discard_pile(),
+        Some(first_card)
+    );
+    assert_eq!(current_player_id(&game), 1);
+}
+
+#[test]
+fn forfeited_player_sits_out_round() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    let (mut game, clock) = timed_game(3, control, TimeoutPolicy::ForfeitRound);
+    let hand = game.get_state().players()[0].cards().clone();
+
+    clock.advance(Duration::seconds(31));
+    assert!(game.check_timeout().unwrap().is_some());
+    assert_eq!(current_player_id(&game), 1);
+    assert!(!game.get_state().players()[0].active());
+
+    play_turn(&mut game);
+    play_turn(&mut game);
+    // player 0's turns are skipped, and they keep their hand to be scored
+    assert_eq!(current_player_id(&game), 1);
+    assert_eq!(game.get_state().players()[0].cards(), &hand);
+}
+
+#[test]
+fn forfeited_player_returns_next_round() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    // nobody is dealt any cards, so the round ends as soon as a player draws and discards
+    let config = BasicConfig {
+        deal_amount: Some(0),
+        draw_deck_amount: None,
+        draw_discard_pile_amount: None,
+        quit_hand_policy: None,
+    };
+    let clock = ManualClock::new(DateTime::UNIX_EPOCH);
+    let mut game = Timed::new(
+        create_basic_game_with_config(3, Some(1), Some(config), None).unwrap(),
+        control,
+        TimeoutPolicy::ForfeitRound,
+        clock.clone(),
+    );
+    game.next_round().unwrap();
+
+    clock.advance(Duration::seconds(31));
+    assert!(game.check_timeout().unwrap().is_some());
+    play_turn(&mut game);
+    assert_eq!(game.get_state().phase(), GamePhase::RoundEnd);
+
+    game.next_round().unwrap();
+    assert!(game.get_state().players()[0].active());
+    assert_eq!(game.get_state().round_scores()[&1].winner_id, 1);
+
+    // sitting out would leave a single player in the round, so the turn is played instead
+    game.quit_player(2).unwrap();
+    clock.advance(Duration::seconds(31));
+    assert!(game.check_timeout().unwrap().is_some());
+    assert_eq!(game.get_state().phase(), GamePhase::RoundEnd);
+}
+
+#[test]
+fn clocks_freeze_while_paused() {
+    let control = TimeControl {
+        turn_limit: Some(Duration::seconds(30)),
+        time_bank: None,
+    };
+    let (mut game, clock) = timed_game(2, control, TimeoutPolicy::DrawAndDiscard);
+
+    clock.advance(Duration::seconds(20));
+    game.pause().unwrap();
+    clock.advance(Duration::minutes(5));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    assert_eq!(game.turn_time_remaining(), Some(Duration::seconds(10)));
+
+    game.resume().unwrap();
+    clock.advance(Duration::seconds(9));
+    assert_eq!(game.check_timeout().unwrap(), None);
+    clock.advance(Duration::seconds(1));
+    assert!(game.check_timeout().unwrap().is_some());
+}
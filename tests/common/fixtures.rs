@@ -2,8 +2,11 @@ use rummy::{
     cards::deck::DeckConfig,
     game::{
         error::GameSetupError,
+        r#trait::Game,
+        state::GamePhase,
         variants::basic::{config::BasicConfig, game::BasicRummyGame},
     },
+    simulation::bots::{Bot, GreedyBot},
     wrappers::{history::History, replay::Replay},
 };
 
@@ -23,6 +26,7 @@ pub fn create_basic_game(player_count: usize) -> Result<BasicRummyGame, GameSetu
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     BasicRummyGame::new(player_ids, game_config, deck_config)
 }
@@ -50,6 +54,7 @@ pub fn create_basic_game_with_config(
             deal_amount: None,
             draw_deck_amount: None,
             draw_discard_pile_amount: None,
+            quit_hand_policy: None,
         },
     };
     BasicRummyGame::new(player_ids, game_config, deck_config)
@@ -70,6 +75,7 @@ pub fn create_basic_game_with_history(
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     History::new(player_ids, config, deck_config)
 }
@@ -90,6 +96,43 @@ pub fn create_basic_game_with_replay(
         deal_amount: None,
         draw_deck_amount: None,
         draw_discard_pile_amount: None,
+        quit_hand_policy: None,
     };
     History::new(player_ids, config, deck_config).map(|g| Replay::new(g, skip_failed_actions))
 }
+
+/// Play `rounds` rounds of a basic game between greedy bots, so that every round's score is recorded.
+pub fn play_basic_game_with_bots(player_count: usize, rounds: usize) -> BasicRummyGame {
+    play_basic_history_with_bots(player_count, rounds)
+        .get_game()
+        .clone()
+}
+
+/// Play `rounds` rounds of a basic game with history between greedy bots, so that every round's score is recorded.
+///
+/// Greedy bots can go round in circles, so this tries seeds until they finish every round,
+/// panicking if none of the first 100 do.
+pub fn play_basic_history_with_bots(player_count: usize, rounds: usize) -> History<BasicRummyGame> {
+    'seeds: for seed in 1..=100 {
+        let game = create_basic_game_with_config(player_count, Some(seed), None, None).unwrap();
+        let mut game = History::from_new_game(game);
+        for _ in 0..rounds {
+            if game.next_round().is_err() {
+                continue 'seeds;
+            }
+            for _ in 0..1000 {
+                let state = game.get_state();
+                if !matches!(state.phase(), GamePhase::Draw | GamePhase::Play) {
+                    break;
+                }
+                let player_id = state.get_current_player().unwrap().id();
+                let action = GreedyBot.next_action(&game, player_id);
+                game.execute_action(action).unwrap();
+            }
+        }
+        if game.next_round().is_ok() {
+            return game;
+        }
+    }
+    panic!("greedy bots didn't finish {rounds} rounds with {player_count} players for any seed");
+}
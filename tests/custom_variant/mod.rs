@@ -0,0 +1,2 @@
+pub mod out_of_turn;
+pub mod serialization;
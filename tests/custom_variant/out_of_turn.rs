@@ -0,0 +1,131 @@
+//! A custom `Game` which allows an action out of turn, like buying the discard in Contract Rummy.
+
+use rummy::{
+    cards::{card::CardData, deck::DeckConfig},
+    game::{
+        action::{DrawDeckAction, DrawDiscardPileAction, GameAction},
+        error::{ActionError, FailedActionError, GameError},
+        r#trait::Game,
+        rules::GameRules,
+        state::GameState,
+        variants::basic::{
+            config::BasicConfig, rules::BasicRules, score::BasicScore, state::BasicState,
+        },
+    },
+    wrappers::history::History,
+};
+
+/// A custom game where players may ask to buy the discard when it isn't their turn.
+#[derive(Clone, Debug, PartialEq)]
+struct BuyingGame {
+    state: GameState<BasicScore, BasicRules>,
+    rules: BasicRules,
+    buy_requests: Vec<usize>,
+}
+
+impl BuyingGame {
+    fn new() -> Self {
+        let deck_config = DeckConfig {
+            shuffle_seed: Some(1),
+            ..DeckConfig::new()
+        };
+        let config = BasicConfig {
+            deal_amount: None,
+            draw_deck_amount: None,
+            draw_discard_pile_amount: None,
+            quit_hand_policy: None,
+        };
+        Self {
+            state: GameState::initialize(vec![0, 1, 2], deck_config, BasicState {}),
+            rules: BasicRules::new(config),
+            buy_requests: vec![],
+        }
+    }
+}
+
+impl Game for BuyingGame {
+    type Rules = BasicRules;
+
+    fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError> {
+        self.rules.execute_action(&mut self.state, action)
+    }
+
+    fn execute_out_of_turn_action(
+        &mut self,
+        player_id: usize,
+        action: GameAction,
+    ) -> Result<(), ActionError> {
+        match action {
+            GameAction::DrawDiscardPile(_) => {
+                self.buy_requests.push(player_id);
+                Ok(())
+            }
+            _ => Err(ActionError::FailedAction(FailedActionError::NotYourTurn {
+                player_id,
+                current_player_id: self.state.get_current_player()?.id(),
+            })),
+        }
+    }
+
+    fn get_state(&self) -> &GameState<BasicScore, BasicRules> {
+        &self.state
+    }
+
+    fn quit_player(&mut self, _player_id: usize) -> Result<(), GameError> {
+        Err(GameError::WrongGamePhase)
+    }
+
+    fn add_player(&mut self, _player_id: usize) -> Result<(), GameError> {
+        Err(GameError::WrongGamePhase)
+    }
+
+    fn rearrange_player_hand(
+        &mut self,
+        _player_id: usize,
+        _new_arrangement: Vec<CardData>,
+    ) -> Result<(), GameError> {
+        Err(GameError::FailedHandRearrangement)
+    }
+
+    fn next_round(&mut self) -> Result<(), GameError> {
+        self.state.start_new_round(7, 0)
+    }
+}
+
+#[test]
+fn out_of_turn_actions_are_delegated_and_recorded() {
+    let mut game = History::from_new_game(BuyingGame::new());
+    game.next_round().unwrap();
+
+    let buy = GameAction::DrawDiscardPile(DrawDiscardPileAction { count: None });
+    game.execute_action_as(2, buy.clone()).unwrap();
+    assert!(matches!(
+        game.execute_action_as(1, GameAction::DrawDeck(DrawDeckAction {})),
+        Err(ActionError::FailedAction(FailedActionError::NotYourTurn {
+            player_id: 1,
+            current_player_id: 0
+        }))
+    ));
+    game.execute_action_as(0, GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+
+    assert_eq!(game.get_game().buy_requests, vec![2]);
+    let entries = &game.get_histories()[&1];
+    let actors: Vec<_> = entries.iter().map(|e| (e.actor, e.successful)).collect();
+    assert_eq!(
+        actors,
+        vec![(Some(2), true), (Some(1), false), (Some(0), true)]
+    );
+}
+
+#[test]
+fn unsupported_operations_fail_by_default() {
+    let mut game = BuyingGame::new();
+    game.next_round().unwrap();
+    assert!(matches!(
+        game.replace_player(1, 3),
+        Err(GameError::NotSupported)
+    ));
+    assert!(matches!(game.pause(), Err(GameError::NotSupported)));
+    assert!(matches!(game.resume(), Err(GameError::NotSupported)));
+}
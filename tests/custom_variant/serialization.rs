@@ -0,0 +1,119 @@
+//! A custom `Game` built from a `GameState`, which gets (de)serialization just by deriving it.
+#![cfg(feature = "serde")]
+
+use rummy::{
+    cards::{card::CardData, deck::DeckConfig},
+    game::{
+        action::{DiscardAction, DrawDeckAction, GameAction},
+        error::{ActionError, GameError},
+        r#trait::Game,
+        rules::GameRules,
+        state::GameState,
+        variants::basic::{
+            config::BasicConfig, rules::BasicRules, score::BasicScore, state::BasicState,
+        },
+    },
+    wrappers::history::History,
+};
+use serde::{Deserialize, Serialize};
+
+/// A custom game which reuses basic Rummy's rules, but always deals 5 cards and keeps its own state.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+struct FiveCardGame {
+    state: GameState<BasicScore, BasicRules>,
+    rules: BasicRules,
+    actions_taken: usize,
+}
+
+impl FiveCardGame {
+    fn new() -> Self {
+        let deck_config = DeckConfig {
+            shuffle_seed: Some(1),
+            ..DeckConfig::new()
+        };
+        let config = BasicConfig {
+            deal_amount: Some(5),
+            draw_deck_amount: None,
+            draw_discard_pile_amount: None,
+            quit_hand_policy: None,
+        };
+        Self {
+            state: GameState::initialize(vec![0, 1], deck_config, BasicState {}),
+            rules: BasicRules::new(config),
+            actions_taken: 0,
+        }
+    }
+}
+
+impl Game for FiveCardGame {
+    type Rules = BasicRules;
+
+    fn execute_action(&mut self, action: GameAction) -> Result<(), ActionError> {
+        self.rules.execute_action(&mut self.state, action)?;
+        self.actions_taken += 1;
+        Ok(())
+    }
+
+    fn get_state(&self) -> &GameState<BasicScore, BasicRules> {
+        &self.state
+    }
+
+    fn quit_player(&mut self, _player_id: usize) -> Result<(), GameError> {
+        Err(GameError::WrongGamePhase)
+    }
+
+    fn add_player(&mut self, _player_id: usize) -> Result<(), GameError> {
+        Err(GameError::WrongGamePhase)
+    }
+
+    fn rearrange_player_hand(
+        &mut self,
+        _player_id: usize,
+        _new_arrangement: Vec<CardData>,
+    ) -> Result<(), GameError> {
+        Err(GameError::FailedHandRearrangement)
+    }
+
+    fn next_round(&mut self) -> Result<(), GameError> {
+        self.state.start_new_round(5, 0)
+    }
+}
+
+/// Start a round and play a turn.
+fn play_turn(game: &mut impl Game) {
+    game.next_round().unwrap();
+    game.execute_action(GameAction::DrawDeck(DrawDeckAction {}))
+        .unwrap();
+    game.execute_action(GameAction::Discard(DiscardAction {
+        card_index: 0,
+        declare_going_out: None,
+    }))
+    .unwrap();
+}
+
+#[test]
+fn custom_game_serialization() {
+    let mut game = FiveCardGame::new();
+    play_turn(&mut game);
+    assert_eq!(game.get_state().players()[0].cards().len(), 5);
+
+    let serialized = serde_json::to_string(&game).unwrap();
+    let deserialized: FiveCardGame = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized, game);
+    assert_eq!(deserialized.actions_taken, 2);
+}
+
+#[test]
+fn custom_game_history_serialization() {
+    let mut game = History::from_new_game(FiveCardGame::new());
+    play_turn(&mut game);
+
+    let serialized = serde_json::to_string(&game).unwrap();
+    let deserialized: History<FiveCardGame> = serde_json::from_str(&serialized).unwrap();
+    assert_eq!(deserialized.get_game(), game.get_game());
+    assert_eq!(
+        deserialized.get_initial_round_states(),
+        game.get_initial_round_states()
+    );
+    assert_eq!(deserialized.get_histories()[&1].len(), 2);
+}
@@ -1,2 +1,3 @@
 pub mod basic_rummy;
 pub mod common;
+pub mod custom_variant;